@@ -8,9 +8,12 @@ use reign_plugin::{
 
 use std::net::ToSocketAddrs;
 
+type RouterFn = Box<dyn FnOnce(&mut Router)>;
+
 #[derive(Default)]
 pub struct Reign {
     pub(crate) plugins: Vec<Box<dyn Plugin>>,
+    state: Vec<RouterFn>,
 }
 
 impl Reign {
@@ -24,12 +27,31 @@ impl Reign {
         Self::default()
     }
 
+    /// Register application state that is available to all the routes.
+    ///
+    /// See [`Router::state`] for more details.
+    pub fn state<T>(mut self, state: T) -> Self
+    where
+        T: Send + Sync + 'static,
+    {
+        self.state
+            .push(Box::new(move |r: &mut Router| r.state(state)));
+        self
+    }
+
     pub async fn serve<A, R>(self, addr: A, f: R)
     where
         A: ToSocketAddrs + Send + 'static,
         R: FnOnce(&mut Router) + 'static,
     {
-        let mut router_fn: Box<dyn FnOnce(&mut Router)> = Box::new(f);
+        let state = self.state;
+        let mut router_fn: RouterFn = Box::new(move |r: &mut Router| {
+            for register in state {
+                register(r);
+            }
+
+            f(r)
+        });
 
         for plugin in self.plugins {
             // Initialize the plugin
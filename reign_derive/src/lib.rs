@@ -41,8 +41,26 @@ pub(crate) const INTERNAL_ERR: &str =
 ///
 /// views!("src", "views");
 /// ```
+///
+/// You can also specify a default layout which wraps every view rendered with `render!`
+///
+/// ```ignore
+/// use reign::prelude::*;
+///
+/// views!("src", "views", layout = layouts::application);
+/// ```
+///
+/// The filters defined in the `filters` module of the crate need to be listed so that they
+/// can be used in the views
+///
+/// ```ignore
+/// use reign::prelude::*;
+///
+/// views!("src", "views", filters = [shout, slugify]);
+/// ```
 #[cfg(feature = "view")]
 #[proc_macro]
+#[proc_macro_error]
 pub fn views(input: TokenStream) -> TokenStream {
     let input: view::render::Views = parse_macro_input!(input);
 
@@ -68,6 +86,22 @@ pub fn views(input: TokenStream) -> TokenStream {
 ///
 /// render!(pages::home, status = 201)
 /// ```
+///
+/// You can also wrap the view in a layout, which overrides the default layout given to
+/// `views!`. Use `layout = None` to render the view without any layout.
+///
+/// ```ignore
+/// use reign::prelude::*;
+///
+/// render!(pages::home, layout = layouts::application)
+/// ```
+///
+/// The view is given to the layout as the default slot. The named templates inside the
+/// root `template` element of the view are given to the layout as the named slots.
+///
+/// When the `hot-reload` feature is enabled, debug builds interpret the template from the
+/// disk on every render instead of using the compiled view. The typed fields of the view
+/// need to implement `serde::Serialize` for this.
 #[cfg(feature = "view")]
 #[proc_macro]
 #[proc_macro_error]
@@ -77,7 +111,22 @@ pub fn render(input: TokenStream) -> TokenStream {
     view::render::render(input).into()
 }
 
-/// Helper for using path params in a [reign_router] handle.
+/// Helper for using params in a [reign_router] handle.
+///
+/// By default, arguments are path params. They can be annotated to be extracted from other
+/// parts of the request instead:
+///
+/// * `#[query]` or `#[query("name")]` for query string parameters
+/// * `#[form]` or `#[json]` for the deserialized body
+/// * `#[header]` or `#[header("x-name")]` for headers, underscores become dashes by default
+/// * `#[cookie]` or `#[cookie("name")]` for cookies
+/// * `#[extension]` for request extensions, which need to implement `Clone`
+/// * `#[state]` for application state registered on the router
+///
+/// Arguments with `Option` types are not required. If a value can't be extracted, the
+/// `reign::router::Error` is returned through the error type of the handle, which needs to
+/// implement `From` for it. The router error responds with `404` for path params, `415` for
+/// bodies with wrong content type, `500` for missing extensions or state and `400` otherwise.
 ///
 /// # Examples
 ///
@@ -88,6 +137,16 @@ pub fn render(input: TokenStream) -> TokenStream {
 /// async fn name(req: &mut Request, id: String) -> Result<impl Response, Error> {
 ///     Ok(id)
 /// }
+///
+/// #[params]
+/// async fn search(
+///     req: &mut Request,
+///     #[query] term: String,
+///     #[query] page: Option<u32>,
+///     #[header("x-version")] version: Option<u8>,
+/// ) -> Result<impl Response, Error> {
+///     Ok(format!("{} {:?} {:?}", term, page, version))
+/// }
 /// ```
 #[cfg(feature = "router")]
 #[proc_macro_attribute]
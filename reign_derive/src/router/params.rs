@@ -1,9 +1,34 @@
 use crate::{router::ty::subty_if_name, INTERNAL_ERR};
 
-use proc_macro2::TokenStream;
+use proc_macro2::{Span, TokenStream};
 use proc_macro_error::abort;
 use quote::quote;
-use syn::{spanned::Spanned, FnArg, Ident, ItemFn, LitStr, Pat, Signature, Type};
+use syn::{
+    spanned::Spanned, Attribute, FnArg, Ident, ItemFn, LitStr, Pat, PatType, Signature, Type,
+};
+
+const SOURCES: &[&str] = &[
+    "param",
+    "query",
+    "form",
+    "json",
+    "header",
+    "cookie",
+    "extension",
+    "state",
+];
+
+/// Where the value of an argument is extracted from.
+enum Source {
+    Param(LitStr),
+    Query(LitStr),
+    Form,
+    Json,
+    Header(LitStr),
+    Cookie(LitStr),
+    Extension,
+    State,
+}
 
 fn arg_ident(arg: &FnArg) -> Ident {
     if let FnArg::Typed(x) = arg {
@@ -23,6 +48,162 @@ fn arg_ty(arg: &FnArg) -> Type {
     abort!(arg.span(), "expected a typed function arg with clear ident");
 }
 
+fn is_source(attr: &Attribute) -> bool {
+    SOURCES.iter().any(|x| attr.path.is_ident(x))
+}
+
+fn name(attr: &Attribute, default: String) -> LitStr {
+    if attr.tokens.is_empty() {
+        LitStr::new(&default, Span::call_site())
+    } else {
+        attr.parse_args()
+            .unwrap_or_else(|_| abort!(attr.tokens.span(), "expected a string literal"))
+    }
+}
+
+fn arg_source(arg: &FnArg) -> Source {
+    let ident = arg_ident(arg).to_string();
+    let attrs = if let FnArg::Typed(x) = arg {
+        x.attrs.iter().filter(|x| is_source(x)).collect::<Vec<_>>()
+    } else {
+        vec![]
+    };
+
+    if attrs.len() > 1 {
+        abort!(attrs[1].span(), "expected only one source attribute");
+    }
+
+    let attr = if let Some(attr) = attrs.first() {
+        attr
+    } else {
+        return Source::Param(LitStr::new(&ident, arg.span()));
+    };
+
+    match attr
+        .path
+        .get_ident()
+        .expect(INTERNAL_ERR)
+        .to_string()
+        .as_str()
+    {
+        "param" => Source::Param(name(attr, ident)),
+        "query" => Source::Query(name(attr, ident)),
+        "header" => Source::Header(name(attr, ident.replace('_', "-"))),
+        "cookie" => Source::Cookie(name(attr, ident)),
+        "form" | "json" | "extension" | "state" if !attr.tokens.is_empty() => {
+            abort!(attr.tokens.span(), "expected no arguments")
+        }
+        "form" => Source::Form,
+        "json" => Source::Json,
+        "state" => Source::State,
+        _ => Source::Extension,
+    }
+}
+
+/// Removes the source attributes so that the arguments can be used in the inner function.
+fn strip_source(arg: &FnArg) -> FnArg {
+    let mut arg = arg.clone();
+
+    if let FnArg::Typed(PatType { attrs, .. }) = &mut arg {
+        attrs.retain(|x| !is_source(x));
+    }
+
+    arg
+}
+
+/// Typed path params were already converted when matching the route, so they are taken
+/// instead of being converted again.
+fn param(req: &Ident, lit: &LitStr, ty: Type) -> TokenStream {
+    let (fn_name, ty) = if let Some(ty) = subty_if_name(ty.clone(), "Vec") {
+        (quote! { param_glob }, ty)
+    } else if let Some(ty) = subty_if_name(ty.clone(), "Option") {
+        if let Some(ty) = subty_if_name(ty.clone(), "Vec") {
+            (quote! { param_opt_glob }, ty)
+        } else {
+            (quote! { take_param_opt }, ty)
+        }
+    } else {
+        (quote! { take_param }, ty)
+    };
+
+    quote! {
+        #req.#fn_name::<#ty>(#lit)
+    }
+}
+
+/// Extracts an optional value with the given function, which is required if the type
+/// is not an `Option`.
+fn optional(
+    req: &Ident,
+    lit: &LitStr,
+    ty: Type,
+    fn_name: TokenStream,
+    err: TokenStream,
+) -> TokenStream {
+    if let Some(ty) = subty_if_name(ty.clone(), "Option") {
+        quote! {
+            #req.#fn_name::<#ty>(#lit)
+        }
+    } else {
+        quote! {
+            #req.#fn_name::<#ty>(#lit).and_then(|x| {
+                x.ok_or_else(|| #err(#lit.into()).into())
+            })
+        }
+    }
+}
+
+fn query(req: &Ident, lit: &LitStr, ty: Type) -> TokenStream {
+    if let Some(ty) = subty_if_name(ty.clone(), "Vec") {
+        quote! {
+            #req.query_all(#lit)
+                .into_iter()
+                .map(|x| {
+                    x.parse::<#ty>().map_err(|_| {
+                        ::reign::router::QueryError::UnableToConvertQuery(#lit.into()).into()
+                    })
+                })
+                .collect::<Result<Vec<#ty>, ::reign::router::Error>>()
+        }
+    } else {
+        optional(
+            req,
+            lit,
+            ty,
+            quote! { query_as },
+            quote! { ::reign::router::QueryError::RequiredQueryNotFound },
+        )
+    }
+}
+
+fn extension(req: &Ident, ty: Type) -> TokenStream {
+    if let Some(ty) = subty_if_name(ty.clone(), "Option") {
+        quote! {
+            Ok::<_, ::reign::router::Error>(#req.extensions().get::<#ty>().cloned())
+        }
+    } else {
+        quote! {
+            #req.extensions().get::<#ty>().cloned().ok_or_else(|| {
+                ::reign::router::Error::from(
+                    ::reign::router::ExtensionError::RequiredExtensionNotFound(
+                        ::std::any::type_name::<#ty>(),
+                    ),
+                )
+            })
+        }
+    }
+}
+
+fn state(req: &Ident, ty: Type) -> TokenStream {
+    if let Some(ty) = subty_if_name(ty.clone(), "Option") {
+        quote! {
+            Ok::<_, ::reign::router::Error>(#req.state::<#ty>().ok())
+        }
+    } else {
+        quote! { #req.state::<#ty>() }
+    }
+}
+
 pub fn params(input: ItemFn) -> TokenStream {
     let ItemFn {
         attrs,
@@ -52,29 +233,40 @@ pub fn params(input: ItemFn) -> TokenStream {
 
     let req_ident = args.first().expect(INTERNAL_ERR);
     let idents = args.iter().skip(1).collect::<Vec<_>>();
+    let inner_inputs = inputs.iter().map(strip_source).collect::<Vec<_>>();
     let assignments = inputs
         .iter()
         .skip(1)
         .map(|x| {
             let ident = arg_ident(x);
-            let lit = LitStr::new(&ident.to_string(), ident.span());
             let ty = arg_ty(x);
 
-            let (fn_name, ty) = if let Some(ty) = subty_if_name(ty.clone(), "Vec") {
-                (quote! { param_glob }, ty)
-            } else if let Some(ty) = subty_if_name(ty.clone(), "Option") {
-                if let Some(ty) = subty_if_name(ty.clone(), "Vec") {
-                    (quote! { param_opt_glob }, ty)
-                } else {
-                    (quote! { param_opt }, ty)
-                }
-            } else {
-                (quote! { param }, ty)
+            let value = match arg_source(x) {
+                Source::Param(lit) => param(req_ident, &lit, ty),
+                Source::Query(lit) => query(req_ident, &lit, ty),
+                Source::Form => quote! { #req_ident.form::<#ty>().await },
+                Source::Json => quote! { #req_ident.json::<#ty>().await },
+                Source::Header(lit) => optional(
+                    req_ident,
+                    &lit,
+                    ty,
+                    quote! { header_as },
+                    quote! { ::reign::router::HeaderError::RequiredHeaderNotFound },
+                ),
+                Source::Cookie(lit) => optional(
+                    req_ident,
+                    &lit,
+                    ty,
+                    quote! { cookie_as },
+                    quote! { ::reign::router::CookieError::RequiredCookieNotFound },
+                ),
+                Source::Extension => extension(req_ident, ty),
+                Source::State => state(req_ident, ty),
             };
 
-            // TODO: Use respond on the param err instead of forwarding
+            // The errors go through the handle's error type, which converts them with `From`
             quote! {
-                let #ident = #req_ident.#fn_name::<#ty>(#lit)?;
+                let #ident = #value?;
             }
         })
         .collect::<Vec<_>>();
@@ -86,7 +278,7 @@ pub fn params(input: ItemFn) -> TokenStream {
         ) #output {
             #[inline]
             #asyncness #fn_token _call(
-                #inputs
+                #(#inner_inputs),*
             ) #output #block
 
             #(#assignments)*
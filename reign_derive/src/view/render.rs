@@ -2,12 +2,9 @@ use crate::{utils::Options, INTERNAL_ERR};
 use inflector::cases::pascalcase::to_pascal_case;
 use once_cell::sync::OnceCell;
 use proc_macro2::{Span, TokenStream};
-use proc_macro_error::abort_call_site;
+use proc_macro_error::{abort, abort_call_site};
 use quote::quote;
-#[cfg(feature = "hot-reload")]
-use serde_json::from_str;
-#[cfg(feature = "hot-reload")]
-use std::fs::read_to_string;
+use reign_view::common::Error;
 use std::{collections::HashMap, env, path::PathBuf};
 use syn::{
     parse::{Parse, ParseStream, Result},
@@ -19,18 +16,35 @@ use syn::{
 
 #[cfg(feature = "hot-reload")]
 static DIR: OnceCell<PathBuf> = OnceCell::new();
+#[cfg(feature = "hot-reload")]
+static FILTERS: OnceCell<Vec<String>> = OnceCell::new();
 static IDENTMAP: OnceCell<HashMap<String, Vec<(String, bool)>>> = OnceCell::new();
+static LAYOUT: OnceCell<Option<Vec<String>>> = OnceCell::new();
 
-// TODO: derive: Options after the paths (including changing `crate::views`)
-// Can't use parse_separated_non_empty here
+// TODO: derive: Option for changing `crate::views`
 pub struct Views {
     paths: Punctuated<LitStr, Comma>,
+    options: Options,
 }
 
 impl Parse for Views {
     fn parse(input: ParseStream) -> Result<Self> {
+        let mut paths = Punctuated::new();
+
+        // Can't use parse_separated_non_empty here because the options follow the paths
+        while input.peek(LitStr) {
+            paths.push_value(input.parse()?);
+
+            if input.peek(Comma) && input.peek2(LitStr) {
+                paths.push_punct(input.parse()?);
+            } else {
+                break;
+            }
+        }
+
         Ok(Views {
-            paths: input.parse_terminated(|i| i.parse::<LitStr>())?,
+            paths,
+            options: input.parse()?,
         })
     }
 }
@@ -41,10 +55,6 @@ pub struct Render {
 }
 
 impl Render {
-    fn id(&self) -> String {
-        self.parts().join(":")
-    }
-
     fn parts(&self) -> Vec<String> {
         self.path.iter().map(|i| format!("{}", i)).collect()
     }
@@ -59,35 +69,102 @@ impl Parse for Render {
     }
 }
 
-fn get_dir(input: Views) -> PathBuf {
+fn get_dir(input: &Views) -> PathBuf {
     let mut dir = PathBuf::from(env::var("CARGO_MANIFEST_DIR").unwrap());
 
-    for i in input.paths.into_iter() {
+    for i in input.paths.iter() {
         dir.push(i.value());
     }
 
     dir
 }
 
-#[cfg(feature = "hot-reload")]
-pub fn views(input: Views) -> TokenStream {
-    let dir = get_dir(input);
+// Cargo does not give an output directory to proc macros, so the cache lives in the
+// target directory of the workspace.
+fn cache_dir() -> Option<PathBuf> {
+    let target = match env::var("CARGO_TARGET_DIR") {
+        Ok(dir) => PathBuf::from(dir),
+        Err(_) => PathBuf::from(env::var("CARGO_MANIFEST_DIR").ok()?)
+            .ancestors()
+            .find(|x| x.join("Cargo.lock").is_file())?
+            .join("target"),
+    };
+
+    Some(
+        target
+            .join("reign")
+            .join("views")
+            .join(env::var("CARGO_PKG_NAME").ok()?),
+    )
+}
 
-    DIR.set(dir).expect(INTERNAL_ERR);
+/// Returns the parts of the view path given to the `layout` option, or `None` if the
+/// option was set to `None` to disable the layout.
+fn layout_parts(layout: &Expr) -> Option<Vec<String>> {
+    match layout {
+        Expr::Path(path) if path.path.is_ident("None") => None,
+        Expr::Path(path) if path.qself.is_none() => Some(
+            path.path
+                .segments
+                .iter()
+                .map(|x| x.ident.to_string())
+                .collect(),
+        ),
+        _ => abort!(
+            layout,
+            "expected a path referencing to a view file or `None`"
+        ),
+    }
+}
 
-    quote! {
-        pub mod views;
+fn check_layout(layout: &Expr, parts: &Option<Vec<String>>) {
+    if let Some(parts) = parts {
+        if !IDENTMAP
+            .get()
+            .expect(INTERNAL_ERR)
+            .contains_key(&parts.join(":"))
+        {
+            abort!(layout, "expected a path referencing to a view file");
+        }
     }
 }
 
-#[cfg(not(feature = "hot-reload"))]
-pub fn views(input: Views) -> TokenStream {
-    let dir = get_dir(input);
+/// Returns the names given to the `filters` option, which are the filters defined by the
+/// app in its `filters` module.
+fn filter_names(filters: &Expr) -> Vec<String> {
+    let names = match filters {
+        Expr::Array(array) => array.elems.iter().map(|x| match x {
+            Expr::Path(path) => path.path.get_ident().map(|x| x.to_string()),
+            _ => None,
+        }),
+        _ => abort!(
+            filters,
+            "expected a list of filters like `[shout, slugify]`"
+        ),
+    };
+
+    names.collect::<Option<_>>().unwrap_or_else(|| {
+        abort!(
+            filters,
+            "expected a list of filters like `[shout, slugify]`"
+        )
+    })
+}
+
+pub fn views(mut input: Views) -> TokenStream {
+    let dir = get_dir(&input);
     let mut map = HashMap::new();
 
+    let filters = input
+        .options
+        .remove("filters")
+        .map(|x| filter_names(&x))
+        .unwrap_or_default();
+
     let views = reign_view::common::recurse(
         &dir,
-        "",
+        cache_dir().as_deref(),
+        &filters,
         &mut map,
         |ident, files| {
             Ok(quote! {
@@ -96,41 +173,67 @@ pub fn views(input: Views) -> TokenStream {
                 }
             })
         },
-        |_, _, file| Ok(file),
+        |path, file_base_name, file, generated| {
+            // The included content is never used. Proc macros can't tell cargo which files
+            // they read, but `include_str!` does, so the crate is rebuilt whenever the
+            // `.html` file changes even if no rust file was touched.
+            let file_path = path.join(format!("{}.html", file_base_name));
+            let file_path = file_path.to_string_lossy();
+
+            // Spans can't point into the view, but the errors in an included file point at
+            // its lines, which follow comments with the position of the elements in the view.
+            let file = match generated {
+                Some(generated) => {
+                    let generated = generated.to_string_lossy();
+
+                    quote! {
+                        include!(#generated);
+                    }
+                }
+                None => file,
+            };
+
+            Ok(quote! {
+                #file
+                const _: &str = include_str!(#file_path);
+            })
+        },
         |_, views| Ok(views),
-    )
-    .expect(INTERNAL_ERR);
+    );
+
+    let views = match views {
+        Ok(views) => views,
+        Err(Error::Parse(e)) => {
+            // The location is a part of the message because the span can't point into the view
+            abort_call_site!("{}", e.to_string().trim_end())
+        }
+        Err(e) => abort_call_site!("unable to read the views: {}", e),
+    };
 
     IDENTMAP.set(map).expect(INTERNAL_ERR);
 
-    quote! {
-        pub mod views {
-            #(#views)*
-        }
-    }
-}
+    let layout = input.options.remove("layout").and_then(|layout| {
+        let parts = layout_parts(&layout);
 
-fn read_manifest() {
-    #[cfg(feature = "hot-reload")]
-    if let None = IDENTMAP.get() {
-        let dir = DIR.get().expect(INTERNAL_ERR).clone();
+        check_layout(&layout, &parts);
+        parts
+    });
 
-        let manifest = read_to_string(dir.join("_manifest.json"));
+    LAYOUT.set(layout).expect(INTERNAL_ERR);
 
-        if manifest.is_err() {
-            abort_call_site!("expected _manifest.json to exist and readable");
-        }
+    #[cfg(feature = "hot-reload")]
+    DIR.set(dir).expect(INTERNAL_ERR);
+    #[cfg(feature = "hot-reload")]
+    FILTERS.set(filters).expect(INTERNAL_ERR);
 
-        IDENTMAP
-            .set(from_str(&manifest.expect(INTERNAL_ERR)).expect(INTERNAL_ERR))
-            .expect(INTERNAL_ERR);
+    quote! {
+        pub mod views {
+            #(#views)*
+        }
     }
 }
 
-fn view_path(input: &Render) -> TokenStream {
-    read_manifest();
-
-    let parts = input.parts();
+fn view_path(parts: &[String]) -> TokenStream {
     let (last, elements) = parts.split_last().unwrap();
 
     let view = Ident::new(&to_pascal_case(last), Span::call_site());
@@ -144,16 +247,20 @@ fn view_path(input: &Render) -> TokenStream {
     }
 }
 
-fn capture(input: &Render) -> TokenStream {
-    let path = view_path(input);
-    let value = IDENTMAP.get().expect(INTERNAL_ERR).get(&input.id());
+fn fields(parts: &[String]) -> &'static Vec<(String, bool)> {
+    let value = IDENTMAP.get().expect(INTERNAL_ERR).get(&parts.join(":"));
 
     if value.is_none() {
         abort_call_site!("expected a path referencing to a view file");
     }
 
-    let idents: Vec<TokenStream> = value
-        .expect(INTERNAL_ERR)
+    value.expect(INTERNAL_ERR)
+}
+
+fn compiled(parts: &[String], slots: TokenStream) -> TokenStream {
+    let path = view_path(parts);
+
+    let idents: Vec<TokenStream> = fields(parts)
         .iter()
         .map(|x| {
             let ident = Ident::new(&x.0, Span::call_site());
@@ -174,27 +281,150 @@ fn capture(input: &Render) -> TokenStream {
 
     quote! {
         crate::views::#path {
-            _slots: ::reign::view::Slots::default(),
+            _slots: #slots,
             #(#idents),*
         }
     }
 }
 
-pub fn render(mut input: Render) -> TokenStream {
-    let capture = capture(&input);
+// The view is consumed inside the generated block because the layout borrows the page
+fn capture(
+    parts: &[String],
+    layout: Option<&[String]>,
+    consume: impl Fn(TokenStream) -> TokenStream,
+) -> TokenStream {
+    let default = quote! { ::reign::view::Slots::default() };
 
-    let status: Expr = input
-        .options
-        .remove("status")
-        .unwrap_or_else(|| parse_str("200").unwrap());
+    let compiled = if let Some(layout) = layout {
+        let page = Ident::new("page", Span::mixed_site());
+        let view = consume(compiled(layout, quote! { #page._content() }));
+        let content = compiled(parts, default);
 
-    if cfg!(feature = "router") {
         quote! {
-            ::reign::router::helpers::render(#capture, #status)
+            {
+                let #page = #content;
+                #view
+            }
         }
     } else {
+        consume(compiled(parts, default))
+    };
+
+    interpret(parts, layout, compiled, consume)
+}
+
+#[cfg(not(feature = "hot-reload"))]
+fn interpret(
+    _: &[String],
+    _: Option<&[String]>,
+    compiled: TokenStream,
+    _: impl Fn(TokenStream) -> TokenStream,
+) -> TokenStream {
+    compiled
+}
+
+// Debug builds interpret the template from the disk so that the changes to it are
+// visible without recompiling. Release builds always use the compiled view.
+#[cfg(feature = "hot-reload")]
+fn interpret(
+    parts: &[String],
+    layout: Option<&[String]>,
+    compiled: TokenStream,
+    consume: impl Fn(TokenStream) -> TokenStream,
+) -> TokenStream {
+    let dir = DIR.get().expect(INTERNAL_ERR).to_string_lossy().to_string();
+    let id = parts.join(":");
+
+    let filters = FILTERS.get().expect(INTERNAL_ERR);
+    let filters = (!filters.is_empty()).then(|| {
+        quote! {
+            .filters(&[#(#filters),*])
+        }
+    });
+
+    let mut all = fields(parts).clone();
+
+    let layout = layout.map(|layout| {
+        let id = layout.join(":");
+
+        for field in fields(layout) {
+            if !all.iter().any(|x| x.0 == field.0) {
+                all.push(field.clone());
+            }
+        }
+
         quote! {
-            format!("{}", #capture)
+            .layout(#id)
+        }
+    });
+
+    let fields: Vec<TokenStream> = all
+        .iter()
+        .map(|x| {
+            let name = &x.0;
+            let ident = Ident::new(name, Span::call_site());
+
+            if x.1 {
+                quote! {
+                    .field(#name, &#ident)
+                }
+            } else {
+                quote! {
+                    .field(#name, ::std::convert::AsRef::<str>::as_ref(&#ident))
+                }
+            }
+        })
+        .collect();
+
+    let interpreted = Ident::new("interpreted", Span::mixed_site());
+    let view = consume(quote! { #interpreted });
+
+    // Templates using the filters of the application can only be rendered by the compiled view
+    quote! {
+        {
+            #[cfg(debug_assertions)]
+            let rendered = match ::reign::view::interpret::View::new(#dir, #id) #layout #filters #(#fields)*
+                .interpret()
+            {
+                Some(#interpreted) => #view,
+                None => #compiled,
+            };
+
+            #[cfg(not(debug_assertions))]
+            let rendered = #compiled;
+
+            rendered
         }
     }
 }
+
+pub fn render(mut input: Render) -> TokenStream {
+    let parts = input.parts();
+
+    let layout = match input.options.remove("layout") {
+        Some(layout) => {
+            let parts = layout_parts(&layout);
+
+            check_layout(&layout, &parts);
+            parts
+        }
+        None => LAYOUT.get().cloned().flatten(),
+    };
+
+    let status: Expr = input
+        .options
+        .remove("status")
+        .unwrap_or_else(|| parse_str("200").unwrap());
+
+    capture(&parts, layout.as_deref(), |view| {
+        if cfg!(feature = "router") {
+            quote! {
+                ::reign::router::helpers::render(#view, #status)
+            }
+        } else {
+            quote! {
+                format!("{}", #view)
+            }
+        }
+    })
+}
@@ -22,6 +22,58 @@ pub enum ParamError {
     UnableToConvertParam(String),
 }
 
+/// Used in [`enum@Error`] when trying to access query string from [`Request`](crate::Request).
+#[derive(Error, Debug)]
+pub enum QueryError {
+    #[error("required query parameter `{0}` not found")]
+    RequiredQueryNotFound(String),
+    #[error("unable to convert query parameter `{0}` from string")]
+    UnableToConvertQuery(String),
+    #[error("unable to deserialize query string, {0}")]
+    UnableToDeserializeQuery(String),
+}
+
+/// Used in [`enum@Error`] when trying to access body from [`Request`](crate::Request).
+#[derive(Error, Debug)]
+pub enum BodyError {
+    #[error("unsupported content type `{0}`")]
+    UnsupportedContentType(String),
+    #[error("unable to deserialize body, {0}")]
+    UnableToDeserializeBody(String),
+}
+
+/// Used in [`enum@Error`] when trying to access headers from [`Request`](crate::Request).
+#[derive(Error, Debug)]
+pub enum HeaderError {
+    #[error("required header `{0}` not found")]
+    RequiredHeaderNotFound(String),
+    #[error("unable to convert header `{0}` from string")]
+    UnableToConvertHeader(String),
+}
+
+/// Used in [`enum@Error`] when trying to access cookies from [`Request`](crate::Request).
+#[derive(Error, Debug)]
+pub enum CookieError {
+    #[error("required cookie `{0}` not found")]
+    RequiredCookieNotFound(String),
+    #[error("unable to convert cookie `{0}` from string")]
+    UnableToConvertCookie(String),
+}
+
+/// Used in [`enum@Error`] when trying to access extensions from [`Request`](crate::Request).
+#[derive(Error, Debug)]
+pub enum ExtensionError {
+    #[error("required extension `{0}` not found")]
+    RequiredExtensionNotFound(&'static str),
+}
+
+/// Used in [`enum@Error`] when trying to access state from [`Request`](crate::Request).
+#[derive(Error, Debug)]
+pub enum StateError {
+    #[error("required state `{0}` not found")]
+    RequiredStateNotFound(&'static str),
+}
+
 /// Main error that can be used by endpoint handlers.
 ///
 /// Implements [`Response`] so that this can be converted into a valid server response.
@@ -30,6 +82,18 @@ pub enum Error {
     #[error(transparent)]
     Param(#[from] ParamError),
     #[error(transparent)]
+    Query(#[from] QueryError),
+    #[error(transparent)]
+    Body(#[from] BodyError),
+    #[error(transparent)]
+    Header(#[from] HeaderError),
+    #[error(transparent)]
+    Cookie(#[from] CookieError),
+    #[error(transparent)]
+    Extension(#[from] ExtensionError),
+    #[error(transparent)]
+    State(#[from] StateError),
+    #[error(transparent)]
     TokioIo(#[from] TokioIoError),
     #[error(transparent)]
     Utf8(#[from] Utf8Error),
@@ -51,7 +115,15 @@ impl Response for Error {
             Self::Param(_) | Self::TokioIo(_) => HyperResponse::builder()
                 .status(StatusCode::NOT_FOUND)
                 .body(Body::empty()),
-            Self::Hyper(_) | Self::Utf8(_) => HyperResponse::builder()
+            Self::Body(BodyError::UnsupportedContentType(_)) => HyperResponse::builder()
+                .status(StatusCode::UNSUPPORTED_MEDIA_TYPE)
+                .body(Body::empty()),
+            Self::Query(_)
+            | Self::Body(_)
+            | Self::Header(_)
+            | Self::Cookie(_)
+            | Self::Hyper(_)
+            | Self::Utf8(_) => HyperResponse::builder()
                 .status(StatusCode::BAD_REQUEST)
                 .body(Body::empty()),
             Self::Status(code) => HyperResponse::builder().status(code).body(Body::empty()),
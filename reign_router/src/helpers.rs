@@ -1,7 +1,19 @@
-use hyper::{header, http::Error as HttpError, Body, Response as HyperResponse, StatusCode};
+use crate::{
+    request::{mime_matches, quality},
+    Request, Response,
+};
+
+use hyper::{
+    header::{self, HeaderValue},
+    http::Error as HttpError,
+    Body, Response as HyperResponse, StatusCode,
+};
+use mime::Mime;
 
 use std::fmt::{write, Display};
 
+type Responder<'a> = Box<dyn FnOnce() -> Result<HyperResponse<Body>, HttpError> + Send + 'a>;
+
 /// Renders a view for [reign router](reign_router) endpoint handle
 ///
 /// The response is sent with content-type set as `text/html`.
@@ -223,3 +235,128 @@ pub fn json<S: serde::Serialize>(value: S, status: u16) -> Result<HyperResponse<
             .body(Body::empty()),
     }
 }
+
+/// Responds with one of the registered formats depending on the `Accept` header of the request.
+///
+/// Only the closure of the selected format is called. The first registered format is selected
+/// when the request has no `Accept` header. If none of the formats are acceptable, the response
+/// is sent with status code `406`. The response always has `vary` header set to `Accept`.
+///
+/// # Examples
+///
+/// ```
+/// use reign::prelude::*;
+/// use serde::Serialize;
+///
+/// #[derive(Serialize)]
+/// struct User {
+///     name: String,
+/// }
+///
+/// async fn handle(req: &mut Request) -> Result<impl Response, Error> {
+///     let user = User {
+///         name: "Reign".into(),
+///     };
+///
+///     Ok(respond_to(req)
+///         .html(|| render(format!("<h1>{}</h1>", user.name), 200))
+///         .json(|| json(&user, 200))
+///         .respond()?)
+/// }
+/// ```
+pub fn respond_to<'a>(req: &Request) -> RespondTo<'a> {
+    RespondTo {
+        accept: req.accept_q(),
+        formats: vec![],
+    }
+}
+
+/// Response builder returned by [`respond_to`].
+pub struct RespondTo<'a> {
+    accept: Vec<(Mime, f32)>,
+    formats: Vec<(Mime, Responder<'a>)>,
+}
+
+impl<'a> RespondTo<'a> {
+    /// Register a responder for the given media type.
+    pub fn format<F>(mut self, mime: Mime, f: F) -> Self
+    where
+        F: FnOnce() -> Result<HyperResponse<Body>, HttpError> + Send + 'a,
+    {
+        self.formats.push((mime, Box::new(f)));
+        self
+    }
+
+    /// Register a responder for `text/html`.
+    #[inline]
+    pub fn html<F>(self, f: F) -> Self
+    where
+        F: FnOnce() -> Result<HyperResponse<Body>, HttpError> + Send + 'a,
+    {
+        self.format(mime::TEXT_HTML, f)
+    }
+
+    /// Register a responder for `application/json`.
+    #[inline]
+    pub fn json<F>(self, f: F) -> Self
+    where
+        F: FnOnce() -> Result<HyperResponse<Body>, HttpError> + Send + 'a,
+    {
+        self.format(mime::APPLICATION_JSON, f)
+    }
+
+    /// Register a responder for `text/plain`.
+    #[inline]
+    pub fn text<F>(self, f: F) -> Self
+    where
+        F: FnOnce() -> Result<HyperResponse<Body>, HttpError> + Send + 'a,
+    {
+        self.format(mime::TEXT_PLAIN, f)
+    }
+
+    /// Whether the most specific range matching the format excludes it with `q=0`.
+    fn rejected(&self, mime: &Mime) -> bool {
+        quality(&self.accept, mime).map_or(false, |q| q <= 0.0)
+    }
+
+    fn select(&self) -> Option<usize> {
+        if self.accept.is_empty() {
+            return if self.formats.is_empty() {
+                None
+            } else {
+                Some(0)
+            };
+        }
+
+        for (range, q) in &self.accept {
+            if *q <= 0.0 {
+                continue;
+            }
+
+            for (i, (mime, _)) in self.formats.iter().enumerate() {
+                if mime_matches(range, mime) && !self.rejected(mime) {
+                    return Some(i);
+                }
+            }
+        }
+
+        None
+    }
+}
+
+impl<'a> Response for RespondTo<'a> {
+    fn respond(mut self) -> Result<HyperResponse<Body>, HttpError> {
+        let mut response = match self.select() {
+            Some(i) => (self.formats.swap_remove(i).1)()?,
+            None => HyperResponse::builder()
+                .status(StatusCode::NOT_ACCEPTABLE)
+                .body(Body::empty())?,
+        };
+
+        response
+            .headers_mut()
+            .append(header::VARY, HeaderValue::from_static("Accept"));
+
+        Ok(response)
+    }
+}
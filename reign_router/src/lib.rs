@@ -1,12 +1,18 @@
 #![doc(html_logo_url = "https://reign.rs/images/media/reign.png")]
 #![doc = include_str!("../README.md")]
 
+pub use chrono;
 pub use futures;
 pub use hyper;
+#[cfg(feature = "tower")]
+pub use tower_layer;
+#[cfg(feature = "uuid")]
+pub use uuid;
 
 mod error;
 mod ext;
 mod handle;
+mod mount;
 mod path;
 mod pipe;
 mod request;
@@ -17,16 +23,18 @@ mod service;
 
 pub mod helpers;
 pub mod middleware;
+pub mod test;
 
 pub use error::*;
 pub use ext::OptionExt;
 pub use handle::HandleFuture;
 #[doc(inline)]
 pub use middleware::{Chain, Middleware};
-pub use path::Path;
+pub use path::{Path, PathParam};
 pub use pipe::Pipe;
 pub use request::Request;
 pub use response::Response;
+pub use route::RouteInfo;
 pub use scope::Scope;
 pub use service::{service, Service};
 
@@ -34,17 +42,28 @@ use futures::future::ok;
 use handle::Handle;
 use hyper::{
     server::{conn::AddrStream, Server},
-    service::{make_service_fn, service_fn},
-    Error as HyperError, Method,
+    service::{make_service_fn, service_fn, Service as HyperService},
+    Body, Error as HyperError, Method, Request as HyperRequest, Response as HyperResponse,
 };
+use mount::{Mounted, MOUNT_PARAM};
 use pipe::MiddlewareItem;
-use route::{Constraint, Route};
+use route::{Constraint, Route, Rule};
 use service::RouteRef;
 
 use log::trace;
 use paste::paste;
 
-use std::{collections::HashMap as Map, convert::Infallible, net::ToSocketAddrs};
+use std::{
+    any::{type_name, Any, TypeId},
+    collections::HashMap as Map,
+    convert::Infallible,
+    error::Error as StdError,
+    net::ToSocketAddrs,
+    sync::Arc,
+};
+
+/// Application state stored by type.
+pub(crate) type State = Map<TypeId, Arc<dyn Any + Send + Sync>>;
 
 pub(crate) const INTERNAL_ERR: &str =
     "Internal error on reign_router. Please create an issue on https://github.com/pksunkara/reign";
@@ -106,6 +125,7 @@ pub struct Router {
     pipes: Map<String, Pipe>,
     scopes: Vec<Scope>,
     routes: Vec<Route>,
+    state: State,
 }
 
 impl Router {
@@ -130,6 +150,36 @@ impl Router {
         self.pipes.get_mut(&name).expect(INTERNAL_ERR)
     }
 
+    /// Register application state that can be retrieved using [`Request::state`].
+    ///
+    /// State is available to the routes defined in this router and in the scopes under it. A
+    /// scope can register state of the same type to override it for its routes.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use reign::router::Router;
+    /// # use reign::prelude::*;
+    /// #
+    /// # async fn foo(req: &mut Request) -> Result<impl Response, Error> { Ok("foo") }
+    ///
+    /// #[derive(Clone)]
+    /// struct Config {
+    ///     name: String,
+    /// }
+    ///
+    /// fn router(r: &mut Router) {
+    ///     r.state(Config { name: "reign".into() });
+    ///     r.get("foo", foo);
+    /// }
+    /// ```
+    pub fn state<T>(&mut self, state: T)
+    where
+        T: Send + Sync + 'static,
+    {
+        self.state.insert(TypeId::of::<T>(), Arc::new(state));
+    }
+
     /// Define a scope with the given prefix.
     ///
     /// # Examples
@@ -279,24 +329,169 @@ impl Router {
         self.routes
             .push(Route::new(path).constraint(constraint).handle(handle));
     }
+
+    /// Forward all requests under the given prefix to another service.
+    ///
+    /// The service can be another reign [`Service`] or any [`hyper::service::Service`] (which
+    /// is the same as `tower::Service`). The prefix is stripped from the path of the request
+    /// and the pipes of the scopes this is defined under still run around the service.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use reign::router::{
+    ///     hyper::{service::service_fn, Body, Request, Response},
+    ///     service, Router,
+    /// };
+    /// # use reign::prelude::*;
+    /// #
+    /// # async fn users(req: &mut reign::router::Request) -> Result<impl reign::router::Response, Error> { Ok("users") }
+    /// use std::convert::Infallible;
+    ///
+    /// fn router(r: &mut Router) {
+    ///     r.mount(
+    ///         "admin",
+    ///         service(|r| {
+    ///             r.get("users", users);
+    ///         }),
+    ///     );
+    ///
+    ///     r.mount(
+    ///         "metrics",
+    ///         service_fn(|_: Request<Body>| async {
+    ///             Ok::<_, Infallible>(Response::new(Body::from("metrics")))
+    ///         }),
+    ///     );
+    /// }
+    /// ```
+    pub fn mount<P, S>(&mut self, prefix: P, service: S)
+    where
+        P: Into<Path>,
+        S: HyperService<HyperRequest<Body>, Response = HyperResponse<Body>>
+            + Clone
+            + Send
+            + 'static,
+        S::Error: Into<Box<dyn StdError + Send + Sync>>,
+        S::Future: Send,
+    {
+        let path = prefix.into().param_opt_regex(MOUNT_PARAM, ".+");
+
+        self.routes.push(
+            Route::new(path)
+                .handle(Mounted::new(service))
+                .name(type_name::<S>()),
+        );
+    }
 }
 
 impl Router {
-    pub(crate) fn regex(&self) -> Vec<(String, String)> {
+    /// List the routes defined in this router in the order they are matched.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use reign::router::Router;
+    /// # use reign::prelude::*;
+    /// #
+    /// # async fn foo(req: &mut Request) -> Result<impl Response, Error> { Ok("foo") }
+    ///
+    /// let mut r = Router::default();
+    ///
+    /// r.scope("api").to(|r| {
+    ///     r.get("foo", foo);
+    /// });
+    ///
+    /// assert_eq!(r.routes()[0].path, "/api/foo");
+    /// assert_eq!(r.routes()[0].methods, vec!["GET"]);
+    /// ```
+    pub fn routes(&self) -> Vec<RouteInfo> {
+        let mut routes = self.infos();
+
+        for route in &mut routes {
+            if route.path.is_empty() {
+                route.path.push('/');
+            }
+        }
+
+        routes
+    }
+
+    fn infos(&self) -> Vec<RouteInfo> {
+        let mut routes = self
+            .routes
+            .iter()
+            .map(|x| RouteInfo {
+                methods: x.methods.iter().map(|x| x.to_string()).collect(),
+                path: x.path.pattern(),
+                name: x.name.map(|x| x.to_string()),
+                pipes: vec![],
+                constraints: x.constraint.iter().map(|_| "custom".into()).collect(),
+            })
+            .collect::<Vec<_>>();
+
+        for scope in &self.scopes {
+            let path = scope.path.pattern();
+
+            for mut route in scope.router.infos() {
+                let mut pipes = scope.pipes.clone();
+                let mut constraints = scope
+                    .rules
+                    .iter()
+                    .map(|x| x.to_string())
+                    .chain(scope.constraint.iter().map(|_| "custom".into()))
+                    .collect::<Vec<_>>();
+
+                for rule in &scope.rules {
+                    if let Rule::Methods(allowed) = &**rule {
+                        if route.methods.is_empty() {
+                            route.methods = allowed.iter().map(|x| x.to_string()).collect();
+                        } else {
+                            route
+                                .methods
+                                .retain(|x| allowed.iter().any(|y| y.as_str() == x));
+                        }
+                    }
+                }
+
+                pipes.extend(route.pipes);
+                constraints.extend(route.constraints);
+
+                routes.push(RouteInfo {
+                    path: format!("{}{}", path, route.path),
+                    pipes,
+                    constraints,
+                    ..route
+                });
+            }
+        }
+
+        routes
+    }
+
+    pub(crate) fn regex(&self) -> Vec<String> {
         let mut regexes = self.routes.iter().map(|x| x.regex()).collect::<Vec<_>>();
 
         for scope in &self.scopes {
             let scope_regex = scope.regex();
 
             for route_regex in scope_regex.1 {
-                regexes.push((route_regex.0, format!("{}{}", scope_regex.0, route_regex.1)))
+                regexes.push(format!("{}{}", scope_regex.0, route_regex))
             }
         }
 
         regexes
     }
 
-    pub(crate) fn refs(&self, upper_pipes: Map<&String, &Pipe>) -> Vec<RouteRef> {
+    pub(crate) fn refs(
+        &self,
+        upper_pipes: Map<&String, &Pipe>,
+        upper_state: &State,
+    ) -> Vec<RouteRef> {
+        let mut state = upper_state.clone();
+        state.extend(self.state.iter().map(|(k, v)| (*k, v.clone())));
+
+        let shared = Arc::new(state.clone());
+
         let mut routes = self
             .routes
             .iter()
@@ -304,6 +499,10 @@ impl Router {
                 handle: x.handle.clone(),
                 middlewares: vec![],
                 constraints: vec![x.constraint.clone()],
+                typed: x.path.typed(),
+                methods: x.methods.clone(),
+                rules: vec![],
+                state: shared.clone(),
             })
             .collect::<Vec<_>>();
 
@@ -311,7 +510,7 @@ impl Router {
         pipes.extend(&self.pipes);
 
         for scope in &self.scopes {
-            let scope_ref = scope.refs(pipes.clone());
+            let scope_ref = scope.refs(pipes.clone(), &state);
 
             for route_ref in scope_ref.1 {
                 let mut constraints = vec![scope_ref.0.clone()];
@@ -327,13 +526,22 @@ impl Router {
                     })
                     .collect::<Vec<_>>();
 
+                let mut typed = scope.path.typed();
+                let mut rules = scope.rules.clone();
+
                 constraints.extend(route_ref.constraints.into_iter());
                 middlewares.extend(route_ref.middlewares.into_iter());
+                typed.extend(route_ref.typed);
+                rules.extend(route_ref.rules);
 
                 routes.push(RouteRef {
                     handle: route_ref.handle.clone(),
                     middlewares,
                     constraints,
+                    typed,
+                    methods: route_ref.methods,
+                    rules,
+                    state: route_ref.state,
                 })
             }
         }
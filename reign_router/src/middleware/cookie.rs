@@ -19,7 +19,7 @@ impl CookieParser {
         Self::default()
     }
 
-    pub(crate) fn parse(&self, req: &mut Request) -> CookieJar {
+    pub(crate) fn parse(&self, req: &Request) -> CookieJar {
         req.headers()
             .get_all(COOKIE)
             .iter()
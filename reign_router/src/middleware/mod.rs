@@ -56,8 +56,12 @@ mod runtime;
 pub mod cookie;
 #[cfg(feature = "session")]
 pub mod session;
+#[cfg(feature = "tower")]
+mod tower;
 
 pub use content_type::ContentType;
 pub use headers_default::HeadersDefault;
 pub use request_logger::RequestLogger;
 pub use runtime::Runtime;
+#[cfg(feature = "tower")]
+pub use tower::{Next, Tower};
@@ -0,0 +1,312 @@
+use crate::{futures::FutureExt, middleware::session::SessionBackend};
+
+use log::{error, trace};
+use tokio::{fs, time::interval};
+
+use std::{
+    convert::TryInto,
+    future::Future,
+    path::{Path, PathBuf},
+    pin::Pin,
+    sync::{
+        atomic::{AtomicBool, AtomicUsize, Ordering},
+        Arc, Weak,
+    },
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+
+const EXPIRY_LEN: usize = 8;
+const EXTENSION: &str = "session";
+
+// Keeps the temporary files of concurrent writes to the same session apart
+static WRITES: AtomicUsize = AtomicUsize::new(0);
+
+/// Filesystem backend for session data.
+///
+/// Every session is stored as a `.session` file in the given directory, prefixed with its
+/// expiry time. Expired sessions are dropped by a background task which is spawned on the
+/// first write and stops when the backend is dropped. Other files in the directory are
+/// left alone.
+///
+/// # Examples
+///
+/// ```
+/// use reign::router::{
+///     middleware::session::{FileBackend, Session},
+///     Router,
+/// };
+/// use serde::{Deserialize, Serialize};
+///
+/// #[derive(Serialize, Deserialize)]
+/// pub struct User(String);
+///
+/// fn router(r: &mut Router) {
+///     r.pipe("common")
+///         .add(Session::<User, _>::new(FileBackend::new(60 * 60, "tmp/sessions")));
+/// }
+/// ```
+pub struct FileBackend {
+    ttl: Duration,
+    sweep: Duration,
+    sweeping: AtomicBool,
+    dir: Arc<PathBuf>,
+}
+
+impl FileBackend {
+    /// Instantiates the backend with the given time to live (in seconds) for the sessions and
+    /// the directory in which they are stored.
+    pub fn new<P>(ttl: usize, dir: P) -> Self
+    where
+        P: Into<PathBuf>,
+    {
+        Self {
+            ttl: Duration::from_secs(ttl as u64),
+            sweep: Duration::from_secs(60),
+            sweeping: AtomicBool::new(false),
+            dir: Arc::new(dir.into()),
+        }
+    }
+
+    /// Instantiates the backend with a week as the time to live for the sessions.
+    pub fn dir<P>(dir: P) -> Self
+    where
+        P: Into<PathBuf>,
+    {
+        Self::new(60 * 60 * 24 * 7, dir)
+    }
+
+    /// Sets the interval (in seconds) at which the expired sessions are dropped.
+    #[inline]
+    pub fn sweep(mut self, sweep: usize) -> Self {
+        self.sweep = Duration::from_secs(sweep as u64);
+        self
+    }
+
+    fn start_sweeper(&self) {
+        if self.sweeping.swap(true, Ordering::SeqCst) {
+            return;
+        }
+
+        let dir = Arc::downgrade(&self.dir);
+        let mut interval = interval(self.sweep);
+
+        tokio::spawn(async move {
+            loop {
+                interval.tick().await;
+
+                if !sweep(&dir).await {
+                    break;
+                }
+            }
+        });
+    }
+
+    /// Session identifiers come from the cookie and must not be able to escape the directory.
+    fn file(&self, identifier: &str) -> Option<PathBuf> {
+        if identifier.is_empty()
+            || !identifier
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_')
+        {
+            return None;
+        }
+
+        Some(self.dir.join(identifier).with_extension(EXTENSION))
+    }
+}
+
+fn now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0)
+}
+
+fn unpack(data: &[u8]) -> Option<(u64, &[u8])> {
+    if data.len() < EXPIRY_LEN {
+        return None;
+    }
+
+    let (expiry, content) = data.split_at(EXPIRY_LEN);
+
+    Some((u64::from_be_bytes(expiry.try_into().ok()?), content))
+}
+
+async fn sweep(dir: &Weak<PathBuf>) -> bool {
+    let dir = match dir.upgrade() {
+        Some(dir) => dir,
+        None => return false,
+    };
+
+    let mut entries = match fs::read_dir(&*dir).await {
+        Ok(entries) => entries,
+        Err(e) => {
+            error!("Failed to read session directory, {}", e);
+            return true;
+        }
+    };
+
+    let now = now();
+
+    while let Ok(Some(entry)) = entries.next_entry().await {
+        let path = entry.path();
+
+        if path.extension().map_or(true, |x| x != EXTENSION) {
+            continue;
+        }
+
+        if let Ok(data) = fs::read(&path).await {
+            if unpack(&data).map_or(true, |(expiry, _)| expiry <= now) {
+                trace!("Sweeping expired session {:?}", path);
+                remove(&path).await;
+            }
+        }
+    }
+
+    true
+}
+
+async fn remove(path: &Path) {
+    if let Err(e) = fs::remove_file(path).await {
+        error!("Failed to remove session file, {}", e);
+    }
+}
+
+impl SessionBackend for FileBackend {
+    fn persist_session<'a>(
+        &'a self,
+        identifier: &'a str,
+        content: &'a [u8],
+    ) -> Pin<Box<dyn Future<Output = bool> + Send + 'a>> {
+        self.start_sweeper();
+
+        async move {
+            let path = match self.file(identifier) {
+                Some(path) => path,
+                None => return false,
+            };
+
+            let mut data = Vec::with_capacity(EXPIRY_LEN + content.len());
+
+            data.extend_from_slice(&(now() + self.ttl.as_secs()).to_be_bytes());
+            data.extend_from_slice(content);
+
+            if let Err(e) = fs::create_dir_all(&*self.dir).await {
+                error!("Failed to create session directory, {}", e);
+                return false;
+            }
+
+            // Written to a temporary file first so that the sweeper never sees a partial session
+            let temp = path.with_extension(format!(
+                "{}.tmp-{}",
+                EXTENSION,
+                WRITES.fetch_add(1, Ordering::Relaxed)
+            ));
+
+            if let Err(e) = fs::write(&temp, data).await {
+                error!("Failed to write session file, {}", e);
+                return false;
+            }
+
+            if let Err(e) = fs::rename(&temp, &path).await {
+                error!("Failed to write session file, {}", e);
+                remove(&temp).await;
+                return false;
+            }
+
+            true
+        }
+        .boxed()
+    }
+
+    fn read_session<'a>(
+        &'a self,
+        identifier: &'a str,
+    ) -> Pin<Box<dyn Future<Output = Option<Vec<u8>>> + Send + 'a>> {
+        async move {
+            let data = fs::read(self.file(identifier)?).await.ok()?;
+            let (expiry, content) = unpack(&data)?;
+
+            if expiry <= now() {
+                return None;
+            }
+
+            Some(content.to_vec())
+        }
+        .boxed()
+    }
+
+    fn drop_session<'a>(
+        &'a self,
+        identifier: &'a str,
+    ) -> Pin<Box<dyn Future<Output = ()> + Send + 'a>> {
+        async move {
+            if let Some(path) = self.file(identifier) {
+                remove(&path).await;
+            }
+        }
+        .boxed()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    use std::env::temp_dir;
+
+    fn backend(ttl: usize, name: &str) -> FileBackend {
+        FileBackend::new(ttl, temp_dir().join("reign_router_sessions").join(name))
+    }
+
+    #[tokio::test]
+    async fn test_persist_and_read() {
+        let backend = backend(60, "persist");
+
+        assert!(backend.persist_session("id", b"data").await);
+        assert_eq!(backend.read_session("id").await, Some(b"data".to_vec()));
+        assert_eq!(backend.read_session("other").await, None);
+    }
+
+    #[tokio::test]
+    async fn test_drop() {
+        let backend = backend(60, "drop");
+
+        backend.persist_session("id", b"data").await;
+        backend.drop_session("id").await;
+
+        assert_eq!(backend.read_session("id").await, None);
+    }
+
+    #[tokio::test]
+    async fn test_expired() {
+        let backend = backend(0, "expired");
+
+        backend.persist_session("id", b"data").await;
+
+        assert_eq!(backend.read_session("id").await, None);
+    }
+
+    #[tokio::test]
+    async fn test_sweep() {
+        let backend = backend(0, "sweep");
+
+        backend.persist_session("id", b"data").await;
+
+        fs::write(backend.dir.join("notes.txt"), b"kept")
+            .await
+            .unwrap();
+
+        assert!(sweep(&Arc::downgrade(&backend.dir)).await);
+        assert!(!backend.dir.join("id.session").exists());
+        assert!(backend.dir.join("notes.txt").exists());
+    }
+
+    #[tokio::test]
+    async fn test_invalid_identifier() {
+        let backend = backend(60, "invalid");
+
+        assert!(!backend.persist_session("../id", b"data").await);
+        assert_eq!(backend.read_session("../id").await, None);
+    }
+}
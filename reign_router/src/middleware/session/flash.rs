@@ -0,0 +1,184 @@
+use crate::{
+    futures::FutureExt,
+    middleware::session::{Session, SessionBackend, SessionData},
+    Chain, HandleFuture, Middleware, Request,
+};
+
+use std::sync::{Arc, Mutex};
+
+type Messages = Vec<(String, String)>;
+
+tokio::task_local! {
+    static FLASHES: Arc<Mutex<Flashes>>;
+}
+
+/// Flash messages that were set in the previous request.
+///
+/// # Examples
+///
+/// ```
+/// use reign::prelude::*;
+///
+/// async fn foo(req: &mut Request) -> Result<impl Response, Error> {
+///     let flashes = req.flashes();
+///
+///     Ok(flashes.get("notice").unwrap_or("No notice").to_string())
+/// }
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Flashes(Messages);
+
+impl Flashes {
+    /// Returns the flash messages available to the request that is currently being handled.
+    ///
+    /// Useful in views so that the handlers don't need to pass the messages to them. The
+    /// messages are empty once they are retrieved with `req.flashes()`.
+    ///
+    /// ```html
+    /// <p class="notice" !for="(_, msg) in reign::router::middleware::session::Flashes::current().iter()">
+    ///   {{ msg }}
+    /// </p>
+    /// ```
+    pub fn current() -> Self {
+        FLASHES
+            .try_with(|flashes| flashes.lock().map(|x| x.clone()).unwrap_or_default())
+            .unwrap_or_default()
+    }
+
+    /// Returns the message stored for the given key.
+    pub fn get(&self, key: &str) -> Option<&str> {
+        self.0
+            .iter()
+            .find(|(k, _)| k == key)
+            .map(|(_, message)| message.as_str())
+    }
+
+    /// Returns an iterator over the keys and messages.
+    pub fn iter(&self) -> impl Iterator<Item = (&str, &str)> {
+        self.0.iter().map(|(k, m)| (k.as_str(), m.as_str()))
+    }
+
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    pub(crate) fn insert(&mut self, key: String, message: String) {
+        if let Some(existing) = self.0.iter_mut().find(|(k, _)| *k == key) {
+            existing.1 = message;
+        } else {
+            self.0.push((key, message));
+        }
+    }
+}
+
+/// Flash messages of the request that is currently being handled.
+///
+/// The incoming messages are shared with [`Flashes::current`].
+#[derive(Default)]
+pub(crate) struct FlashState {
+    pub(crate) incoming: Arc<Mutex<Flashes>>,
+    pub(crate) outgoing: Flashes,
+}
+
+/// Stores flash messages in a session so that they are available in the next request.
+///
+/// # Examples
+///
+/// ```
+/// use reign::router::{
+///     middleware::session::{Flash, MemoryBackend},
+///     Router,
+/// };
+///
+/// fn router(r: &mut Router) {
+///     r.pipe("common").add(Flash::new(MemoryBackend::default()));
+/// }
+/// ```
+pub struct Flash<'a, B>
+where
+    B: SessionBackend + Send + Sync,
+{
+    session: Session<'a, Messages, B>,
+}
+
+impl<'a, B> Flash<'a, B>
+where
+    B: SessionBackend + Send + Sync,
+{
+    /// Instantiates the middleware with the backend for the flash session.
+    pub fn new(backend: B) -> Self {
+        Self {
+            session: Session::new(backend).name("_reign_flash"),
+        }
+    }
+
+    /// Configures the underlying session which is used to store the messages.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use reign::router::{
+    ///     middleware::session::{Flash, MemoryBackend},
+    ///     Router,
+    /// };
+    ///
+    /// fn router(r: &mut Router) {
+    ///     r.pipe("common")
+    ///         .add(Flash::new(MemoryBackend::default()).session(|s| s.secure(false)));
+    /// }
+    /// ```
+    pub fn session<F>(mut self, f: F) -> Self
+    where
+        F: FnOnce(Session<'a, Messages, B>) -> Session<'a, Messages, B>,
+    {
+        self.session = f(self.session);
+        self
+    }
+}
+
+impl<'a, B> Middleware for Flash<'a, B>
+where
+    B: SessionBackend + Send + Sync,
+{
+    fn handle<'m>(&'m self, req: &'m mut Request, chain: Chain<'m>) -> HandleFuture<'m> {
+        let id = self.session.identifier(req);
+
+        async move {
+            let loaded = self.session.read_session(req, &id).await;
+
+            // Messages are only available for one request, so we consume them right away
+            let incoming = match req.extensions_mut().remove::<SessionData<Messages>>() {
+                Some(SessionData::Clean(messages)) => Flashes(messages),
+                _ => Flashes::default(),
+            };
+
+            let incoming = Arc::new(Mutex::new(incoming));
+
+            req.extensions_mut().insert(SessionData::<Messages>::None);
+            req.extensions_mut().insert(FlashState {
+                incoming: incoming.clone(),
+                outgoing: Flashes::default(),
+            });
+
+            let mut response = FLASHES.scope(incoming, chain.run(req)).await?;
+
+            if let Some(state) = req.extensions_mut().remove::<FlashState>() {
+                if !state.outgoing.is_empty() {
+                    req.extensions_mut()
+                        .insert(SessionData::Dirty(state.outgoing.0));
+                }
+            }
+
+            self.session.write_session(req, &mut response, loaded).await;
+
+            Ok(response)
+        }
+        .boxed()
+    }
+}
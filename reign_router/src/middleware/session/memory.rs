@@ -0,0 +1,196 @@
+use crate::{futures::FutureExt, middleware::session::SessionBackend, INTERNAL_ERR};
+
+use log::trace;
+use tokio::time::interval;
+
+use std::{
+    collections::HashMap as Map,
+    future::Future,
+    pin::Pin,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc, Mutex, Weak,
+    },
+    time::{Duration, Instant},
+};
+
+type Store = Mutex<Map<String, (Instant, Vec<u8>)>>;
+
+/// In-memory backend for session data.
+///
+/// Useful in tests and single-node deployments. Expired sessions are dropped by a
+/// background task which is spawned on the first write and stops when the backend is dropped.
+///
+/// # Examples
+///
+/// ```
+/// use reign::router::{
+///     middleware::session::{MemoryBackend, Session},
+///     Router,
+/// };
+/// use serde::{Deserialize, Serialize};
+///
+/// #[derive(Serialize, Deserialize)]
+/// pub struct User(String);
+///
+/// fn router(r: &mut Router) {
+///     r.pipe("common")
+///         .add(Session::<User, _>::new(MemoryBackend::new(60 * 60)));
+/// }
+/// ```
+pub struct MemoryBackend {
+    ttl: Duration,
+    sweep: Duration,
+    sweeping: AtomicBool,
+    store: Arc<Store>,
+}
+
+impl MemoryBackend {
+    /// Instantiates the backend with the given time to live (in seconds) for the sessions.
+    pub fn new(ttl: usize) -> Self {
+        Self {
+            ttl: Duration::from_secs(ttl as u64),
+            sweep: Duration::from_secs(60),
+            sweeping: AtomicBool::new(false),
+            store: Arc::new(Mutex::new(Map::new())),
+        }
+    }
+
+    /// Sets the interval (in seconds) at which the expired sessions are dropped.
+    #[inline]
+    pub fn sweep(mut self, sweep: usize) -> Self {
+        self.sweep = Duration::from_secs(sweep as u64);
+        self
+    }
+
+    fn start_sweeper(&self) {
+        if self.sweeping.swap(true, Ordering::SeqCst) {
+            return;
+        }
+
+        let store = Arc::downgrade(&self.store);
+        let mut interval = interval(self.sweep);
+
+        tokio::spawn(async move {
+            loop {
+                interval.tick().await;
+
+                if !sweep(&store) {
+                    break;
+                }
+            }
+        });
+    }
+}
+
+impl Default for MemoryBackend {
+    fn default() -> Self {
+        Self::new(60 * 60 * 24 * 7)
+    }
+}
+
+fn sweep(store: &Weak<Store>) -> bool {
+    if let Some(store) = store.upgrade() {
+        let now = Instant::now();
+        let mut store = store.lock().expect(INTERNAL_ERR);
+        let before = store.len();
+
+        store.retain(|_, (expiry, _)| *expiry > now);
+        trace!("Swept {} expired sessions", before - store.len());
+
+        true
+    } else {
+        false
+    }
+}
+
+impl SessionBackend for MemoryBackend {
+    fn persist_session<'a>(
+        &'a self,
+        identifier: &'a str,
+        content: &'a [u8],
+    ) -> Pin<Box<dyn Future<Output = bool> + Send + 'a>> {
+        self.start_sweeper();
+
+        self.store.lock().expect(INTERNAL_ERR).insert(
+            identifier.to_string(),
+            (Instant::now() + self.ttl, content.to_vec()),
+        );
+
+        async { true }.boxed()
+    }
+
+    fn read_session<'a>(
+        &'a self,
+        identifier: &'a str,
+    ) -> Pin<Box<dyn Future<Output = Option<Vec<u8>>> + Send + 'a>> {
+        let data = self
+            .store
+            .lock()
+            .expect(INTERNAL_ERR)
+            .get(identifier)
+            .filter(|(expiry, _)| *expiry > Instant::now())
+            .map(|(_, data)| data.clone());
+
+        async { data }.boxed()
+    }
+
+    fn drop_session<'a>(
+        &'a self,
+        identifier: &'a str,
+    ) -> Pin<Box<dyn Future<Output = ()> + Send + 'a>> {
+        self.store.lock().expect(INTERNAL_ERR).remove(identifier);
+
+        async {}.boxed()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_persist_and_read() {
+        let backend = MemoryBackend::new(60);
+
+        assert!(backend.persist_session("id", b"data").await);
+        assert_eq!(backend.read_session("id").await, Some(b"data".to_vec()));
+        assert_eq!(backend.read_session("other").await, None);
+    }
+
+    #[tokio::test]
+    async fn test_drop() {
+        let backend = MemoryBackend::new(60);
+
+        backend.persist_session("id", b"data").await;
+        backend.drop_session("id").await;
+
+        assert_eq!(backend.read_session("id").await, None);
+    }
+
+    #[tokio::test]
+    async fn test_expired() {
+        let backend = MemoryBackend::new(0);
+
+        backend.persist_session("id", b"data").await;
+
+        assert_eq!(backend.read_session("id").await, None);
+    }
+
+    #[tokio::test]
+    async fn test_sweep() {
+        let backend = MemoryBackend::new(0);
+
+        backend.persist_session("id", b"data").await;
+
+        assert!(sweep(&Arc::downgrade(&backend.store)));
+        assert!(backend.store.lock().unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_sweep_dropped() {
+        let store = Arc::downgrade(&MemoryBackend::new(0).store);
+
+        assert!(!sweep(&store));
+    }
+}
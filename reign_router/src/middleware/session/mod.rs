@@ -7,6 +7,7 @@ use crate::{
 
 use base64::{encode_config, URL_SAFE_NO_PAD};
 use bincode::{deserialize, serialize};
+use chrono::{DateTime, Utc};
 use futures::FutureExt;
 use hyper::{header::SET_COOKIE, Body, Response};
 use log::trace;
@@ -19,11 +20,21 @@ use serde::{Deserialize, Serialize};
 
 use std::{
     future::Future,
+    marker::PhantomData,
     pin::Pin,
     sync::{Arc, Mutex, PoisonError},
+    time::{Duration, SystemTime, UNIX_EPOCH},
 };
 
+mod file;
+mod flash;
+mod memory;
+
 pub use cookie::SameSite;
+pub use file::FileBackend;
+pub(crate) use flash::FlashState;
+pub use flash::{Flash, Flashes};
+pub use memory::MemoryBackend;
 
 const INTERNAL_ERR: &str =
     "Internal error on reign_router/middleware/session. Please create an issue on https://github.com/pksunkara/reign";
@@ -62,6 +73,47 @@ where
     None,
 }
 
+/// Marks that the identifier of the session should be rotated when the response is written.
+pub(crate) struct SessionRegenerate<T>(PhantomData<T>)
+where
+    T: Serialize + for<'de> Deserialize<'de> + Send + Sync + 'static;
+
+impl<T> SessionRegenerate<T>
+where
+    T: Serialize + for<'de> Deserialize<'de> + Send + Sync + 'static,
+{
+    pub(crate) fn new() -> Self {
+        Self(PhantomData)
+    }
+}
+
+/// Determines how long a session lives.
+///
+/// When set, the session cookie is sent with `Max-Age` and `Expires` attributes and
+/// the middleware stops accepting the session once it has expired.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Expiry {
+    /// Session expires after the given number of seconds of inactivity. Every request
+    /// that reads the session refreshes it.
+    Sliding(u64),
+    /// Session expires after the given number of seconds since it was created irrespective
+    /// of any activity.
+    Absolute(u64),
+}
+
+/// Session state that was loaded from the backend for the current request.
+struct Loaded {
+    id: String,
+    expires: Option<u64>,
+}
+
+fn now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0)
+}
+
 /// Manages the session lifecycle.
 pub struct Session<'a, T, B>
 where
@@ -74,9 +126,10 @@ where
     same_site: SameSite,
     path: &'a str,
     domain: Option<&'a str>,
+    expiry: Option<Expiry>,
     backend: B,
     rng: Arc<Mutex<ReseedingRng<ChaChaCore, OsRng>>>,
-    phantom: std::marker::PhantomData<T>,
+    phantom: PhantomData<T>,
 }
 
 impl<'a, T, B> Session<'a, T, B>
@@ -88,15 +141,18 @@ where
     ///
     /// # Examples
     ///
-    /// ```ignore
-    /// use reign::router::{Router, middleware::session::Session};
-    /// use serde::{Serialize, Deserialize};
+    /// ```
+    /// use reign::router::{
+    ///     middleware::session::{MemoryBackend, Session},
+    ///     Router,
+    /// };
+    /// use serde::{Deserialize, Serialize};
     ///
     /// #[derive(Serialize, Deserialize)]
     /// pub struct User(String);
     ///
     /// fn router(r: &mut Router) {
-    ///     r.pipe("common").add(Session::<User, _>::new(MemoryBackend::new()));
+    ///     r.pipe("common").add(Session::<User, _>::new(MemoryBackend::default()));
     /// }
     /// ```
     pub fn new(backend: B) -> Self {
@@ -107,13 +163,14 @@ where
             same_site: SameSite::Lax,
             domain: None,
             path: "/",
+            expiry: None,
             backend,
             rng: Arc::new(Mutex::new(ReseedingRng::new(
                 ChaChaCore::from_entropy(),
                 32_768,
                 OsRng,
             ))),
-            phantom: std::marker::PhantomData,
+            phantom: PhantomData,
         }
     }
 
@@ -153,6 +210,32 @@ where
         self
     }
 
+    /// Sets the expiry of the session.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use reign::router::{
+    ///     middleware::session::{Expiry, MemoryBackend, Session},
+    ///     Router,
+    /// };
+    /// use serde::{Deserialize, Serialize};
+    ///
+    /// #[derive(Serialize, Deserialize)]
+    /// pub struct User(String);
+    ///
+    /// fn router(r: &mut Router) {
+    ///     r.pipe("common").add(
+    ///         Session::<User, _>::new(MemoryBackend::default()).expiry(Expiry::Sliding(60 * 30)),
+    ///     );
+    /// }
+    /// ```
+    #[inline]
+    pub fn expiry(mut self, expiry: Expiry) -> Self {
+        self.expiry = Some(expiry);
+        self
+    }
+
     fn cookie_value(&self, value: &str) -> String {
         let mut cookie_value = String::with_capacity(255);
 
@@ -185,52 +268,129 @@ where
         cookie_value
     }
 
-    async fn read_session(&self, req: &mut Request, id: &Option<String>) -> bool {
+    fn expiring_cookie_value(&self, value: &str, expires: Option<u64>) -> String {
+        let mut cookie_value = self.cookie_value(value);
+
+        if let Some(expires) = expires {
+            let date: DateTime<Utc> = (UNIX_EPOCH + Duration::from_secs(expires)).into();
+
+            cookie_value.push_str(&format!(
+                "; Max-Age={}; Expires={}",
+                expires.saturating_sub(now()),
+                date.format("%a, %d %b %Y %H:%M:%S GMT")
+            ));
+        }
+
+        cookie_value
+    }
+
+    fn expires(&self, loaded: Option<&Loaded>) -> Option<u64> {
+        match self.expiry? {
+            Expiry::Sliding(secs) => Some(now() + secs),
+            Expiry::Absolute(secs) => loaded
+                .and_then(|x| x.expires)
+                .or_else(|| Some(now() + secs)),
+        }
+    }
+
+    fn identifier(&self, req: &mut Request) -> Option<String> {
+        let cookies = req
+            .extensions()
+            .get::<CookieJar>()
+            .cloned()
+            .unwrap_or_else(|| CookieParser::new().parse(req));
+
+        cookies.get(self.name).map(|x| x.value().to_string())
+    }
+
+    async fn read_session(&self, req: &mut Request, id: &Option<String>) -> Option<Loaded> {
         if let Some(id) = id {
             trace!("Session id {} found in cookie", id);
 
             if let Some(data) = self.backend.read_session(id).await {
-                if let Ok(bytes) = deserialize::<T>(&data) {
-                    req.extensions_mut().insert(SessionData::<T>::Clean(bytes));
-                    return true;
+                if let Ok((expires, data)) = deserialize::<(Option<u64>, T)>(&data) {
+                    if expires.map_or(false, |x| x <= now()) {
+                        trace!("Session id {} has expired", id);
+                        self.backend.drop_session(id).await;
+                    } else {
+                        req.extensions_mut().insert(SessionData::<T>::Clean(data));
+
+                        return Some(Loaded {
+                            id: id.clone(),
+                            expires,
+                        });
+                    }
                 }
             }
         }
 
         req.extensions_mut().insert(SessionData::<T>::None);
-        false
+        None
     }
 
     async fn write_session(
         &self,
         req: &mut Request,
         res: &mut Response<Body>,
-        had_data: bool,
-        id: &Option<String>,
+        loaded: Option<Loaded>,
     ) {
+        let regenerate = req
+            .extensions_mut()
+            .remove::<SessionRegenerate<T>>()
+            .is_some();
+
         if let Some(data) = req.extensions_mut().remove::<SessionData<T>>() {
             match data {
                 SessionData::Dirty(data) => {
-                    if let Ok(bytes) = serialize(&data) {
-                        let id = self.random_identifier();
-                        let written = self.backend.persist_session(&id, &bytes).await;
-
-                        if written {
-                            self.write_cookie(self.cookie_value(&id), res);
-                        }
-                    }
+                    self.persist(res, &data, loaded, regenerate).await;
+                }
+                SessionData::Clean(data)
+                    if regenerate || matches!(self.expiry, Some(Expiry::Sliding(_))) =>
+                {
+                    self.persist(res, &data, loaded, regenerate).await;
                 }
-                SessionData::None if had_data => {
-                    self.reset_cookie(res);
-                    self.backend
-                        .drop_session(id.as_ref().expect(INTERNAL_ERR))
-                        .await;
+                SessionData::None => {
+                    if let Some(loaded) = loaded {
+                        self.reset_cookie(res);
+                        self.backend.drop_session(&loaded.id).await;
+                    }
                 }
                 _ => {}
             }
         }
     }
 
+    /// Only identifiers of sessions loaded from the backend are reused. Any other
+    /// identifier is coming from the client and must not be trusted.
+    async fn persist(
+        &self,
+        res: &mut Response<Body>,
+        data: &T,
+        loaded: Option<Loaded>,
+        regenerate: bool,
+    ) {
+        let expires = self.expires(loaded.as_ref());
+
+        if let Ok(bytes) = serialize(&(expires, data)) {
+            let id = match &loaded {
+                Some(loaded) if !regenerate => loaded.id.clone(),
+                _ => self.random_identifier(),
+            };
+
+            let written = self.backend.persist_session(&id, &bytes).await;
+
+            if written {
+                self.write_cookie(self.expiring_cookie_value(&id, expires), res);
+
+                if let Some(loaded) = loaded {
+                    if loaded.id != id {
+                        self.backend.drop_session(&loaded.id).await;
+                    }
+                }
+            }
+        }
+    }
+
     fn reset_cookie(&self, res: &mut Response<Body>) {
         self.write_cookie(
             format!(
@@ -264,20 +424,14 @@ where
     B: SessionBackend + Send + Sync,
 {
     fn handle<'m>(&'m self, req: &'m mut Request, chain: Chain<'m>) -> HandleFuture<'m> {
-        let cookies = req
-            .extensions()
-            .get::<CookieJar>()
-            .cloned()
-            .unwrap_or_else(|| CookieParser::new().parse(req));
-
-        let id = cookies.get(self.name).map(|x| x.value().to_string());
+        let id = self.identifier(req);
 
         async move {
-            let had_data = self.read_session(req, &id).await;
+            let loaded = self.read_session(req, &id).await;
 
             let mut response = chain.run(req).await?;
 
-            self.write_session(req, &mut response, had_data, &id).await;
+            self.write_session(req, &mut response, loaded).await;
 
             Ok(response)
         }
@@ -0,0 +1,217 @@
+use crate::{
+    futures::{
+        future::{poll_fn, select, BoxFuture, Either},
+        stream::unfold,
+        FutureExt,
+    },
+    hyper::{
+        body::{Buf, HttpBody},
+        header::{HeaderValue, CONTENT_LENGTH},
+        service::Service as HyperService,
+        Body, Request as HyperRequest, Response as HyperResponse,
+    },
+    mount::other,
+    Chain, Error, HandleFuture, Middleware, Request, INTERNAL_ERR,
+};
+
+use futures::channel::oneshot::{channel, Receiver, Sender};
+use tower_layer::Layer;
+
+use std::{
+    any::Any,
+    error::Error as StdError,
+    sync::{Mutex, PoisonError},
+    task::{Context, Poll},
+};
+
+type BoxError = Box<dyn StdError + Send + Sync>;
+type Reply = Result<HyperResponse<Body>, Error>;
+
+fn into_body<B>(body: B) -> Body
+where
+    B: HttpBody + Send + 'static,
+    B::Data: Send,
+    B::Error: Into<BoxError>,
+{
+    // Hyper bodies are kept as they are so that they don't lose their size hint
+    let mut body = Some(body);
+
+    if let Some(body) = (&mut body as &mut dyn Any).downcast_mut::<Option<Body>>() {
+        return body.take().expect(INTERNAL_ERR);
+    }
+
+    let body = body.expect(INTERNAL_ERR);
+
+    Body::wrap_stream(unfold(Box::pin(body), |mut body| async move {
+        body.data()
+            .await
+            .map(|data| (data.map(|mut x| x.copy_to_bytes(x.remaining())), body))
+    }))
+}
+
+fn into_error<E>(e: E) -> Error
+where
+    E: Into<BoxError>,
+{
+    // Errors coming from the rest of the chain keep their response status
+    match e.into().downcast::<Error>() {
+        Ok(e) => *e,
+        Err(e) => other(e),
+    }
+}
+
+/// Channels to the rest of the chain for the request being handled, which are given to
+/// [`Next`] in the extensions of the request.
+struct Slot {
+    request: Sender<HyperRequest<Body>>,
+    response: Receiver<Reply>,
+}
+
+/// Remaining middleware chain exposed as a [`tower_service::Service`] to the wrapped layer.
+///
+/// It takes the rest of the chain from the extensions of the request, so the layer should
+/// keep them when it builds a new request. The chain can only be called once per request.
+///
+/// [`tower_service::Service`]: crate::hyper::service::Service
+#[derive(Clone)]
+pub struct Next(());
+
+impl<B> HyperService<HyperRequest<B>> for Next
+where
+    B: HttpBody + Send + 'static,
+    B::Data: Send,
+    B::Error: Into<BoxError>,
+{
+    type Response = HyperResponse<Body>;
+    type Error = Error;
+    type Future = BoxFuture<'static, Reply>;
+
+    fn poll_ready(&mut self, _: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        Poll::Ready(Ok(()))
+    }
+
+    fn call(&mut self, mut req: HyperRequest<B>) -> Self::Future {
+        let slot = req.extensions_mut().remove::<Slot>();
+
+        async move {
+            match slot {
+                Some(Slot { request, response }) => {
+                    request
+                        .send(req.map(into_body))
+                        .map_err(|_| other("Middleware chain is no longer running"))?;
+
+                    response
+                        .await
+                        .map_err(|_| other("Middleware chain did not respond"))?
+                }
+                None => Err(other(
+                    "Middleware chain can only be called once with the extensions of the request",
+                )),
+            }
+        }
+        .boxed()
+    }
+}
+
+/// Middleware that runs a [`tower_layer::Layer`] around the rest of the chain.
+///
+/// The wrapped service receives a [`hyper::Request`] built from the reign request. Any changes
+/// it makes to the method, uri, version, headers, extensions or body are visible to the rest of
+/// the chain. The layer can also respond without calling the inner service.
+///
+/// The layer is applied once and every request is handled by a clone of the resulting service,
+/// so the state shared between the clones, like the permits of a concurrency limit, applies to
+/// all the requests. Services which keep their state in themselves, like a rate limit, need to
+/// be behind a buffer to be shared.
+///
+/// # Examples
+///
+/// ```
+/// use reign::router::{middleware::Tower, tower_layer::layer_fn, Router};
+///
+/// fn router(r: &mut Router) {
+///     r.pipe("common").add(Tower::new(layer_fn(|inner| inner)));
+/// }
+/// ```
+pub struct Tower<S> {
+    service: Mutex<S>,
+}
+
+impl<S> Tower<S> {
+    /// Wraps the given layer as a middleware.
+    pub fn new<L>(layer: L) -> Self
+    where
+        L: Layer<Next, Service = S>,
+    {
+        Self {
+            service: Mutex::new(layer.layer(Next(()))),
+        }
+    }
+}
+
+impl<S, B> Middleware for Tower<S>
+where
+    S: HyperService<HyperRequest<Body>, Response = HyperResponse<B>> + Clone + Send + 'static,
+    S::Error: Into<BoxError>,
+    S::Future: Send + 'static,
+    B: HttpBody + Send + 'static,
+    B::Data: Send,
+    B::Error: Into<BoxError>,
+{
+    fn handle<'m>(&'m self, req: &'m mut Request, chain: Chain<'m>) -> HandleFuture<'m> {
+        let (request_tx, request_rx) = channel();
+        let (response_tx, response_rx) = channel();
+
+        let mut service = self
+            .service
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .clone();
+
+        async move {
+            let uri = req.uri().clone();
+            let mut hyper_req = req.take_hyper(uri)?;
+
+            hyper_req.extensions_mut().insert(Slot {
+                request: request_tx,
+                response: response_rx,
+            });
+
+            let outer = async move {
+                poll_fn(|cx| service.poll_ready(cx))
+                    .await
+                    .map_err(into_error)?;
+
+                service.call(hyper_req).await.map_err(into_error)
+            }
+            .boxed();
+
+            let inner = async move {
+                if let Ok(hyper_req) = request_rx.await {
+                    req.update(hyper_req);
+                    let _ = response_tx.send(chain.run(req).await);
+                }
+            }
+            .boxed();
+
+            let response = match select(outer, inner).await {
+                Either::Left((response, _)) => response?,
+                Either::Right((_, outer)) => outer.await?,
+            };
+
+            let len = response.body().size_hint().exact();
+            let mut response = response.map(into_body);
+
+            // Streamed bodies don't know their size, so it is kept in the header instead
+            if let Some(len) = len.filter(|_| response.body().size_hint().exact().is_none()) {
+                response
+                    .headers_mut()
+                    .entry(CONTENT_LENGTH)
+                    .or_insert_with(|| HeaderValue::from(len));
+            }
+
+            Ok(response)
+        }
+        .boxed()
+    }
+}
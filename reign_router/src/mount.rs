@@ -0,0 +1,74 @@
+use crate::{
+    futures::{future::poll_fn, FutureExt},
+    hyper::{
+        http::uri::{PathAndQuery, Uri},
+        service::Service as HyperService,
+        Body, Request as HyperRequest, Response as HyperResponse,
+    },
+    Error, Handle, HandleFuture, Request,
+};
+
+use anyhow::anyhow;
+
+use std::{error::Error as StdError, sync::Mutex};
+
+/// Name of the param that captures the remaining path of a mounted service.
+pub(crate) const MOUNT_PARAM: &str = "reign_mount";
+
+/// Endpoint handle that forwards the request to a service with the mount prefix stripped.
+pub(crate) struct Mounted<S> {
+    service: Mutex<S>,
+}
+
+impl<S> Mounted<S> {
+    pub(crate) fn new(service: S) -> Self {
+        Self {
+            service: Mutex::new(service),
+        }
+    }
+}
+
+pub(crate) fn other<E>(e: E) -> Error
+where
+    E: Into<Box<dyn StdError + Send + Sync>>,
+{
+    Error::Other(anyhow!(e.into()))
+}
+
+fn forward(req: &mut Request) -> Result<HyperRequest<Body>, Error> {
+    let mut path = format!("/{}", req.params.get(MOUNT_PARAM).map_or("", |x| x));
+
+    if let Some(query) = req.uri().query() {
+        path.push('?');
+        path.push_str(query);
+    }
+
+    let mut uri = req.uri().clone().into_parts();
+    uri.path_and_query = Some(path.parse::<PathAndQuery>().map_err(other)?);
+
+    req.take_hyper(Uri::from_parts(uri).map_err(other)?)
+}
+
+impl<S> Handle for Mounted<S>
+where
+    S: HyperService<HyperRequest<Body>, Response = HyperResponse<Body>> + Clone + Send + 'static,
+    S::Error: Into<Box<dyn StdError + Send + Sync>>,
+    S::Future: Send,
+{
+    fn call<'a>(&'a self, req: &'a mut Request) -> HandleFuture<'a> {
+        let mut service = self
+            .service
+            .lock()
+            .expect("Mounted service is poisoned")
+            .clone();
+
+        async move {
+            let req = forward(req)?;
+
+            poll_fn(|cx| service.poll_ready(cx)).await.map_err(other)?;
+
+            service.call(req).await.map_err(other)
+        }
+        .boxed()
+    }
+}
@@ -1,5 +1,108 @@
 // use percent_encoding::utf8_percent_encode;
 
+use crate::mount::MOUNT_PARAM;
+
+use chrono::NaiveDate;
+
+use std::{
+    any::{type_name, Any},
+    fmt,
+    str::FromStr,
+};
+
+pub(crate) type Parsed = Box<dyn Any + Send + Sync>;
+
+/// Types that can be used for typed path parameters.
+///
+/// The regex is used when matching the route so that the segment is only matched if it
+/// can be converted into the type.
+///
+/// # Examples
+///
+/// ```
+/// use reign::router::PathParam;
+/// use std::str::FromStr;
+///
+/// struct Slug(String);
+///
+/// impl FromStr for Slug {
+///     type Err = ();
+///
+///     fn from_str(s: &str) -> Result<Self, Self::Err> {
+///         Ok(Slug(s.to_string()))
+///     }
+/// }
+///
+/// impl PathParam for Slug {
+///     const REGEX: &'static str = "[a-z0-9-]+";
+/// }
+/// ```
+pub trait PathParam: FromStr + Send + Sync + 'static {
+    /// Regex pattern that matches the string representation of the type.
+    const REGEX: &'static str;
+}
+
+macro_rules! path_param {
+    ($regex:literal, $($ty:ty),+) => {
+        $(
+            impl PathParam for $ty {
+                const REGEX: &'static str = $regex;
+            }
+        )+
+    };
+}
+
+path_param!("-?[0-9]+", i8, i16, i32, i64, i128, isize);
+path_param!("[0-9]+", u8, u16, u32, u64, u128, usize);
+path_param!("true|false", bool);
+path_param!("[^/]+", String);
+path_param!("[0-9]{4}-[0-9]{2}-[0-9]{2}", NaiveDate);
+
+#[cfg(feature = "uuid")]
+path_param!(
+    "[0-9a-fA-F]{8}-[0-9a-fA-F]{4}-[0-9a-fA-F]{4}-[0-9a-fA-F]{4}-[0-9a-fA-F]{12}",
+    uuid::Uuid
+);
+
+fn parse<T>(value: &str) -> Option<Parsed>
+where
+    T: PathParam,
+{
+    T::from_str(value).ok().map(|x| Box::new(x) as Parsed)
+}
+
+/// Regex and conversion for a typed path parameter.
+#[derive(Clone, Copy)]
+pub(crate) struct Typed {
+    regex: &'static str,
+    name: &'static str,
+    pub(crate) parse: fn(&str) -> Option<Parsed>,
+}
+
+impl Typed {
+    fn new<T>() -> Self
+    where
+        T: PathParam,
+    {
+        let name = type_name::<T>();
+
+        Self {
+            regex: T::REGEX,
+            name: name.rsplit("::").next().unwrap_or(name),
+            parse: parse::<T>,
+        }
+    }
+}
+
+impl fmt::Debug for Typed {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Typed")
+            .field("regex", &self.regex)
+            .field("name", &self.name)
+            .finish()
+    }
+}
+
 #[derive(Debug, Clone)]
 enum PathPart {
     Static(String),
@@ -7,6 +110,8 @@ enum PathPart {
     ParamOpt(String),
     ParamRegex(String, String),
     ParamOptRegex(String, String),
+    ParamTyped(String, Typed),
+    ParamOptTyped(String, Typed),
 }
 
 /// Path that is specified for a route in the router definition.
@@ -196,6 +301,85 @@ impl Path {
         self
     }
 
+    /// Add a required typed path parameter with the given name to the path.
+    ///
+    /// The parameter is only matched if it can be converted into the type, which means that
+    /// the endpoint handler doesn't need to convert it again when using `#[params]`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use reign::router::{Path, Router};
+    /// # use reign::prelude::*;
+    /// #
+    /// # async fn foo(req: &mut Request) -> Result<impl Response, Error> { Ok("foo") }
+    ///
+    /// fn router(r: &mut Router) {
+    ///     r.get(Path::new().param_typed::<i32>("id"), foo);
+    /// }
+    /// ```
+    pub fn param_typed<T>(mut self, name: impl Into<String>) -> Self
+    where
+        T: PathParam,
+    {
+        self.parts
+            .push(PathPart::ParamTyped(name.into(), Typed::new::<T>()));
+        self
+    }
+
+    /// Add an optional typed path parameter with the given name to the path.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use reign::router::{Path, Router};
+    /// # use reign::prelude::*;
+    /// #
+    /// # async fn foo(req: &mut Request) -> Result<impl Response, Error> { Ok("foo") }
+    ///
+    /// fn router(r: &mut Router) {
+    ///     r.get(Path::new().param_opt_typed::<i32>("id"), foo);
+    /// }
+    /// ```
+    pub fn param_opt_typed<T>(mut self, name: impl Into<String>) -> Self
+    where
+        T: PathParam,
+    {
+        self.parts
+            .push(PathPart::ParamOptTyped(name.into(), Typed::new::<T>()));
+        self
+    }
+
+    pub(crate) fn typed(&self) -> Vec<(String, Typed)> {
+        self.parts
+            .iter()
+            .filter_map(|part| match part {
+                PathPart::ParamTyped(p, t) | PathPart::ParamOptTyped(p, t) => Some((p.clone(), *t)),
+                _ => None,
+            })
+            .collect()
+    }
+
+    /// Readable form of the path, with params shown as `{name}`.
+    pub(crate) fn pattern(&self) -> String {
+        let mut pattern = vec![];
+
+        for part in &self.parts {
+            match part {
+                PathPart::Static(p) => pattern.push(format!("/{}", p)),
+                PathPart::Param(p) => pattern.push(format!("/{{{}}}", p)),
+                PathPart::ParamOpt(p) => pattern.push(format!("/{{{}?}}", p)),
+                PathPart::ParamOptRegex(p, _) if p == MOUNT_PARAM => pattern.push("/*".into()),
+                PathPart::ParamRegex(p, r) => pattern.push(format!("/{{{}:{}}}", p, r)),
+                PathPart::ParamOptRegex(p, r) => pattern.push(format!("/{{{}?:{}}}", p, r)),
+                PathPart::ParamTyped(p, t) => pattern.push(format!("/{{{}:{}}}", p, t.name)),
+                PathPart::ParamOptTyped(p, t) => pattern.push(format!("/{{{}?:{}}}", p, t.name)),
+            }
+        }
+
+        pattern.join("")
+    }
+
     pub(crate) fn regex(&self) -> String {
         let mut regex = vec![];
 
@@ -206,6 +390,8 @@ impl Path {
                 PathPart::ParamOpt(p) => regex.push(format!("(/(?P<{}>[^/]+))?", p)),
                 PathPart::ParamRegex(p, r) => regex.push(format!("/(?P<{}>{})", p, r)),
                 PathPart::ParamOptRegex(p, r) => regex.push(format!("(/(?P<{}>{}))?", p, r)),
+                PathPart::ParamTyped(p, t) => regex.push(format!("/(?P<{}>{})", p, t.regex)),
+                PathPart::ParamOptTyped(p, t) => regex.push(format!("(/(?P<{}>{}))?", p, t.regex)),
             }
         }
 
@@ -256,14 +442,43 @@ impl Into<Path> for String {
 ///
 ///     // Optional Glob param
 ///     r.get(p!("tree" / id*?), tree);
+///
+///     // Typed param, any type implementing `PathParam`
+///     r.get(p!("number" / id: i32), number);
+///
+///     // Optional Typed param
+///     r.get(p!("number" / id?: u64), number);
+///
+///     // Date param
+///     r.get(p!("archive" / day: date), foobar);
 /// }
 /// ```
+///
+/// With the `uuid` feature, `uuid` can also be used as the type of a param.
 #[macro_export]
 macro_rules! path {
     (@expr $e:expr,) => { $e };
     (@expr $e:expr, / $part:literal $($tail:tt)*) => {
         $crate::path!(@expr $e.path($part), $($tail)*);
     };
+    (@expr $e:expr, / $part:ident : uuid $($tail:tt)*) => {
+        $crate::path!(@expr $e.param_typed::<$crate::uuid::Uuid>(stringify!($part)), $($tail)*);
+    };
+    (@expr $e:expr, / $part:ident : date $($tail:tt)*) => {
+        $crate::path!(@expr $e.param_typed::<$crate::chrono::NaiveDate>(stringify!($part)), $($tail)*);
+    };
+    (@expr $e:expr, / $part:ident : $ty:ident $($tail:tt)*) => {
+        $crate::path!(@expr $e.param_typed::<$ty>(stringify!($part)), $($tail)*);
+    };
+    (@expr $e:expr, / $part:ident ? : uuid $($tail:tt)*) => {
+        $crate::path!(@expr $e.param_opt_typed::<$crate::uuid::Uuid>(stringify!($part)), $($tail)*);
+    };
+    (@expr $e:expr, / $part:ident ? : date $($tail:tt)*) => {
+        $crate::path!(@expr $e.param_opt_typed::<$crate::chrono::NaiveDate>(stringify!($part)), $($tail)*);
+    };
+    (@expr $e:expr, / $part:ident ? : $ty:ident $($tail:tt)*) => {
+        $crate::path!(@expr $e.param_opt_typed::<$ty>(stringify!($part)), $($tail)*);
+    };
     (@expr $e:expr, / $part:ident @ $regex:literal $($tail:tt)*) => {
         $crate::path!(@expr $e.param_regex(stringify!($part), $regex), $($tail)*);
     };
@@ -296,4 +511,40 @@ mod test {
         let p = Path::new().path("foo").path("bar");
         assert_eq!(p.regex(), "/foo/bar");
     }
+
+    #[test]
+    fn test_regex_param_typed() {
+        let p = Path::new()
+            .path("foo")
+            .param_typed::<u32>("id")
+            .param_opt_typed::<NaiveDate>("day");
+        assert_eq!(
+            p.regex(),
+            "/foo/(?P<id>[0-9]+)(/(?P<day>[0-9]{4}-[0-9]{2}-[0-9]{2}))?"
+        );
+    }
+
+    #[test]
+    fn test_typed_parse() {
+        let p = Path::new().param("name").param_typed::<i8>("id");
+        let typed = p.typed();
+
+        assert_eq!(typed.len(), 1);
+        assert_eq!(typed[0].0, "id");
+        assert_eq!(
+            (typed[0].1.parse)("-12").and_then(|x| x.downcast::<i8>().ok()),
+            Some(Box::new(-12))
+        );
+        assert!((typed[0].1.parse)("1000").is_none());
+    }
+
+    #[test]
+    fn test_pattern() {
+        let p = Path::new()
+            .path("foo")
+            .param("id")
+            .param_opt_regex("page", "[0-9]+")
+            .param_opt_typed::<NaiveDate>("day");
+        assert_eq!(p.pattern(), "/foo/{id}/{page?:[0-9]+}/{day?:NaiveDate}");
+    }
 }
@@ -1,19 +1,67 @@
 #[cfg(feature = "session")]
-use crate::middleware::session::SessionData;
+use crate::middleware::session::{FlashState, Flashes, SessionData, SessionRegenerate};
+#[cfg(any(feature = "form", feature = "json"))]
+use crate::{hyper::header::CONTENT_TYPE, BodyError};
 use crate::{
     hyper::{
         body::{to_bytes, Bytes},
+        header::{ACCEPT, HOST},
         http::{request::Parts, Extensions},
         Body, HeaderMap, Method, Request as HyperRequest, Uri, Version,
     },
-    Error, ParamError,
+    path::Parsed,
+    Error, HeaderError, ParamError, QueryError, State, StateError,
 };
+#[cfg(feature = "cookie")]
+use crate::{
+    middleware::cookie::{CookieJar, CookieParser},
+    CookieError,
+};
+
+use mime::{Mime, STAR};
 
+#[cfg(any(feature = "form", feature = "json"))]
+use serde::de::DeserializeOwned;
 #[cfg(feature = "session")]
 use serde::{Deserialize, Serialize};
+#[cfg(feature = "form")]
+use serde_qs::Config as QsConfig;
 use url::form_urlencoded::parse;
 
-use std::{collections::HashMap as Map, net::SocketAddr, str::FromStr};
+use std::{
+    any::{type_name, Any, TypeId},
+    cmp::Ordering,
+    collections::HashMap as Map,
+    net::SocketAddr,
+    str::FromStr,
+    sync::Arc,
+};
+
+fn specificity(mime: &Mime) -> u8 {
+    if mime.type_() == STAR {
+        0
+    } else if mime.subtype() == STAR {
+        1
+    } else {
+        2
+    }
+}
+
+/// Checks whether the given media range from `Accept` header matches the media type.
+pub(crate) fn mime_matches(range: &Mime, mime: &Mime) -> bool {
+    (range.type_() == STAR || range.type_() == mime.type_())
+        && (range.subtype() == STAR || range.subtype() == mime.subtype())
+}
+
+/// Quality value given to the media type by the most specific matching range of the
+/// parsed `Accept` header.
+pub(crate) fn quality(accept: &[(Mime, f32)], mime: &Mime) -> Option<f32> {
+    accept
+        .iter()
+        .filter(|(range, _)| mime_matches(range, mime))
+        .max_by_key(|(range, _)| specificity(range))
+        .map(|(_, q)| *q)
+}
 
 /// Request denotes the incoming request to the server and also acts as a state.
 ///
@@ -31,7 +79,9 @@ pub struct Request {
     parts: Parts,
     ip: SocketAddr,
     pub(crate) params: Map<String, String>,
-    pub(crate) query: Map<String, String>,
+    pub(crate) parsed: Map<String, Parsed>,
+    pub(crate) query: Vec<(String, String)>,
+    pub(crate) state: Arc<State>,
 }
 
 impl Request {
@@ -39,22 +89,52 @@ impl Request {
         let (parts, body) = req.into_parts();
 
         let mut ret = Self {
+            query: Self::parse_query(&parts.uri),
             parts,
             ip,
             params: Map::new(),
-            query: Map::new(),
+            parsed: Map::new(),
+            state: Arc::default(),
         };
 
-        if let Some(query) = ret
-            .uri()
-            .query()
+        ret.parts.extensions.insert(body);
+        ret
+    }
+
+    fn parse_query(uri: &Uri) -> Vec<(String, String)> {
+        uri.query()
             .map(|v| parse(v.as_bytes()).into_owned().collect())
-        {
-            ret.query = query;
+            .unwrap_or_default()
+    }
+
+    /// Builds a [`hyper::Request`] with the given uri out of this request, taking the body.
+    pub(crate) fn take_hyper(&mut self, uri: Uri) -> Result<HyperRequest<Body>, Error> {
+        let mut builder = HyperRequest::builder()
+            .method(self.method())
+            .uri(uri)
+            .version(*self.version())
+            .extension(*self.ip());
+
+        if let Some(headers) = builder.headers_mut() {
+            headers.extend(self.headers().clone());
         }
 
-        ret.parts.extensions.insert(body);
-        ret
+        Ok(builder.body(self.extensions_mut().remove::<Body>().unwrap_or_default())?)
+    }
+
+    /// Replaces the request line and headers with the ones from the given [`hyper::Request`]
+    /// and puts back its body.
+    #[cfg(feature = "tower")]
+    pub(crate) fn update(&mut self, req: HyperRequest<Body>) {
+        let (parts, body) = req.into_parts();
+
+        self.query = Self::parse_query(&parts.uri);
+        self.parts.method = parts.method;
+        self.parts.uri = parts.uri;
+        self.parts.version = parts.version;
+        self.parts.headers = parts.headers;
+        self.parts.extensions.extend(parts.extensions);
+        self.parts.extensions.insert(body);
     }
 
     /// Returns a reference to the associated remote IP socket address.
@@ -121,6 +201,32 @@ impl Request {
         &self.parts.uri
     }
 
+    /// Returns the host of the request, without the port.
+    ///
+    /// Uses the host from the URI if available, otherwise the `Host` header.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use reign::prelude::*;
+    ///
+    /// async fn foo(req: &mut Request) -> Result<impl Response, Error> {
+    ///     Ok(req.host().unwrap_or("").to_string())
+    /// }
+    /// ```
+    pub fn host(&self) -> Option<&str> {
+        self.uri().host().or_else(|| {
+            self.headers()
+                .get(HOST)
+                .and_then(|x| x.to_str().ok())
+                .map(|x| match x.rfind(':') {
+                    // The colons of an IPv6 address are inside the brackets
+                    Some(i) if !x[i..].contains(']') => &x[..i],
+                    _ => x,
+                })
+        })
+    }
+
     /// Returns a reference to the associated HeaderMap.
     ///
     /// # Examples
@@ -141,6 +247,149 @@ impl Request {
         &self.parts.headers
     }
 
+    /// Retrieve the value of a header converted to the given type.
+    ///
+    /// Responds with `400` if the value can't be converted.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use reign::prelude::*;
+    ///
+    /// async fn foo(req: &mut Request) -> Result<impl Response, Error> {
+    ///     let version = req.header_as::<u8>("x-version")?.unwrap_or(1);
+    ///
+    ///     Ok(format!("version {}", version))
+    /// }
+    /// ```
+    pub fn header_as<T>(&self, name: &str) -> Result<Option<T>, Error>
+    where
+        T: FromStr,
+    {
+        Ok(self.headers().get(name).map_or_else(
+            || Ok(None),
+            |h| {
+                h.to_str()
+                    .ok()
+                    .and_then(|h| T::from_str(h).ok())
+                    .ok_or_else(|| HeaderError::UnableToConvertHeader(name.into()))
+                    .map(Some)
+            },
+        )?)
+    }
+
+    /// Retrieve the value of a cookie converted to the given type.
+    ///
+    /// Uses the cookies parsed by [`CookieParser`] if available, otherwise parses them from
+    /// the headers. Responds with `400` if the value can't be converted.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use reign::prelude::*;
+    ///
+    /// async fn foo(req: &mut Request) -> Result<impl Response, Error> {
+    ///     let theme = req.cookie_as::<String>("theme")?;
+    ///
+    ///     Ok(theme.unwrap_or_else(|| "light".into()))
+    /// }
+    /// ```
+    #[cfg(feature = "cookie")]
+    pub fn cookie_as<T>(&self, name: &str) -> Result<Option<T>, Error>
+    where
+        T: FromStr,
+    {
+        let cookies = self
+            .extensions()
+            .get::<CookieJar>()
+            .cloned()
+            .unwrap_or_else(|| CookieParser::new().parse(self));
+
+        Ok(cookies.get(name).map_or_else(
+            || Ok(None),
+            |c| {
+                T::from_str(c.value())
+                    .map_err(|_| CookieError::UnableToConvertCookie(name.into()))
+                    .map(Some)
+            },
+        )?)
+    }
+
+    /// Returns the media types from the `Accept` header ordered by preference.
+    ///
+    /// Media types with higher quality values come first. For equal quality values, the more
+    /// specific media types come first. Media types with zero quality value are left out.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use reign::prelude::*;
+    ///
+    /// async fn foo(req: &mut Request) -> Result<impl Response, Error> {
+    ///     if let Some(mime) = req.accept().first() {
+    ///         Ok(mime.to_string())
+    ///     } else {
+    ///         Ok("Anything".into())
+    ///     }
+    /// }
+    /// ```
+    pub fn accept(&self) -> Vec<Mime> {
+        self.accept_q()
+            .into_iter()
+            .filter(|(_, q)| *q > 0.0)
+            .map(|(mime, _)| mime)
+            .collect()
+    }
+
+    /// Returns true if the given media type is acceptable according to the `Accept` header.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use reign::prelude::*;
+    ///
+    /// async fn foo(req: &mut Request) -> Result<impl Response, Error> {
+    ///     Ok(req.accepts(&mime::APPLICATION_JSON).to_string())
+    /// }
+    /// ```
+    pub fn accepts(&self, mime: &Mime) -> bool {
+        let accept = self.accept_q();
+
+        if accept.is_empty() {
+            return true;
+        }
+
+        quality(&accept, mime).map_or(false, |q| q > 0.0)
+    }
+
+    pub(crate) fn accept_q(&self) -> Vec<(Mime, f32)> {
+        let mut accept = self
+            .headers()
+            .get_all(ACCEPT)
+            .iter()
+            .flat_map(|x| x.to_str())
+            .flat_map(|x| x.split(','))
+            .flat_map(|x| x.trim().parse::<Mime>())
+            .map(|mime| {
+                let q = mime
+                    .get_param("q")
+                    .and_then(|q| q.as_str().parse::<f32>().ok())
+                    .unwrap_or(1.0)
+                    .clamp(0.0, 1.0);
+
+                (mime.essence_str().parse().unwrap_or(mime), q)
+            })
+            .collect::<Vec<_>>();
+
+        accept.sort_by(|a, b| {
+            b.1.partial_cmp(&a.1)
+                .unwrap_or(Ordering::Equal)
+                .then_with(|| specificity(&b.0).cmp(&specificity(&a.0)))
+        });
+
+        accept
+    }
+
     /// Returns a reference to the underlying any-type storage.
     ///
     /// # Examples
@@ -186,6 +435,38 @@ impl Request {
         &mut self.parts.extensions
     }
 
+    /// Retrieve a clone of the application state of the given type.
+    ///
+    /// State is registered on the router using [`Router::state`](crate::Router::state) and is
+    /// shared by all the requests, so it should be cheap to clone. Wrap it in an [`Arc`] if not.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use reign::prelude::*;
+    ///
+    /// #[derive(Clone)]
+    /// struct Config {
+    ///     name: String,
+    /// }
+    ///
+    /// async fn foo(req: &mut Request) -> Result<impl Response, Error> {
+    ///     let config = req.state::<Config>()?;
+    ///
+    ///     Ok(config.name)
+    /// }
+    /// ```
+    pub fn state<T>(&self) -> Result<T, Error>
+    where
+        T: Clone + Send + Sync + 'static,
+    {
+        self.state
+            .get(&TypeId::of::<T>())
+            .and_then(|x| x.downcast_ref::<T>())
+            .cloned()
+            .ok_or_else(|| StateError::RequiredStateNotFound(type_name::<T>()).into())
+    }
+
     /// Retrieve the request body.
     ///
     /// This consumes the body from the request and it will not be available for
@@ -213,6 +494,101 @@ impl Request {
         }
     }
 
+    /// Deserialize the URL encoded form body into the given type.
+    ///
+    /// Nested structures and sequences are supported in the same way as
+    /// [`query_struct`](Self::query_struct). Responds with `415` if the body is not URL
+    /// encoded and with `400` if it can't be deserialized.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use reign::prelude::*;
+    /// use serde::Deserialize;
+    ///
+    /// #[derive(Deserialize)]
+    /// struct Login {
+    ///     email: String,
+    /// }
+    ///
+    /// async fn foo(req: &mut Request) -> Result<impl Response, Error> {
+    ///     let login = req.form::<Login>().await?;
+    ///
+    ///     Ok(login.email)
+    /// }
+    /// ```
+    #[cfg(feature = "form")]
+    pub async fn form<T>(&mut self) -> Result<T, Error>
+    where
+        T: DeserializeOwned,
+    {
+        self.content_type_is(|mime| mime.subtype() == mime::WWW_FORM_URLENCODED)?;
+
+        let body = self.body().await?.unwrap_or_default();
+
+        Ok(QsConfig::new(5, false)
+            .deserialize_bytes(&body)
+            .map_err(|e| BodyError::UnableToDeserializeBody(e.to_string()))?)
+    }
+
+    /// Deserialize the JSON body into the given type.
+    ///
+    /// Responds with `415` if the body is not JSON and with `400` if it can't be deserialized.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use reign::prelude::*;
+    /// use serde::Deserialize;
+    ///
+    /// #[derive(Deserialize)]
+    /// struct Article {
+    ///     title: String,
+    /// }
+    ///
+    /// async fn foo(req: &mut Request) -> Result<impl Response, Error> {
+    ///     let article = req.json::<Article>().await?;
+    ///
+    ///     Ok(article.title)
+    /// }
+    /// ```
+    #[cfg(feature = "json")]
+    pub async fn json<T>(&mut self) -> Result<T, Error>
+    where
+        T: DeserializeOwned,
+    {
+        self.content_type_is(|mime| {
+            mime.subtype() == mime::JSON || mime.suffix() == Some(mime::JSON)
+        })?;
+
+        let body = self.body().await?.unwrap_or_default();
+
+        Ok(serde_json::from_slice(&body)
+            .map_err(|e| BodyError::UnableToDeserializeBody(e.to_string()))?)
+    }
+
+    #[cfg(any(feature = "form", feature = "json"))]
+    fn content_type_is<F>(&self, f: F) -> Result<(), Error>
+    where
+        F: FnOnce(&Mime) -> bool,
+    {
+        let content_type = self.headers().get(CONTENT_TYPE);
+
+        match content_type
+            .and_then(|x| x.to_str().ok())
+            .and_then(|x| x.parse::<Mime>().ok())
+        {
+            Some(mime) if f(&mime) => Ok(()),
+            _ => Err(BodyError::UnsupportedContentType(
+                content_type
+                    .and_then(|x| x.to_str().ok())
+                    .unwrap_or_default()
+                    .into(),
+            )
+            .into()),
+        }
+    }
+
     /// Retrieve the value of a query string parameter.
     ///
     /// # Examples
@@ -228,9 +604,98 @@ impl Request {
     ///     }
     /// }
     /// ```
-    #[inline]
+    ///
+    /// If the parameter is repeated, the last value is returned.
     pub fn query(&self, name: &str) -> Option<&String> {
-        self.query.get(name)
+        self.query
+            .iter()
+            .rev()
+            .find(|(key, _)| key == name)
+            .map(|(_, value)| value)
+    }
+
+    /// Retrieve all the values of a repeated query string parameter.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use reign::prelude::*;
+    ///
+    /// async fn foo(req: &mut Request) -> Result<impl Response, Error> {
+    ///     let tags = req.query_all("tag");
+    ///
+    ///     Ok(tags.into_iter().cloned().collect::<Vec<_>>().join(","))
+    /// }
+    /// ```
+    pub fn query_all(&self, name: &str) -> Vec<&String> {
+        self.query
+            .iter()
+            .filter(|(key, _)| key == name)
+            .map(|(_, value)| value)
+            .collect()
+    }
+
+    /// Retrieve the value of a query string parameter converted to the given type.
+    ///
+    /// Responds with `400` if the value can't be converted.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use reign::prelude::*;
+    ///
+    /// async fn foo(req: &mut Request) -> Result<impl Response, Error> {
+    ///     let page = req.query_as::<u32>("page")?.unwrap_or(1);
+    ///
+    ///     Ok(format!("page {}", page))
+    /// }
+    /// ```
+    pub fn query_as<T>(&self, name: &str) -> Result<Option<T>, Error>
+    where
+        T: FromStr,
+    {
+        Ok(self.query(name).map_or_else(
+            || Ok(None),
+            |q| {
+                T::from_str(q)
+                    .map_err(|_| QueryError::UnableToConvertQuery(name.into()))
+                    .map(Some)
+            },
+        )?)
+    }
+
+    /// Deserialize the whole query string into the given type.
+    ///
+    /// Nested structures and sequences are supported by using brackets in the keys, for example
+    /// `user[name]=John&user[tags][]=admin`. Responds with `400` if the query string can't be
+    /// deserialized.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use reign::prelude::*;
+    /// use serde::Deserialize;
+    ///
+    /// #[derive(Deserialize)]
+    /// struct Search {
+    ///     term: String,
+    ///     page: Option<u32>,
+    /// }
+    ///
+    /// async fn foo(req: &mut Request) -> Result<impl Response, Error> {
+    ///     let search = req.query_struct::<Search>()?;
+    ///
+    ///     Ok(format!("{} on page {}", search.term, search.page.unwrap_or(1)))
+    /// }
+    /// ```
+    #[cfg(feature = "form")]
+    pub fn query_struct<T>(&self) -> Result<T, Error>
+    where
+        T: DeserializeOwned,
+    {
+        Ok(QsConfig::new(5, false)
+            .deserialize_str(self.uri().query().unwrap_or(""))
+            .map_err(|e| QueryError::UnableToDeserializeQuery(e.to_string()))?)
     }
 
     /// Retrieve the value of a required path parameter.
@@ -286,6 +751,43 @@ impl Request {
         )?)
     }
 
+    /// Used by `#[params]` to take the value of a typed path parameter which was already
+    /// converted when matching the route, falling back to converting it.
+    #[doc(hidden)]
+    pub fn take_param<T>(&mut self, name: &str) -> Result<T, Error>
+    where
+        T: FromStr + Any,
+    {
+        match self.take_parsed::<T>(name) {
+            Some(value) => Ok(value),
+            None => self.param(name),
+        }
+    }
+
+    #[doc(hidden)]
+    pub fn take_param_opt<T>(&mut self, name: &str) -> Result<Option<T>, Error>
+    where
+        T: FromStr + Any,
+    {
+        match self.take_parsed::<T>(name) {
+            Some(value) => Ok(Some(value)),
+            None => self.param_opt(name),
+        }
+    }
+
+    fn take_parsed<T>(&mut self, name: &str) -> Option<T>
+    where
+        T: Any,
+    {
+        match self.parsed.remove(name)?.downcast::<T>() {
+            Ok(value) => Some(*value),
+            Err(parsed) => {
+                self.parsed.insert(name.into(), parsed);
+                None
+            }
+        }
+    }
+
     /// Retrieve the value of a required glob path parameter.
     ///
     /// # Examples
@@ -429,6 +931,94 @@ impl Request {
             self.extensions_mut().insert(SessionData::<T>::None);
         }
     }
+
+    /// Rotate the identifier of the current session while keeping its data.
+    ///
+    /// Should be called when the privilege level of the session changes, for example on
+    /// login, to prevent session fixation attacks.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use reign::prelude::*;
+    /// use serde::{Deserialize, Serialize};
+    ///
+    /// #[derive(Serialize, Deserialize)]
+    /// struct User(String);
+    ///
+    /// async fn foo(req: &mut Request) -> Result<impl Response, Error> {
+    ///     req.regenerate_session::<User>();
+    ///     req.save_session(User("John".into()));
+    ///     Ok("Logged in")
+    /// }
+    /// ```
+    #[cfg(feature = "session")]
+    pub fn regenerate_session<T>(&mut self)
+    where
+        T: Serialize + for<'de> Deserialize<'de> + Send + Sync + 'static,
+    {
+        self.extensions_mut().insert(SessionRegenerate::<T>::new());
+    }
+
+    /// Store a flash message which will be available in the next request.
+    ///
+    /// Needs the [`Flash`](crate::middleware::session::Flash) middleware.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use reign::prelude::*;
+    ///
+    /// async fn foo(req: &mut Request) -> Result<impl Response, Error> {
+    ///     req.flash("notice", "Article created");
+    ///     Ok(redirect("/articles")?)
+    /// }
+    /// ```
+    #[cfg(feature = "session")]
+    pub fn flash<K, M>(&mut self, key: K, message: M)
+    where
+        K: Into<String>,
+        M: Into<String>,
+    {
+        if self.extensions().get::<FlashState>().is_none() {
+            self.extensions_mut().insert(FlashState::default());
+        }
+
+        if let Some(state) = self.extensions_mut().get_mut::<FlashState>() {
+            state.outgoing.insert(key.into(), message.into());
+        }
+    }
+
+    /// Retrieve the flash messages that were stored in the previous request.
+    ///
+    /// This clears the messages and they will not be available for any other handlers after this.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use reign::prelude::*;
+    ///
+    /// async fn foo(req: &mut Request) -> Result<impl Response, Error> {
+    ///     if let Some(notice) = req.flashes().get("notice") {
+    ///         Ok(notice.to_string())
+    ///     } else {
+    ///         Ok("No notice".into())
+    ///     }
+    /// }
+    /// ```
+    #[cfg(feature = "session")]
+    pub fn flashes(&mut self) -> Flashes {
+        self.extensions_mut()
+            .get_mut::<FlashState>()
+            .and_then(|state| {
+                state
+                    .incoming
+                    .lock()
+                    .ok()
+                    .map(|mut x| std::mem::take(&mut *x))
+            })
+            .unwrap_or_default()
+    }
 }
 
 #[cfg(test)]
@@ -447,6 +1037,162 @@ mod tests {
         req
     }
 
+    fn req_accept(val: &str) -> Request {
+        Request::new(
+            "10.10.10.10:80".parse().unwrap(),
+            HyperRequest::get("https://reign.rs")
+                .header(ACCEPT, val)
+                .body(Body::empty())
+                .unwrap(),
+        )
+    }
+
+    #[test]
+    fn test_accept() {
+        let req = req_accept("text/*;q=0.5, application/json, text/html;q=0.5, */*;q=0.1");
+
+        assert_eq!(
+            req.accept(),
+            vec![
+                mime::APPLICATION_JSON,
+                mime::TEXT_HTML,
+                mime::TEXT_STAR,
+                mime::STAR_STAR,
+            ]
+        );
+    }
+
+    #[test]
+    fn test_accept_zero_quality() {
+        let req = req_accept("text/html;q=0, */*");
+
+        assert_eq!(req.accept(), vec![mime::STAR_STAR]);
+        assert!(!req.accepts(&mime::TEXT_HTML));
+        assert!(req.accepts(&mime::APPLICATION_JSON));
+    }
+
+    #[test]
+    fn test_accept_invalid() {
+        let req = req_accept("nonsense, application/json");
+
+        assert_eq!(req.accept(), vec![mime::APPLICATION_JSON]);
+    }
+
+    #[test]
+    fn test_accepts_without_header() {
+        let req = req_param("hey");
+
+        assert!(req.accept().is_empty());
+        assert!(req.accepts(&mime::TEXT_HTML));
+    }
+
+    fn req_query(query: &str) -> Request {
+        Request::new(
+            "10.10.10.10:80".parse().unwrap(),
+            HyperRequest::get(format!("https://reign.rs/?{}", query))
+                .body(Body::empty())
+                .unwrap(),
+        )
+    }
+
+    #[test]
+    fn test_query_repeated() {
+        let req = req_query("tag=a&tag=b&page=2");
+
+        assert_eq!(req.query("tag").unwrap(), "b");
+        assert_eq!(req.query_all("tag"), vec!["a", "b"]);
+        assert!(req.query_all("none").is_empty());
+    }
+
+    #[test]
+    fn test_query_as() {
+        let req = req_query("page=2&name=hey");
+
+        assert_eq!(req.query_as::<u32>("page").unwrap(), Some(2));
+        assert_eq!(req.query_as::<u32>("none").unwrap(), None);
+        assert!(matches!(
+            req.query_as::<u32>("name"),
+            Err(Error::Query(QueryError::UnableToConvertQuery(_)))
+        ));
+    }
+
+    #[cfg(feature = "form")]
+    #[test]
+    fn test_query_struct() {
+        #[derive(serde::Deserialize, Debug, PartialEq)]
+        struct User {
+            name: String,
+            tags: Vec<String>,
+        }
+
+        #[derive(serde::Deserialize, Debug, PartialEq)]
+        struct Search {
+            page: u32,
+            user: User,
+        }
+
+        let req = req_query("page=2&user[name]=John&user[tags][]=a&user%5Btags%5D%5B%5D=b");
+
+        assert_eq!(
+            req.query_struct::<Search>().unwrap(),
+            Search {
+                page: 2,
+                user: User {
+                    name: "John".into(),
+                    tags: vec!["a".into(), "b".into()],
+                },
+            }
+        );
+
+        assert!(matches!(
+            req_query("page=hey").query_struct::<Search>(),
+            Err(Error::Query(QueryError::UnableToDeserializeQuery(_)))
+        ));
+    }
+
+    #[test]
+    fn test_host() {
+        let req = Request::new(
+            "10.10.10.10:80".parse().unwrap(),
+            HyperRequest::get("/")
+                .header(HOST, "acme.reign.rs:8080")
+                .body(Body::empty())
+                .unwrap(),
+        );
+
+        assert_eq!(req.host(), Some("acme.reign.rs"));
+        assert_eq!(req_query("").host(), Some("reign.rs"));
+    }
+
+    #[test]
+    fn test_state() {
+        let mut req = req_query("");
+        let mut state = State::new();
+
+        state.insert(
+            TypeId::of::<u8>(),
+            Arc::new(8u8) as Arc<dyn Any + Send + Sync>,
+        );
+        req.state = Arc::new(state);
+
+        assert_eq!(req.state::<u8>().unwrap(), 8);
+        assert!(matches!(
+            req.state::<u16>(),
+            Err(Error::State(StateError::RequiredStateNotFound("u16")))
+        ));
+    }
+
+    #[test]
+    fn test_take_param() {
+        let mut req = req_param("12");
+        req.parsed.insert("id".into(), Box::new(12u8));
+
+        assert!(matches!(req.take_param::<u32>("id"), Ok(12)));
+        assert!(matches!(req.take_param::<u8>("id"), Ok(12)));
+        assert!(req.parsed.is_empty());
+        assert!(matches!(req.take_param_opt::<u8>("none"), Ok(None)));
+    }
+
     #[test]
     fn test_param() {
         let req = req_param("hey");
@@ -1,6 +1,27 @@
-use crate::{hyper::Method, Handle, Path, Request};
+use crate::{
+    hyper::{
+        header::{HeaderName, HeaderValue},
+        Method,
+    },
+    Handle, Path, Request,
+};
 
-use std::sync::Arc;
+use mime::Mime;
+use regex::{escape, Regex};
+
+use std::{any::type_name, fmt, sync::Arc};
+
+pub(crate) const METHODS: [Method; 9] = [
+    Method::GET,
+    Method::POST,
+    Method::PUT,
+    Method::PATCH,
+    Method::DELETE,
+    Method::HEAD,
+    Method::OPTIONS,
+    Method::TRACE,
+    Method::CONNECT,
+];
 
 pub(crate) type Constraint = Box<dyn Fn(&Request) -> bool + Send + Sync + 'static>;
 
@@ -10,6 +31,7 @@ pub(crate) struct Route {
     pub(crate) methods: Vec<Method>,
     pub(crate) handle: Option<Arc<Box<dyn Handle>>>,
     pub(crate) constraint: Option<Arc<Constraint>>,
+    pub(crate) name: Option<&'static str>,
 }
 
 impl Route {
@@ -33,6 +55,12 @@ impl Route {
         H: Handle,
     {
         self.handle = Some(Arc::new(Box::new(handle)));
+        self.name = Some(type_name::<H>());
+        self
+    }
+
+    pub(crate) fn name(mut self, name: &'static str) -> Self {
+        self.name = Some(name);
         self
     }
 
@@ -44,21 +72,113 @@ impl Route {
         self
     }
 
-    pub(crate) fn regex(&self) -> (String, String) {
-        let methods = if self.methods.is_empty() {
-            "^(?:GET|POST|PUT|PATCH|DELETE|HEAD|OPTIONS|TRACE|CONNECT)".into()
-        } else {
-            format!(
-                "^(?:{})",
-                self.methods
+    pub(crate) fn regex(&self) -> String {
+        format!("{}$", self.path.regex())
+    }
+}
+
+/// Description of a route in the router as seen when matching requests.
+///
+/// Returned by [`Router::routes`](crate::Router::routes) and useful for debugging how scopes and
+/// pipes were combined.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RouteInfo {
+    /// HTTP methods allowed by the route. Empty if all methods are allowed.
+    pub methods: Vec<String>,
+    /// Full path pattern of the route including the scope prefixes.
+    pub path: String,
+    /// Type name of the endpoint handler, which is the path of the handler function.
+    pub name: Option<String>,
+    /// Middleware pipes that run for the route, outermost first.
+    pub pipes: Vec<String>,
+    /// Constraints that restrict matching of the route, outermost first.
+    ///
+    /// Constraints defined as closures are shown as `custom`.
+    pub constraints: Vec<String>,
+}
+
+/// Declarative constraint which is defined on a scope.
+#[derive(Debug)]
+pub(crate) enum Rule {
+    Host(String, Regex),
+    Methods(Vec<Method>),
+    Header(HeaderName, HeaderValue),
+    ContentType(Vec<Mime>),
+}
+
+impl Rule {
+    /// Converts the host pattern into a regex that captures the params in it.
+    pub(crate) fn host(pattern: &str) -> Self {
+        let mut regex = String::from("(?i)^");
+        let mut rest = pattern;
+
+        while let Some(start) = rest.find('{') {
+            let end = rest[start..]
+                .find('}')
+                .expect("Host pattern has an unclosed `{`")
+                + start;
+
+            regex.push_str(&escape(&rest[..start]));
+            regex.push_str(&format!("(?P<{}>[^.]+)", &rest[start + 1..end]));
+            rest = &rest[end + 1..];
+        }
+
+        regex.push_str(&escape(rest));
+        regex.push('$');
+
+        Self::Host(
+            pattern.to_string(),
+            Regex::new(&regex).expect("Host pattern is not valid"),
+        )
+    }
+
+    pub(crate) fn header(name: &str, value: &str) -> Self {
+        if name.to_lowercase() != name {
+            panic!("Only lowercase headers are allowed");
+        }
+
+        Self::Header(
+            HeaderName::from_lowercase(name.as_bytes()).unwrap(),
+            HeaderValue::from_str(value).unwrap(),
+        )
+    }
+
+    pub(crate) fn content_type(mimes: &[&str]) -> Self {
+        Self::ContentType(
+            mimes
+                .iter()
+                .map(|x| x.parse().expect("Content type is not valid"))
+                .collect(),
+        )
+    }
+}
+
+impl fmt::Display for Rule {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Host(pattern, _) => write!(f, "host = {}", pattern),
+            Self::Methods(methods) => write!(
+                f,
+                "methods = {}",
+                methods
                     .iter()
                     .map(|x| x.as_str())
                     .collect::<Vec<_>>()
-                    .join("|")
-            )
-        };
-
-        (methods, format!("{}$", self.path.regex()))
+                    .join(", ")
+            ),
+            Self::Header(name, value) => {
+                write!(f, "{} = {}", name, value.to_str().unwrap_or("<binary>"))
+            }
+            Self::ContentType(mimes) => write!(
+                f,
+                "content-type = {}",
+                mimes
+                    .iter()
+                    .map(|x| x.as_ref())
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            ),
+        }
     }
 }
 
@@ -67,29 +187,55 @@ mod test {
     use super::*;
 
     #[test]
-    fn test_regex_single_method() {
-        let r = Route::new("").methods(&[Method::GET]);
-        assert_eq!(r.regex().0, "^(?:GET)");
+    fn test_regex_path() {
+        let r = Route::new("");
+        assert_eq!(r.regex(), "$");
     }
 
     #[test]
-    fn test_regex_multi_methods() {
-        let r = Route::new("").methods(&[Method::GET, Method::POST]);
-        assert_eq!(r.regex().0, "^(?:GET|POST)");
+    fn test_host() {
+        if let Rule::Host(_, regex) = Rule::host("{tenant}.example.com") {
+            assert_eq!(regex.as_str(), "(?i)^(?P<tenant>[^.]+)\\.example\\.com$");
+            assert_eq!(
+                &regex.captures("Acme.example.com").unwrap()["tenant"],
+                "Acme"
+            );
+            assert!(!regex.is_match("a.b.example.com"));
+        } else {
+            unreachable!();
+        }
     }
 
     #[test]
-    fn test_regex_all_methods() {
-        let r = Route::new("");
+    fn test_host_static() {
+        if let Rule::Host(_, regex) = Rule::host("example.com") {
+            assert!(regex.is_match("example.com"));
+            assert!(!regex.is_match("exampleacom"));
+        } else {
+            unreachable!();
+        }
+    }
+
+    #[test]
+    fn test_display() {
         assert_eq!(
-            r.regex().0,
-            "^(?:GET|POST|PUT|PATCH|DELETE|HEAD|OPTIONS|TRACE|CONNECT)"
+            Rule::host("{tenant}.example.com").to_string(),
+            "host = {tenant}.example.com"
+        );
+        assert_eq!(
+            Rule::Methods(vec![Method::GET, Method::HEAD]).to_string(),
+            "methods = GET, HEAD"
+        );
+        assert_eq!(Rule::header("x-version", "2").to_string(), "x-version = 2");
+        assert_eq!(
+            Rule::content_type(&["application/json", "text/*"]).to_string(),
+            "content-type = application/json, text/*"
         );
     }
 
     #[test]
-    fn test_regex_path() {
-        let r = Route::new("");
-        assert_eq!(r.regex().1, "$");
+    #[should_panic(expected = "unclosed")]
+    fn test_host_unclosed() {
+        Rule::host("{tenant.example.com");
     }
 }
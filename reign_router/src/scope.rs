@@ -1,4 +1,4 @@
-use crate::{Constraint, Path, Pipe, Request, RouteRef, Router};
+use crate::{hyper::Method, Constraint, Path, Pipe, Request, RouteRef, Router, Rule, State};
 
 use std::{collections::HashMap as Map, sync::Arc};
 
@@ -40,6 +40,7 @@ pub struct Scope {
     pub(crate) pipes: Vec<String>,
     pub(crate) router: Router,
     pub(crate) constraint: Option<Arc<Constraint>>,
+    pub(crate) rules: Vec<Arc<Rule>>,
 }
 
 impl Scope {
@@ -135,17 +136,111 @@ impl Scope {
         self
     }
 
-    pub(crate) fn regex(&self) -> (String, Vec<(String, String)>) {
+    /// Restrict the routes under this scope to the hosts matching the given pattern.
+    ///
+    /// Parts of the pattern enclosed in braces are captured as params and can be accessed like
+    /// path params. The port is ignored when matching.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use reign::router::Router;
+    /// # use reign::prelude::*;
+    /// #
+    /// # async fn foo(req: &mut Request) -> Result<impl Response, Error> { Ok("foo") }
+    ///
+    /// fn router(r: &mut Router) {
+    ///     r.scope("").host("{tenant}.example.com").to(|r| {
+    ///         // `req.param::<String>("tenant")` is available in `foo`
+    ///         r.get("foo", foo);
+    ///     });
+    /// }
+    /// ```
+    pub fn host(&mut self, pattern: &str) -> &mut Self {
+        self.rules.push(Arc::new(Rule::host(pattern)));
+        self
+    }
+
+    /// Restrict the routes under this scope to the given HTTP methods.
+    ///
+    /// Requests with other methods are responded with `405` if their path matches.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use reign::router::{hyper::Method, Router};
+    /// # use reign::prelude::*;
+    /// #
+    /// # async fn foo(req: &mut Request) -> Result<impl Response, Error> { Ok("foo") }
+    ///
+    /// fn router(r: &mut Router) {
+    ///     r.scope("api").methods(&[Method::GET, Method::HEAD]).to(|r| {
+    ///         r.all("foo", foo);
+    ///     });
+    /// }
+    /// ```
+    pub fn methods(&mut self, methods: &[Method]) -> &mut Self {
+        self.rules.push(Arc::new(Rule::Methods(methods.to_vec())));
+        self
+    }
+
+    /// Restrict the routes under this scope to requests having the given header value.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use reign::router::Router;
+    /// # use reign::prelude::*;
+    /// #
+    /// # async fn foo(req: &mut Request) -> Result<impl Response, Error> { Ok("foo") }
+    ///
+    /// fn router(r: &mut Router) {
+    ///     r.scope("api").header("x-version", "2").to(|r| {
+    ///         r.get("foo", foo);
+    ///     });
+    /// }
+    /// ```
+    pub fn header(&mut self, name: &str, value: &str) -> &mut Self {
+        self.rules.push(Arc::new(Rule::header(name, value)));
+        self
+    }
+
+    /// Restrict the routes under this scope to requests having one of the given content types.
+    ///
+    /// Wildcards like `application/*` are supported. Requests with other content types are
+    /// responded with `415` if everything else matches.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use reign::router::Router;
+    /// # use reign::prelude::*;
+    /// #
+    /// # async fn foo(req: &mut Request) -> Result<impl Response, Error> { Ok("foo") }
+    ///
+    /// fn router(r: &mut Router) {
+    ///     r.scope("api").content_type(&["application/json"]).to(|r| {
+    ///         r.post("foo", foo);
+    ///     });
+    /// }
+    /// ```
+    pub fn content_type(&mut self, mimes: &[&str]) -> &mut Self {
+        self.rules.push(Arc::new(Rule::content_type(mimes)));
+        self
+    }
+
+    pub(crate) fn regex(&self) -> (String, Vec<String>) {
         (self.path.regex(), self.router.regex())
     }
 
     pub(crate) fn refs(
         &self,
         upper_pipes: Map<&String, &Pipe>,
+        upper_state: &State,
     ) -> (Option<Arc<Constraint>>, Vec<RouteRef>, Vec<String>) {
         (
             self.constraint.clone(),
-            self.router.refs(upper_pipes),
+            self.router.refs(upper_pipes, upper_state),
             self.pipes.clone(),
         )
     }
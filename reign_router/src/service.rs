@@ -1,20 +1,127 @@
 use crate::{
+    futures::FutureExt,
     hyper::{
-        http::Error as HttpError, Body, Request as HyperRequest, Response as HyperResponse,
-        StatusCode,
+        header::{HeaderValue, ALLOW, CONTENT_TYPE},
+        http::Error as HttpError,
+        service::Service as HyperService,
+        Body, Method, Request as HyperRequest, Response as HyperResponse, StatusCode,
     },
-    Chain, Constraint, Handle, MiddlewareItem, Request, Response, Router, INTERNAL_ERR,
+    path::Typed,
+    request::mime_matches,
+    route::METHODS,
+    Chain, Constraint, Handle, MiddlewareItem, Request, Response, RouteInfo, Router, Rule, State,
+    INTERNAL_ERR,
 };
 
+use mime::Mime;
+
 use log::{debug, error, info, trace};
 use regex::{Regex, RegexSet};
 
-use std::{collections::HashMap as Map, net::SocketAddr, sync::Arc};
+use std::{
+    collections::HashMap as Map,
+    future::Future,
+    net::SocketAddr,
+    pin::Pin,
+    sync::Arc,
+    task::{Context, Poll},
+};
 
 pub(crate) struct RouteRef {
     pub(crate) handle: Option<Arc<Box<dyn Handle>>>,
     pub(crate) middlewares: Vec<Arc<MiddlewareItem>>,
     pub(crate) constraints: Vec<Option<Arc<Constraint>>>,
+    pub(crate) typed: Vec<(String, Typed)>,
+    pub(crate) methods: Vec<Method>,
+    pub(crate) rules: Vec<Arc<Rule>>,
+    pub(crate) state: Arc<State>,
+}
+
+/// Result of checking whether a route can respond to a request whose path matched.
+enum Check {
+    Matched,
+    NotFound,
+    MethodNotAllowed(Vec<Method>),
+    UnsupportedMediaType,
+}
+
+impl RouteRef {
+    fn check(&self, req: &mut Request) -> Check {
+        // Typed params are converted once here so that they don't need to be converted again
+        for (name, typed) in &self.typed {
+            if let Some(value) = req.params.get(name) {
+                if let Some(parsed) = (typed.parse)(value) {
+                    req.parsed.insert(name.clone(), parsed);
+                } else {
+                    return Check::NotFound;
+                }
+            }
+        }
+
+        let mut methods = if self.methods.is_empty() {
+            METHODS.to_vec()
+        } else {
+            self.methods.clone()
+        };
+        let mut mimes = vec![];
+
+        for rule in &self.rules {
+            match &**rule {
+                Rule::Host(_, regex) => {
+                    let captures = match req.host().and_then(|host| regex.captures(host)) {
+                        Some(captures) => captures,
+                        None => return Check::NotFound,
+                    };
+
+                    let params = regex
+                        .capture_names()
+                        .flatten()
+                        .filter_map(|name| {
+                            captures
+                                .name(name)
+                                .map(|value| (name.to_string(), value.as_str().to_string()))
+                        })
+                        .collect::<Vec<_>>();
+
+                    req.params.extend(params);
+                }
+                Rule::Header(name, value) => {
+                    if req.headers().get(name) != Some(value) {
+                        return Check::NotFound;
+                    }
+                }
+                Rule::Methods(allowed) => methods.retain(|x| allowed.contains(x)),
+                Rule::ContentType(allowed) => mimes.push(allowed),
+            }
+        }
+
+        for constraint in self.constraints.iter().flatten() {
+            if !constraint(req) {
+                return Check::NotFound;
+            }
+        }
+
+        if !methods.contains(req.method()) {
+            return Check::MethodNotAllowed(methods);
+        }
+
+        let content_type = req
+            .headers()
+            .get(CONTENT_TYPE)
+            .and_then(|x| x.to_str().ok())
+            .and_then(|x| x.parse::<Mime>().ok());
+
+        for allowed in mimes {
+            if !content_type
+                .as_ref()
+                .map_or(false, |mime| allowed.iter().any(|x| mime_matches(x, mime)))
+            {
+                return Check::UnsupportedMediaType;
+            }
+        }
+
+        Check::Matched
+    }
 }
 
 /// Thread safe structure that optimizes the given router for responding to requests.
@@ -28,12 +135,12 @@ pub struct Service {
 
 impl Service {
     pub(crate) fn new(router: Router) -> Self {
-        let refs = router.refs(Map::new());
+        let refs = router.refs(Map::new(), &State::new());
 
         let regexes = router
             .regex()
             .iter()
-            .map(|x| format!("{}{}", x.0, x.1))
+            .map(|x| format!("^{}", x))
             .collect::<Vec<_>>();
 
         debug!("Route regexes: {:?}", regexes);
@@ -51,6 +158,13 @@ impl Service {
         }
     }
 
+    /// List the routes of the router in the order they are matched.
+    ///
+    /// See [`Router::routes`] for more details.
+    pub fn routes(&self) -> Vec<RouteInfo> {
+        self.router.routes()
+    }
+
     /// Respond to a given [`hyper::Request`] and IP address.
     ///
     /// # Examples
@@ -82,11 +196,12 @@ impl Service {
     ) -> Result<HyperResponse<Body>, HttpError> {
         trace!("Incoming request to router");
 
-        let to_match = format!("{}{}", req.method().as_str(), req.uri().path());
-        let to_match = to_match.trim_end_matches('/');
-        let matches = self.regex_set.matches(to_match);
+        let to_match = req.uri().path().trim_end_matches('/').to_string();
+        let matches = self.regex_set.matches(&to_match);
 
         let mut request = Request::new(ip, req);
+        let mut allowed = vec![];
+        let mut unsupported = false;
 
         for m in matches {
             let regex = self.regexes.get(m).expect(INTERNAL_ERR);
@@ -94,41 +209,70 @@ impl Service {
             debug!("Checking regex: {:?}", regex);
 
             let mut params = Map::new();
-            let captures = regex.captures(to_match).expect(INTERNAL_ERR);
+            let captures = regex.captures(&to_match).expect(INTERNAL_ERR);
 
-            for name in regex.capture_names() {
-                if let Some(name) = name {
-                    if let Some(value) = captures.name(name) {
-                        params.insert(name.to_string(), value.as_str().to_string());
-                    }
+            for name in regex.capture_names().flatten() {
+                if let Some(value) = captures.name(name) {
+                    params.insert(name.to_string(), value.as_str().to_string());
                 }
             }
 
             debug!("Params extracted: {:?}", params);
 
             request.params = params;
+            request.parsed = Map::new();
 
             if let Some(route) = self.refs.get(m) {
-                let mut matched = true;
-
-                for constraint in &route.constraints {
-                    if let Some(constraint) = constraint {
-                        matched = constraint(&request);
-
-                        if !matched {
-                            break;
+                match route.check(&mut request) {
+                    Check::Matched => {
+                        if let Some(handle) = &route.handle {
+                            return Self::run(handle, request, route).await;
+                        }
+                    }
+                    Check::MethodNotAllowed(methods) => {
+                        for method in methods {
+                            if !allowed.contains(&method) {
+                                allowed.push(method);
+                            }
                         }
                     }
+                    Check::UnsupportedMediaType => unsupported = true,
+                    Check::NotFound => {}
                 }
+            }
+        }
 
-                if !matched {
-                    continue;
-                }
+        // TODO: Support custom error handler through post middleware
+        // Can make this a special error or make a special middleware pipeline for errors
+        if unsupported {
+            info!(
+                "{} {} - 415 Unsupported Media Type",
+                request.method(),
+                request.uri().path()
+            );
 
-                if let Some(handle) = &route.handle {
-                    return Self::run(handle, request, route).await;
-                }
-            }
+            return HyperResponse::builder()
+                .status(StatusCode::UNSUPPORTED_MEDIA_TYPE)
+                .body(Body::empty());
+        }
+
+        if !allowed.is_empty() {
+            info!(
+                "{} {} - 405 Method Not Allowed",
+                request.method(),
+                request.uri().path()
+            );
+
+            let allow = allowed
+                .iter()
+                .map(|x| x.as_str())
+                .collect::<Vec<_>>()
+                .join(", ");
+
+            return HyperResponse::builder()
+                .status(StatusCode::METHOD_NOT_ALLOWED)
+                .header(ALLOW, HeaderValue::from_str(&allow)?)
+                .body(Body::empty());
         }
 
         info!(
@@ -137,8 +281,6 @@ impl Service {
             request.uri().path()
         );
 
-        // TODO: Check for 405 and support custom error handler through post middleware
-        // Can make this a special error or make a special middleware pipeline for errors
         Ok(HyperResponse::builder()
             .status(StatusCode::NOT_FOUND)
             .body(Body::empty())?)
@@ -154,6 +296,8 @@ impl Service {
             middlewares: &route.middlewares,
         };
 
+        request.state = route.state.clone();
+
         match chain.run(&mut request).await {
             Ok(r) => Ok(r),
             Err(err) => {
@@ -164,31 +308,51 @@ impl Service {
     }
 }
 
+/// Allows the service to be used wherever a [`hyper::service::Service`] is expected, for example
+/// when mounting it in another router.
+///
+/// The IP address is taken from the request extensions if available.
+impl HyperService<HyperRequest<Body>> for Service {
+    type Response = HyperResponse<Body>;
+    type Error = HttpError;
+    type Future = Pin<Box<dyn Future<Output = Result<Self::Response, Self::Error>> + Send>>;
+
+    fn poll_ready(&mut self, _: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        Poll::Ready(Ok(()))
+    }
+
+    fn call(&mut self, req: HyperRequest<Body>) -> Self::Future {
+        let ip = req
+            .extensions()
+            .get::<SocketAddr>()
+            .copied()
+            .unwrap_or_else(|| ([0, 0, 0, 0], 0).into());
+
+        self.clone().call(req, ip).boxed()
+    }
+}
+
 /// Converts the router into a service that responds to a given [`hyper::Request`].
 ///
-/// Useful in tests without needing to spin up the server.
+/// Useful in tests without needing to spin up the server, especially along with
+/// [`TestClient`](crate::test::TestClient).
 ///
 /// # Examples
 ///
-/// ```no_run
-/// use reign::router::{service, Router};
+/// ```
+/// use reign::{
+///     router::{service, Router},
+///     test::TestClient,
+/// };
 ///
 /// fn router(r: &mut Router) {}
 ///
-/// #[tokio::test]
-/// async fn test() {
-///     let service = service(router);
+/// # #[tokio::main(flavor = "current_thread")]
+/// # async fn main() {
+/// let mut client = TestClient::new(service(router));
 ///
-///     let response = service
-///         .call(
-///             Req::get("https://reign.rs/get")
-///                 .body(Body::empty())
-///                 .unwrap(),
-///             "10.10.10.10:80".parse().unwrap(),
-///         )
-///         .await
-///         .unwrap();
-/// }
+/// client.get("/get").send().await.assert_status(404);
+/// # }
 /// ```
 pub fn service<R>(f: R) -> Service
 where
@@ -0,0 +1,376 @@
+//! Contains the client for testing a router without spinning up the server
+
+use crate::{
+    hyper::{
+        body::{to_bytes, Bytes},
+        header::{HeaderName, HeaderValue, CONTENT_TYPE, COOKIE, SET_COOKIE},
+        http::{request::Builder, Error as HttpError},
+        Body, HeaderMap, Method, Request as HyperRequest, StatusCode,
+    },
+    Service,
+};
+
+#[cfg(feature = "json")]
+use serde::de::DeserializeOwned;
+#[cfg(any(feature = "form", feature = "json"))]
+use serde::Serialize;
+#[cfg(feature = "json")]
+use serde_json::{from_slice, to_value, Value};
+
+use std::{collections::BTreeMap, net::SocketAddr};
+
+/// Client that sends requests to a [`Service`] directly.
+///
+/// Cookies set by the responses are stored and sent with the subsequent requests, which makes
+/// it suitable for testing sessions and flash messages.
+///
+/// # Examples
+///
+/// ```
+/// use reign::{router::service, test::TestClient};
+/// # use reign::prelude::*;
+/// #
+/// # async fn foo(req: &mut Request) -> Result<impl Response, Error> { Ok("foo") }
+///
+/// # #[tokio::main(flavor = "current_thread")]
+/// # async fn main() {
+/// let mut client = TestClient::new(service(|r| {
+///     r.get("foo", foo);
+/// }));
+///
+/// client
+///     .get("/foo")
+///     .send()
+///     .await
+///     .assert_status(200)
+///     .assert_body_contains("foo");
+/// # }
+/// ```
+pub struct TestClient {
+    service: Service,
+    ip: SocketAddr,
+    cookies: BTreeMap<String, String>,
+}
+
+macro_rules! method {
+    ($method:ident, $name:ident) => {
+        #[doc = concat!(" Start building a `", stringify!($name), "` request to the given path.")]
+        pub fn $method(&mut self, path: &str) -> TestRequest<'_> {
+            self.request(Method::$name, path)
+        }
+    };
+}
+
+impl TestClient {
+    /// Create a client for the given service.
+    pub fn new(service: Service) -> Self {
+        Self {
+            service,
+            ip: ([127, 0, 0, 1], 0).into(),
+            cookies: BTreeMap::new(),
+        }
+    }
+
+    /// Change the remote IP address the requests are sent from.
+    #[inline]
+    pub fn ip(mut self, ip: SocketAddr) -> Self {
+        self.ip = ip;
+        self
+    }
+
+    /// Start building a request with the given method to the given path.
+    pub fn request(&mut self, method: Method, path: &str) -> TestRequest<'_> {
+        let uri = if path.starts_with('/') {
+            format!("http://localhost{}", path)
+        } else {
+            path.to_string()
+        };
+
+        TestRequest {
+            client: self,
+            builder: HyperRequest::builder().method(method).uri(uri),
+            cookies: vec![],
+            body: Body::empty(),
+        }
+    }
+
+    method!(get, GET);
+    method!(post, POST);
+    method!(put, PUT);
+    method!(patch, PATCH);
+    method!(delete, DELETE);
+    method!(head, HEAD);
+    method!(options, OPTIONS);
+
+    /// Retrieve the value of a cookie stored in the cookie jar.
+    pub fn cookie(&self, name: &str) -> Option<&str> {
+        self.cookies.get(name).map(|x| x.as_str())
+    }
+
+    /// Store a cookie in the cookie jar so that it is sent with all the subsequent requests.
+    pub fn set_cookie(&mut self, name: &str, value: &str) -> &mut Self {
+        self.cookies.insert(name.to_string(), value.to_string());
+        self
+    }
+
+    /// Remove all the cookies from the cookie jar.
+    pub fn clear_cookies(&mut self) -> &mut Self {
+        self.cookies.clear();
+        self
+    }
+
+    fn store_cookies(&mut self, headers: &HeaderMap) {
+        for value in headers.get_all(SET_COOKIE) {
+            let value = match value.to_str() {
+                Ok(value) => value,
+                Err(_) => continue,
+            };
+
+            let mut parts = value.split(';').map(|x| x.trim());
+            let (name, value) = match parts.next().and_then(|x| x.split_once('=')) {
+                Some(pair) => pair,
+                None => continue,
+            };
+
+            let removed = parts.any(|x| x.eq_ignore_ascii_case("max-age=0"));
+
+            if removed || value.is_empty() {
+                self.cookies.remove(name);
+            } else {
+                self.cookies.insert(name.to_string(), value.to_string());
+            }
+        }
+    }
+}
+
+/// Request that is being built by [`TestClient`].
+pub struct TestRequest<'a> {
+    client: &'a mut TestClient,
+    builder: Builder,
+    cookies: Vec<(String, String)>,
+    body: Body,
+}
+
+impl<'a> TestRequest<'a> {
+    /// Add a header to the request.
+    #[inline]
+    pub fn header<K, V>(mut self, name: K, value: V) -> Self
+    where
+        HeaderName: TryFrom<K>,
+        <HeaderName as TryFrom<K>>::Error: Into<HttpError>,
+        HeaderValue: TryFrom<V>,
+        <HeaderValue as TryFrom<V>>::Error: Into<HttpError>,
+    {
+        self.builder = self.builder.header(name, value);
+        self
+    }
+
+    /// Add a cookie to only this request along with the ones in the cookie jar.
+    #[inline]
+    pub fn cookie(mut self, name: &str, value: &str) -> Self {
+        self.cookies.push((name.to_string(), value.to_string()));
+        self
+    }
+
+    /// Set the body of the request.
+    #[inline]
+    pub fn body<B>(mut self, body: B) -> Self
+    where
+        B: Into<Body>,
+    {
+        self.body = body.into();
+        self
+    }
+
+    /// Set the body of the request to the given value serialized as JSON.
+    #[cfg(feature = "json")]
+    pub fn json<T>(self, value: &T) -> Self
+    where
+        T: Serialize,
+    {
+        let body = serde_json::to_vec(value).expect("Unable to serialize the JSON body");
+
+        self.header(CONTENT_TYPE, mime::APPLICATION_JSON.as_ref())
+            .body(body)
+    }
+
+    /// Set the body of the request to the given value serialized as an urlencoded form.
+    #[cfg(feature = "form")]
+    pub fn form<T>(self, value: &T) -> Self
+    where
+        T: Serialize,
+    {
+        let body = serde_qs::to_string(value).expect("Unable to serialize the form body");
+
+        self.header(CONTENT_TYPE, mime::APPLICATION_WWW_FORM_URLENCODED.as_ref())
+            .body(body)
+    }
+
+    /// Send the request to the service and wait for the whole response.
+    ///
+    /// Panics if the request is not valid.
+    pub async fn send(self) -> TestResponse {
+        let Self {
+            client,
+            mut builder,
+            cookies,
+            body,
+        } = self;
+
+        let cookie = client
+            .cookies
+            .iter()
+            .map(|(name, value)| (name.as_str(), value.as_str()))
+            .chain(
+                cookies
+                    .iter()
+                    .map(|(name, value)| (name.as_str(), value.as_str())),
+            )
+            .map(|(name, value)| format!("{}={}", name, value))
+            .collect::<Vec<_>>();
+
+        if !cookie.is_empty() {
+            builder = builder.header(COOKIE, cookie.join("; "));
+        }
+
+        let req = builder
+            .body(body)
+            .expect("Unable to build the test request");
+
+        let res = client
+            .service
+            .clone()
+            .call(req, client.ip)
+            .await
+            .expect("Unable to build the response");
+
+        client.store_cookies(res.headers());
+
+        let status = res.status();
+        let headers = res.headers().clone();
+        let body = to_bytes(res.into_body())
+            .await
+            .expect("Unable to read the response body");
+
+        TestResponse {
+            status,
+            headers,
+            body,
+        }
+    }
+}
+
+/// Response received by [`TestClient`] along with assertions on it.
+///
+/// The assertions panic with a message describing the response if they fail.
+#[derive(Debug)]
+pub struct TestResponse {
+    status: StatusCode,
+    headers: HeaderMap,
+    body: Bytes,
+}
+
+impl TestResponse {
+    /// Returns the status of the response.
+    #[inline]
+    pub fn status(&self) -> StatusCode {
+        self.status
+    }
+
+    /// Returns the headers of the response.
+    #[inline]
+    pub fn headers(&self) -> &HeaderMap {
+        &self.headers
+    }
+
+    /// Returns the value of the given header if it is present and valid UTF-8.
+    pub fn header(&self, name: &str) -> Option<&str> {
+        self.headers.get(name).and_then(|x| x.to_str().ok())
+    }
+
+    /// Returns the body of the response.
+    #[inline]
+    pub fn body(&self) -> &Bytes {
+        &self.body
+    }
+
+    /// Returns the body of the response as a string, replacing invalid UTF-8.
+    pub fn text(&self) -> String {
+        String::from_utf8_lossy(&self.body).into_owned()
+    }
+
+    /// Deserializes the body of the response from JSON.
+    #[cfg(feature = "json")]
+    pub fn json<T>(&self) -> T
+    where
+        T: DeserializeOwned,
+    {
+        from_slice(&self.body).unwrap_or_else(|e| {
+            panic!(
+                "Unable to deserialize the response body `{}`: {}",
+                self.text(),
+                e
+            )
+        })
+    }
+
+    /// Asserts that the response has the given status.
+    #[track_caller]
+    pub fn assert_status(&self, status: u16) -> &Self {
+        let status = StatusCode::from_u16(status).expect("Expected status is not valid");
+
+        assert_eq!(
+            self.status,
+            status,
+            "Expected status {} but got {} with body `{}`",
+            status,
+            self.status,
+            self.text()
+        );
+        self
+    }
+
+    /// Asserts that the response has the given header with the given value.
+    #[track_caller]
+    pub fn assert_header(&self, name: &str, value: &str) -> &Self {
+        assert_eq!(
+            self.header(name),
+            Some(value),
+            "Expected header `{}` to be `{}`",
+            name,
+            value
+        );
+        self
+    }
+
+    /// Asserts that the body of the response contains the given text.
+    #[track_caller]
+    pub fn assert_body_contains(&self, text: &str) -> &Self {
+        let body = self.text();
+
+        assert!(
+            body.contains(text),
+            "Expected body to contain `{}` but got `{}`",
+            text,
+            body
+        );
+        self
+    }
+
+    /// Asserts that the body of the response is JSON equal to the given value.
+    #[cfg(feature = "json")]
+    #[track_caller]
+    pub fn assert_json<T>(&self, value: &T) -> &Self
+    where
+        T: Serialize,
+    {
+        let expected = to_value(value).expect("Unable to serialize the expected JSON");
+
+        assert_eq!(
+            self.json::<Value>(),
+            expected,
+            "Expected JSON body to be equal"
+        );
+        self
+    }
+}
@@ -0,0 +1,166 @@
+use reign::prelude::*;
+use reign_router::{
+    middleware::session::{MemoryBackend, Session},
+    service,
+    test::TestClient,
+};
+use serde::{Deserialize, Serialize};
+
+#[derive(Serialize, Deserialize, Clone)]
+struct User(String);
+
+#[derive(Serialize, Deserialize)]
+struct Article {
+    title: String,
+    tags: Vec<String>,
+}
+
+async fn login(req: &mut Request) -> Result<impl Response, Error> {
+    req.save_session(User("John".into()));
+    Ok("login")
+}
+
+async fn show(req: &mut Request) -> Result<impl Response, Error> {
+    Ok(req
+        .session::<User>()
+        .map(|user| user.0.clone())
+        .unwrap_or_else(|| "anonymous".into()))
+}
+
+async fn logout(req: &mut Request) -> Result<impl Response, Error> {
+    req.delete_session::<User>();
+    Ok("logout")
+}
+
+async fn create(req: &mut Request) -> Result<impl Response, Error> {
+    let article = req.json::<Article>().await?;
+
+    Ok(json(article, 201)?)
+}
+
+async fn submit(req: &mut Request) -> Result<impl Response, Error> {
+    let article = req.form::<Article>().await?;
+
+    Ok(format!("{} {}", article.title, article.tags.join(",")))
+}
+
+async fn agent(req: &mut Request) -> Result<impl Response, Error> {
+    Ok(format!(
+        "{} {}",
+        req.headers()
+            .get("user-agent")
+            .map_or("none", |x| x.to_str().unwrap()),
+        req.cookie_as::<String>("theme")?.unwrap_or_default()
+    ))
+}
+
+fn client() -> TestClient {
+    TestClient::new(service(|r| {
+        r.pipe("app")
+            .add(Session::<User, _>::new(MemoryBackend::new(60)));
+
+        r.scope("").through(&["app"]).to(|r| {
+            r.get("login", login);
+            r.get("show", show);
+            r.get("logout", logout);
+        });
+
+        r.post("articles", create);
+        r.post("submit", submit);
+        r.get("agent", agent);
+    }))
+}
+
+#[tokio::test]
+async fn test_client_session() {
+    let mut client = client();
+
+    client
+        .get("/show")
+        .send()
+        .await
+        .assert_status(200)
+        .assert_body_contains("anonymous");
+
+    client.get("/login").send().await.assert_status(200);
+    assert!(client.cookie("_reign_session").is_some());
+
+    client
+        .get("/show")
+        .send()
+        .await
+        .assert_body_contains("John");
+
+    client.get("/logout").send().await;
+    assert!(client.cookie("_reign_session").is_none());
+
+    client
+        .get("/show")
+        .send()
+        .await
+        .assert_body_contains("anonymous");
+}
+
+#[tokio::test]
+async fn test_client_json() {
+    let article = Article {
+        title: "Reign".into(),
+        tags: vec!["rust".into()],
+    };
+
+    let res = client().post("/articles").json(&article).send().await;
+
+    res.assert_status(201)
+        .assert_header("content-type", "application/json")
+        .assert_json(&article);
+
+    assert_eq!(res.json::<Article>().title, "Reign");
+}
+
+#[tokio::test]
+async fn test_client_form() {
+    let article = Article {
+        title: "Reign".into(),
+        tags: vec!["rust".into(), "web".into()],
+    };
+
+    client()
+        .post("/submit")
+        .form(&article)
+        .send()
+        .await
+        .assert_status(200)
+        .assert_body_contains("Reign rust,web");
+}
+
+#[tokio::test]
+async fn test_client_headers_cookies() {
+    let mut client = client();
+
+    client
+        .get("/agent")
+        .header("user-agent", "tester")
+        .cookie("theme", "dark")
+        .send()
+        .await
+        .assert_body_contains("tester dark");
+
+    client
+        .get("/agent")
+        .send()
+        .await
+        .assert_body_contains("none ");
+
+    client.set_cookie("theme", "light");
+    client
+        .get("/agent")
+        .send()
+        .await
+        .assert_body_contains("light");
+}
+
+#[tokio::test]
+#[should_panic(expected = "Expected status 404 Not Found but got 200 OK")]
+async fn test_client_assert_status() {
+    client().get("/show").send().await.assert_status(404);
+}
@@ -45,3 +45,225 @@ async fn test_constraint() {
     assert_eq!(res.status(), StatusCode::OK);
     assert_eq!(to_bytes(res.into_body()).await.unwrap(), "index");
 }
+
+#[tokio::test]
+async fn test_host() {
+    async fn tenant(req: &mut Request) -> Result<impl Response, Error> {
+        req.param::<String>("tenant")
+    }
+
+    let service = service(|r| {
+        r.scope("").host("{tenant}.reign.rs").to(|r| {
+            r.get("foo", tenant);
+        });
+    });
+
+    let res = service
+        .clone()
+        .call(
+            Req::get("https://acme.reign.rs:8080/foo")
+                .body(Body::empty())
+                .unwrap(),
+            "10.10.10.10:80".parse().unwrap(),
+        )
+        .await
+        .unwrap();
+
+    assert_eq!(res.status(), StatusCode::OK);
+    assert_eq!(to_bytes(res.into_body()).await.unwrap(), "acme");
+
+    let res = service
+        .clone()
+        .call(
+            Req::get("/foo")
+                .header("host", "beta.reign.rs")
+                .body(Body::empty())
+                .unwrap(),
+            "10.10.10.10:80".parse().unwrap(),
+        )
+        .await
+        .unwrap();
+
+    assert_eq!(to_bytes(res.into_body()).await.unwrap(), "beta");
+
+    let res = service
+        .clone()
+        .call(
+            Req::get("https://reign.rs/foo")
+                .body(Body::empty())
+                .unwrap(),
+            "10.10.10.10:80".parse().unwrap(),
+        )
+        .await
+        .unwrap();
+
+    assert_eq!(res.status(), StatusCode::NOT_FOUND);
+
+    let res = service
+        .call(
+            Req::post("https://acme.reign.rs/foo")
+                .body(Body::empty())
+                .unwrap(),
+            "10.10.10.10:80".parse().unwrap(),
+        )
+        .await
+        .unwrap();
+
+    assert_eq!(res.status(), StatusCode::METHOD_NOT_ALLOWED);
+}
+
+#[tokio::test]
+async fn test_header() {
+    async fn v1(_: &mut Request) -> Result<impl Response, Error> {
+        Ok("v1")
+    }
+
+    async fn v2(_: &mut Request) -> Result<impl Response, Error> {
+        Ok("v2")
+    }
+
+    let service = service(|r| {
+        r.scope("").header("x-version", "2").to(|r| {
+            r.get("foo", v2);
+        });
+        r.scope("").to(|r| {
+            r.get("foo", v1);
+        });
+    });
+
+    let res = service
+        .clone()
+        .call(
+            Req::get("https://reign.rs/foo")
+                .header("x-version", "2")
+                .body(Body::empty())
+                .unwrap(),
+            "10.10.10.10:80".parse().unwrap(),
+        )
+        .await
+        .unwrap();
+
+    assert_eq!(to_bytes(res.into_body()).await.unwrap(), "v2");
+
+    let res = service
+        .call(
+            Req::get("https://reign.rs/foo")
+                .body(Body::empty())
+                .unwrap(),
+            "10.10.10.10:80".parse().unwrap(),
+        )
+        .await
+        .unwrap();
+
+    assert_eq!(to_bytes(res.into_body()).await.unwrap(), "v1");
+}
+
+#[tokio::test]
+async fn test_content_type() {
+    async fn index(_: &mut Request) -> Result<impl Response, Error> {
+        Ok("index")
+    }
+
+    let service = service(|r| {
+        r.scope("")
+            .content_type(&["application/json", "text/*"])
+            .to(|r| {
+                r.post("foo", index);
+            });
+    });
+
+    let res = service
+        .clone()
+        .call(
+            Req::post("https://reign.rs/foo")
+                .header("content-type", "text/plain; charset=utf-8")
+                .body(Body::empty())
+                .unwrap(),
+            "10.10.10.10:80".parse().unwrap(),
+        )
+        .await
+        .unwrap();
+
+    assert_eq!(res.status(), StatusCode::OK);
+
+    let res = service
+        .clone()
+        .call(
+            Req::post("https://reign.rs/foo")
+                .header("content-type", "application/xml")
+                .body(Body::empty())
+                .unwrap(),
+            "10.10.10.10:80".parse().unwrap(),
+        )
+        .await
+        .unwrap();
+
+    assert_eq!(res.status(), StatusCode::UNSUPPORTED_MEDIA_TYPE);
+
+    let res = service
+        .call(
+            Req::get("https://reign.rs/foo")
+                .header("content-type", "application/xml")
+                .body(Body::empty())
+                .unwrap(),
+            "10.10.10.10:80".parse().unwrap(),
+        )
+        .await
+        .unwrap();
+
+    assert_eq!(res.status(), StatusCode::METHOD_NOT_ALLOWED);
+}
+
+#[tokio::test]
+async fn test_host_ipv6() {
+    async fn host(req: &mut Request) -> Result<impl Response, Error> {
+        Ok(req.host().unwrap_or("").to_string())
+    }
+
+    let service = service(|r| {
+        r.scope("").host("[::1]").to(|r| {
+            r.get("foo", host);
+        });
+    });
+
+    let res = service
+        .clone()
+        .call(
+            Req::get("/foo")
+                .header("host", "[::1]:8080")
+                .body(Body::empty())
+                .unwrap(),
+            "10.10.10.10:80".parse().unwrap(),
+        )
+        .await
+        .unwrap();
+
+    assert_eq!(res.status(), StatusCode::OK);
+    assert_eq!(to_bytes(res.into_body()).await.unwrap(), "[::1]");
+
+    let res = service
+        .clone()
+        .call(
+            Req::get("/foo")
+                .header("host", "[::1]")
+                .body(Body::empty())
+                .unwrap(),
+            "10.10.10.10:80".parse().unwrap(),
+        )
+        .await
+        .unwrap();
+
+    assert_eq!(res.status(), StatusCode::OK);
+
+    let res = service
+        .call(
+            Req::get("https://[::1]:8080/foo")
+                .body(Body::empty())
+                .unwrap(),
+            "10.10.10.10:80".parse().unwrap(),
+        )
+        .await
+        .unwrap();
+
+    assert_eq!(to_bytes(res.into_body()).await.unwrap(), "[::1]");
+}
@@ -0,0 +1,168 @@
+use reign_router::{
+    helpers::redirect,
+    hyper::{body::to_bytes, header, Body, Request as Req, Response as HyperResponse, StatusCode},
+    middleware::session::{Flash, Flashes, MemoryBackend},
+    service, Error, Request, Response,
+};
+
+async fn create(req: &mut Request) -> Result<impl Response, Error> {
+    req.flash("notice", "Article created");
+    Ok(redirect("/show")?)
+}
+
+async fn show(req: &mut Request) -> Result<impl Response, Error> {
+    let flashes = req.flashes();
+
+    Ok(flashes.get("notice").unwrap_or("none").to_string())
+}
+
+async fn view(_: &mut Request) -> Result<impl Response, Error> {
+    Ok(Flashes::current()
+        .iter()
+        .map(|(k, m)| format!("{}: {}", k, m))
+        .collect::<Vec<_>>()
+        .join(", "))
+}
+
+async fn consumed(req: &mut Request) -> Result<impl Response, Error> {
+    req.flashes();
+
+    Ok(Flashes::current().len().to_string())
+}
+
+fn get(path: &str, cookie: Option<&str>) -> Req<Body> {
+    let mut req = Req::get(format!("https://reign.rs/{}", path));
+
+    if let Some(cookie) = cookie {
+        req = req.header(header::COOKIE, cookie);
+    }
+
+    req.body(Body::empty()).unwrap()
+}
+
+fn flash_cookie(res: &HyperResponse<Body>) -> String {
+    res.headers()[header::SET_COOKIE]
+        .to_str()
+        .unwrap()
+        .split(';')
+        .next()
+        .unwrap()
+        .to_string()
+}
+
+#[tokio::test]
+async fn test_flash() {
+    let service = service(|r| {
+        r.pipe("app").add(Flash::new(MemoryBackend::new(60)));
+
+        r.scope("").through(&["app"]).to(|r| {
+            r.get("create", create);
+            r.get("show", show);
+        });
+    });
+
+    let res = service
+        .clone()
+        .call(get("create", None), "10.10.10.10:80".parse().unwrap())
+        .await
+        .unwrap();
+
+    assert_eq!(res.status(), StatusCode::SEE_OTHER);
+
+    let cookie = flash_cookie(&res);
+
+    assert!(cookie.starts_with("_reign_flash="));
+
+    let res = service
+        .clone()
+        .call(
+            get("show", Some(&cookie)),
+            "10.10.10.10:80".parse().unwrap(),
+        )
+        .await
+        .unwrap();
+
+    assert!(res.headers()[header::SET_COOKIE]
+        .to_str()
+        .unwrap()
+        .contains("max-age=0"));
+    assert_eq!(to_bytes(res.into_body()).await.unwrap(), "Article created");
+
+    let res = service
+        .call(
+            get("show", Some(&cookie)),
+            "10.10.10.10:80".parse().unwrap(),
+        )
+        .await
+        .unwrap();
+
+    assert_eq!(to_bytes(res.into_body()).await.unwrap(), "none");
+}
+
+#[tokio::test]
+async fn test_flash_current() {
+    let service = service(|r| {
+        r.pipe("app").add(Flash::new(MemoryBackend::new(60)));
+
+        r.scope("").through(&["app"]).to(|r| {
+            r.get("create", create);
+            r.get("view", view);
+        });
+    });
+
+    let res = service
+        .clone()
+        .call(get("create", None), "10.10.10.10:80".parse().unwrap())
+        .await
+        .unwrap();
+
+    let cookie = flash_cookie(&res);
+
+    let res = service
+        .call(
+            get("view", Some(&cookie)),
+            "10.10.10.10:80".parse().unwrap(),
+        )
+        .await
+        .unwrap();
+
+    assert_eq!(
+        to_bytes(res.into_body()).await.unwrap(),
+        "notice: Article created"
+    );
+}
+
+#[tokio::test]
+async fn test_flash_current_after_consumed() {
+    let service = service(|r| {
+        r.pipe("app").add(Flash::new(MemoryBackend::new(60)));
+
+        r.scope("").through(["app"]).to(|r| {
+            r.get("create", create);
+            r.get("consumed", consumed);
+        });
+    });
+
+    let res = service
+        .clone()
+        .call(get("create", None), "10.10.10.10:80".parse().unwrap())
+        .await
+        .unwrap();
+
+    let cookie = flash_cookie(&res);
+
+    let res = service
+        .call(
+            get("consumed", Some(&cookie)),
+            "10.10.10.10:80".parse().unwrap(),
+        )
+        .await
+        .unwrap();
+
+    assert_eq!(to_bytes(res.into_body()).await.unwrap(), "0");
+}
+
+#[test]
+fn test_flash_current_outside_request() {
+    assert!(Flashes::current().is_empty());
+}
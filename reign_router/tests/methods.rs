@@ -16,7 +16,7 @@ macro_rules! call {
             .await
             .unwrap();
 
-        assert_eq!(res.status(), StatusCode::NOT_FOUND);
+        assert_eq!(res.status(), StatusCode::METHOD_NOT_ALLOWED);
         assert_eq!(to_bytes(res.into_body()).await.unwrap(), "");
     };
     ($service:ident, $path:expr, $method:ident, $($others:ident),+) => {
@@ -407,3 +407,58 @@ async fn test_mutliple_methods() {
     assert_eq!(res.status(), StatusCode::OK);
     assert_eq!(to_bytes(res.into_body()).await.unwrap(), "index");
 }
+
+#[tokio::test]
+async fn test_method_not_allowed_header() {
+    async fn index(_: &mut Request) -> Result<impl Response, Error> {
+        Ok("index")
+    }
+
+    let service = service(|r| {
+        r.get("index", index);
+        r.post("index", index);
+        r.scope("api").methods(&[Method::GET]).to(|r| {
+            r.all("index", index);
+        });
+    });
+
+    let res = service
+        .clone()
+        .call(
+            Req::delete("https://reign.rs/index")
+                .body(Body::empty())
+                .unwrap(),
+            "10.10.10.10:80".parse().unwrap(),
+        )
+        .await
+        .unwrap();
+
+    assert_eq!(res.status(), StatusCode::METHOD_NOT_ALLOWED);
+    assert_eq!(res.headers()["allow"], "GET, POST");
+
+    let res = service
+        .clone()
+        .call(
+            Req::post("https://reign.rs/api/index")
+                .body(Body::empty())
+                .unwrap(),
+            "10.10.10.10:80".parse().unwrap(),
+        )
+        .await
+        .unwrap();
+
+    assert_eq!(res.status(), StatusCode::METHOD_NOT_ALLOWED);
+    assert_eq!(res.headers()["allow"], "GET");
+
+    let res = service
+        .call(
+            Req::delete("https://reign.rs/other")
+                .body(Body::empty())
+                .unwrap(),
+            "10.10.10.10:80".parse().unwrap(),
+        )
+        .await
+        .unwrap();
+
+    assert_eq!(res.status(), StatusCode::NOT_FOUND);
+}
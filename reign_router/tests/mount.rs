@@ -0,0 +1,124 @@
+use reign_router::{
+    hyper::{
+        body::to_bytes, service::service_fn, Body, Request as Req, Response as Res, StatusCode,
+    },
+    middleware::HeadersDefault,
+    path as p, service, Error, Request, Response,
+};
+
+use std::convert::Infallible;
+
+async fn users(req: &mut Request) -> Result<impl Response, Error> {
+    Ok(format!(
+        "users {} {}",
+        req.query("page").map_or("", |x| x),
+        req.ip()
+    ))
+}
+
+async fn index(_: &mut Request) -> Result<impl Response, Error> {
+    Ok("index")
+}
+
+#[tokio::test]
+async fn test_mount_service() {
+    let service = service(|r| {
+        r.pipe("app")
+            .add(HeadersDefault::empty().add("x-powered-by", "reign"));
+
+        r.scope("").through(&["app"]).to(|r| {
+            r.mount(
+                "admin",
+                service(|r| {
+                    r.get("", index);
+                    r.get("users", users);
+                }),
+            );
+        });
+    });
+
+    let res = service
+        .clone()
+        .call(
+            Req::get("https://reign.rs/admin/users?page=2")
+                .body(Body::empty())
+                .unwrap(),
+            "10.10.10.10:80".parse().unwrap(),
+        )
+        .await
+        .unwrap();
+
+    assert_eq!(res.status(), StatusCode::OK);
+    assert_eq!(res.headers()["x-powered-by"], "reign");
+    assert_eq!(
+        to_bytes(res.into_body()).await.unwrap(),
+        "users 2 10.10.10.10:80"
+    );
+
+    let res = service
+        .clone()
+        .call(
+            Req::get("https://reign.rs/admin")
+                .body(Body::empty())
+                .unwrap(),
+            "10.10.10.10:80".parse().unwrap(),
+        )
+        .await
+        .unwrap();
+
+    assert_eq!(to_bytes(res.into_body()).await.unwrap(), "index");
+
+    let res = service
+        .call(
+            Req::get("https://reign.rs/admin/other")
+                .body(Body::empty())
+                .unwrap(),
+            "10.10.10.10:80".parse().unwrap(),
+        )
+        .await
+        .unwrap();
+
+    assert_eq!(res.status(), StatusCode::NOT_FOUND);
+    assert_eq!(res.headers()["x-powered-by"], "reign");
+}
+
+#[tokio::test]
+async fn test_mount_hyper_service() {
+    let service = service(|r| {
+        r.mount(
+            p!("tenants" / id / "metrics"),
+            service_fn(|req: Req<Body>| async move {
+                Ok::<_, Infallible>(Res::new(Body::from(format!(
+                    "{} {}",
+                    req.method(),
+                    req.uri().path()
+                ))))
+            }),
+        );
+    });
+
+    let res = service
+        .clone()
+        .call(
+            Req::post("https://reign.rs/tenants/1/metrics/cpu/total")
+                .body(Body::empty())
+                .unwrap(),
+            "10.10.10.10:80".parse().unwrap(),
+        )
+        .await
+        .unwrap();
+
+    assert_eq!(to_bytes(res.into_body()).await.unwrap(), "POST /cpu/total");
+
+    let res = service
+        .call(
+            Req::get("https://reign.rs/tenants/1")
+                .body(Body::empty())
+                .unwrap(),
+            "10.10.10.10:80".parse().unwrap(),
+        )
+        .await
+        .unwrap();
+
+    assert_eq!(res.status(), StatusCode::NOT_FOUND);
+}
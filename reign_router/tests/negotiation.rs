@@ -0,0 +1,96 @@
+use reign_router::{
+    helpers::{json, render, respond_to},
+    hyper::{body::to_bytes, header, Body, Request as Req, StatusCode},
+    service, Error, Request, Response,
+};
+
+async fn index(req: &mut Request) -> Result<impl Response, Error> {
+    let name = "reign";
+
+    Ok(respond_to(req)
+        .html(|| render(format!("<h1>{}</h1>", name), 200))
+        .json(|| json(name, 200))
+        .respond()?)
+}
+
+fn get(accept: Option<&str>) -> Req<Body> {
+    let mut req = Req::get("https://reign.rs/");
+
+    if let Some(accept) = accept {
+        req = req.header(header::ACCEPT, accept);
+    }
+
+    req.body(Body::empty()).unwrap()
+}
+
+#[tokio::test]
+async fn test_respond_to() {
+    let service = service(|r| {
+        r.get("", index);
+    });
+
+    let res = service
+        .clone()
+        .call(get(None), "10.10.10.10:80".parse().unwrap())
+        .await
+        .unwrap();
+
+    assert_eq!(res.status(), StatusCode::OK);
+    assert_eq!(res.headers()[header::VARY], "Accept");
+    assert_eq!(to_bytes(res.into_body()).await.unwrap(), "<h1>reign</h1>");
+
+    let res = service
+        .clone()
+        .call(
+            get(Some("text/html;q=0.9, application/json")),
+            "10.10.10.10:80".parse().unwrap(),
+        )
+        .await
+        .unwrap();
+
+    assert_eq!(res.status(), StatusCode::OK);
+    assert_eq!(res.headers()[header::CONTENT_TYPE], "application/json");
+    assert_eq!(to_bytes(res.into_body()).await.unwrap(), "\"reign\"");
+
+    let res = service
+        .clone()
+        .call(
+            get(Some("application/*, text/html;q=0.5")),
+            "10.10.10.10:80".parse().unwrap(),
+        )
+        .await
+        .unwrap();
+
+    assert_eq!(res.headers()[header::CONTENT_TYPE], "application/json");
+
+    let res = service
+        .clone()
+        .call(
+            get(Some("text/html;q=0, */*")),
+            "10.10.10.10:80".parse().unwrap(),
+        )
+        .await
+        .unwrap();
+
+    assert_eq!(res.headers()[header::CONTENT_TYPE], "application/json");
+
+    let res = service
+        .clone()
+        .call(
+            get(Some("text/*;q=0, */*")),
+            "10.10.10.10:80".parse().unwrap(),
+        )
+        .await
+        .unwrap();
+
+    assert_eq!(res.headers()[header::CONTENT_TYPE], "application/json");
+
+    let res = service
+        .call(get(Some("image/png")), "10.10.10.10:80".parse().unwrap())
+        .await
+        .unwrap();
+
+    assert_eq!(res.status(), StatusCode::NOT_ACCEPTABLE);
+    assert_eq!(res.headers()[header::VARY], "Accept");
+    assert_eq!(to_bytes(res.into_body()).await.unwrap(), "");
+}
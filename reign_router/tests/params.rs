@@ -0,0 +1,319 @@
+use reign::prelude::*;
+use reign_router::{chrono::NaiveDate, uuid::Uuid};
+use reign_router::{
+    hyper::{body::to_bytes, header, Body, Request as Req, Response as HyperResponse, StatusCode},
+    path as p, service, Chain, HandleFuture, Middleware, Service,
+};
+use serde::Deserialize;
+
+#[derive(Deserialize)]
+struct Article {
+    title: String,
+    tags: Vec<String>,
+}
+
+#[derive(Clone)]
+struct User(String);
+
+struct Login;
+
+impl Middleware for Login {
+    fn handle<'m>(&'m self, req: &'m mut Request, chain: Chain<'m>) -> HandleFuture<'m> {
+        req.extensions_mut().insert(User("John".into()));
+        chain.run(req)
+    }
+}
+
+#[params]
+async fn path(_req: &mut Request, id: u32) -> Result<impl Response, Error> {
+    Ok(format!("path {}", id))
+}
+
+#[params]
+async fn typed(_req: &mut Request, id: i8) -> Result<impl Response, Error> {
+    Ok(format!("typed {}", id))
+}
+
+#[params]
+async fn typed_opt(
+    _req: &mut Request,
+    day: Option<NaiveDate>,
+    key: Uuid,
+) -> Result<impl Response, Error> {
+    Ok(format!("typed_opt {:?} {}", day, key))
+}
+
+async fn new(_req: &mut Request) -> Result<impl Response, Error> {
+    Ok("new")
+}
+
+#[params]
+async fn query(
+    _req: &mut Request,
+    #[query] term: String,
+    #[query("p")] page: Option<u32>,
+    #[query] tag: Vec<String>,
+) -> Result<impl Response, Error> {
+    Ok(format!("query {} {:?} {:?}", term, page, tag))
+}
+
+#[params]
+async fn form(_req: &mut Request, #[form] article: Article) -> Result<impl Response, Error> {
+    Ok(format!("form {} {:?}", article.title, article.tags))
+}
+
+#[params]
+async fn json(_req: &mut Request, #[json] article: Article) -> Result<impl Response, Error> {
+    Ok(format!("json {} {:?}", article.title, article.tags))
+}
+
+#[params]
+async fn headers(
+    _req: &mut Request,
+    #[header] x_version: u8,
+    #[header("x-client")] client: Option<String>,
+) -> Result<impl Response, Error> {
+    Ok(format!("headers {} {:?}", x_version, client))
+}
+
+#[params]
+async fn cookie(_req: &mut Request, #[cookie] theme: String) -> Result<impl Response, Error> {
+    Ok(format!("cookie {}", theme))
+}
+
+#[params]
+async fn extension(
+    _req: &mut Request,
+    #[extension] user: User,
+    #[extension] missing: Option<u8>,
+) -> Result<impl Response, Error> {
+    Ok(format!("extension {} {:?}", user.0, missing))
+}
+
+fn app() -> Service {
+    service(|r| {
+        r.get(p!("path" / id), path);
+        r.get("query", query);
+        r.post("form", form);
+        r.post("json", json);
+        r.get("headers", headers);
+        r.get("cookie", cookie);
+        r.get("extension", extension);
+    })
+}
+
+async fn call(req: Req<Body>) -> HyperResponse<Body> {
+    app()
+        .call(req, "10.10.10.10:80".parse().unwrap())
+        .await
+        .unwrap()
+}
+
+async fn get(path: &str) -> HyperResponse<Body> {
+    call(
+        Req::get(format!("https://reign.rs/{}", path))
+            .body(Body::empty())
+            .unwrap(),
+    )
+    .await
+}
+
+async fn post(path: &str, content_type: &str, body: &str) -> HyperResponse<Body> {
+    call(
+        Req::post(format!("https://reign.rs/{}", path))
+            .header(header::CONTENT_TYPE, content_type)
+            .body(Body::from(body.to_string()))
+            .unwrap(),
+    )
+    .await
+}
+
+async fn body(res: HyperResponse<Body>) -> String {
+    String::from_utf8(to_bytes(res.into_body()).await.unwrap().to_vec()).unwrap()
+}
+
+#[tokio::test]
+async fn test_path() {
+    assert_eq!(body(get("path/1").await).await, "path 1");
+    assert_eq!(get("path/a").await.status(), StatusCode::NOT_FOUND);
+}
+
+#[tokio::test]
+async fn test_typed() {
+    let service = service(|r| {
+        r.get(p!("articles" / id: i8), typed);
+        r.get("articles/new", new);
+        r.get(p!("keys" / day?: date / key: uuid), typed_opt);
+    });
+
+    let call = |path: &str| {
+        service.clone().call(
+            Req::get(format!("https://reign.rs/{}", path))
+                .body(Body::empty())
+                .unwrap(),
+            "10.10.10.10:80".parse().unwrap(),
+        )
+    };
+
+    assert_eq!(body(call("articles/-12").await.unwrap()).await, "typed -12");
+    assert_eq!(body(call("articles/new").await.unwrap()).await, "new");
+    assert_eq!(
+        call("articles/1000").await.unwrap().status(),
+        StatusCode::NOT_FOUND
+    );
+    assert_eq!(
+        body(
+            call("keys/2021-02-03/67e55044-10b1-426f-9247-bb680e5fe0c8")
+                .await
+                .unwrap()
+        )
+        .await,
+        "typed_opt Some(2021-02-03) 67e55044-10b1-426f-9247-bb680e5fe0c8"
+    );
+    assert_eq!(
+        body(
+            call("keys/67e55044-10b1-426f-9247-bb680e5fe0c8")
+                .await
+                .unwrap()
+        )
+        .await,
+        "typed_opt None 67e55044-10b1-426f-9247-bb680e5fe0c8"
+    );
+    assert_eq!(
+        call("keys/2021-13-03/67e55044-10b1-426f-9247-bb680e5fe0c8")
+            .await
+            .unwrap()
+            .status(),
+        StatusCode::NOT_FOUND
+    );
+}
+
+#[tokio::test]
+async fn test_query() {
+    assert_eq!(
+        body(get("query?term=hey&p=2&tag=a&tag=b").await).await,
+        "query hey Some(2) [\"a\", \"b\"]"
+    );
+    assert_eq!(body(get("query?term=hey").await).await, "query hey None []");
+    assert_eq!(get("query").await.status(), StatusCode::BAD_REQUEST);
+    assert_eq!(
+        get("query?term=hey&p=a").await.status(),
+        StatusCode::BAD_REQUEST
+    );
+}
+
+#[tokio::test]
+async fn test_form() {
+    let res = post(
+        "form",
+        "application/x-www-form-urlencoded",
+        "title=Hello&tags[]=a&tags[]=b",
+    )
+    .await;
+
+    assert_eq!(body(res).await, "form Hello [\"a\", \"b\"]");
+
+    let res = post("form", "application/x-www-form-urlencoded", "tags[]=a").await;
+
+    assert_eq!(res.status(), StatusCode::BAD_REQUEST);
+
+    let res = post("form", "application/json", "{}").await;
+
+    assert_eq!(res.status(), StatusCode::UNSUPPORTED_MEDIA_TYPE);
+}
+
+#[tokio::test]
+async fn test_json() {
+    let res = post(
+        "json",
+        "application/json; charset=utf-8",
+        r#"{"title":"Hello","tags":["a"]}"#,
+    )
+    .await;
+
+    assert_eq!(body(res).await, "json Hello [\"a\"]");
+
+    let res = post("json", "application/json", "{").await;
+
+    assert_eq!(res.status(), StatusCode::BAD_REQUEST);
+
+    let res = post("json", "text/plain", "{}").await;
+
+    assert_eq!(res.status(), StatusCode::UNSUPPORTED_MEDIA_TYPE);
+}
+
+#[tokio::test]
+async fn test_headers() {
+    let res = call(
+        Req::get("https://reign.rs/headers")
+            .header("x-version", "2")
+            .header("x-client", "cli")
+            .body(Body::empty())
+            .unwrap(),
+    )
+    .await;
+
+    assert_eq!(body(res).await, "headers 2 Some(\"cli\")");
+    assert_eq!(get("headers").await.status(), StatusCode::BAD_REQUEST);
+
+    let res = call(
+        Req::get("https://reign.rs/headers")
+            .header("x-version", "a")
+            .body(Body::empty())
+            .unwrap(),
+    )
+    .await;
+
+    assert_eq!(res.status(), StatusCode::BAD_REQUEST);
+}
+
+#[tokio::test]
+async fn test_cookie() {
+    let res = call(
+        Req::get("https://reign.rs/cookie")
+            .header(header::COOKIE, "lang=en; theme=dark")
+            .body(Body::empty())
+            .unwrap(),
+    )
+    .await;
+
+    assert_eq!(body(res).await, "cookie dark");
+    assert_eq!(get("cookie").await.status(), StatusCode::BAD_REQUEST);
+}
+
+#[tokio::test]
+async fn test_extension() {
+    let service = service(|r| {
+        r.pipe("user").add(Login);
+
+        r.scope("").through(&["user"]).to(|r| {
+            r.get("extension", extension);
+        });
+        r.get("missing", extension);
+    });
+
+    let res = service
+        .clone()
+        .call(
+            Req::get("https://reign.rs/extension")
+                .body(Body::empty())
+                .unwrap(),
+            "10.10.10.10:80".parse().unwrap(),
+        )
+        .await
+        .unwrap();
+
+    assert_eq!(body(res).await, "extension John None");
+
+    let res = service
+        .call(
+            Req::get("https://reign.rs/missing")
+                .body(Body::empty())
+                .unwrap(),
+            "10.10.10.10:80".parse().unwrap(),
+        )
+        .await
+        .unwrap();
+
+    assert_eq!(res.status(), StatusCode::INTERNAL_SERVER_ERROR);
+}
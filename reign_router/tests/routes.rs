@@ -0,0 +1,76 @@
+use reign_router::{
+    hyper::Method, middleware::HeadersDefault, path as p, service, Error, Request, Response,
+    RouteInfo,
+};
+
+async fn index(_: &mut Request) -> Result<impl Response, Error> {
+    Ok("index")
+}
+
+async fn show(_: &mut Request) -> Result<impl Response, Error> {
+    Ok("show")
+}
+
+#[test]
+fn test_routes() {
+    let service = service(|r| {
+        r.pipe("app").add(HeadersDefault::empty());
+        r.pipe("api").add(HeadersDefault::empty());
+
+        r.get("", index);
+
+        r.scope("").through(&["app"]).to(|r| {
+            r.scope("api")
+                .through(&["api"])
+                .host("{tenant}.example.com")
+                .methods(&[Method::GET, Method::HEAD])
+                .to(|r| {
+                    r.any(&[Method::GET, Method::POST], p!("users" / id: u32), show);
+                    r.all_with_constraint("health", |_| true, index);
+                });
+
+            r.mount("admin", service(|_| {}));
+        });
+    });
+
+    assert_eq!(
+        service.routes(),
+        vec![
+            RouteInfo {
+                methods: vec!["GET".into()],
+                path: "/".into(),
+                name: Some("routes::index".into()),
+                pipes: vec![],
+                constraints: vec![],
+            },
+            RouteInfo {
+                methods: vec![],
+                path: "/admin/*".into(),
+                name: Some("reign_router::service::Service".into()),
+                pipes: vec!["app".into()],
+                constraints: vec![],
+            },
+            RouteInfo {
+                methods: vec!["GET".into()],
+                path: "/api/users/{id:u32}".into(),
+                name: Some("routes::show".into()),
+                pipes: vec!["app".into(), "api".into()],
+                constraints: vec![
+                    "host = {tenant}.example.com".into(),
+                    "methods = GET, HEAD".into(),
+                ],
+            },
+            RouteInfo {
+                methods: vec!["GET".into(), "HEAD".into()],
+                path: "/api/health".into(),
+                name: Some("routes::index".into()),
+                pipes: vec!["app".into(), "api".into()],
+                constraints: vec![
+                    "host = {tenant}.example.com".into(),
+                    "methods = GET, HEAD".into(),
+                    "custom".into(),
+                ],
+            },
+        ]
+    );
+}
@@ -0,0 +1,344 @@
+use reign_router::{
+    hyper::{body::to_bytes, header, Body, Request as Req, StatusCode},
+    middleware::session::{Expiry, MemoryBackend, Session},
+    service, Error, Request, Response,
+};
+use serde::{Deserialize, Serialize};
+
+#[derive(Serialize, Deserialize)]
+struct User(String);
+
+async fn login(req: &mut Request) -> Result<impl Response, Error> {
+    req.save_session(User("John".into()));
+    Ok("login")
+}
+
+async fn show(req: &mut Request) -> Result<impl Response, Error> {
+    Ok(req
+        .session::<User>()
+        .map(|user| user.0.clone())
+        .unwrap_or_else(|| "anonymous".into()))
+}
+
+async fn rename(req: &mut Request) -> Result<impl Response, Error> {
+    req.save_session(User("Jane".into()));
+    Ok("rename")
+}
+
+async fn rotate(req: &mut Request) -> Result<impl Response, Error> {
+    req.regenerate_session::<User>();
+    Ok("rotate")
+}
+
+async fn logout(req: &mut Request) -> Result<impl Response, Error> {
+    req.delete_session::<User>();
+    Ok("logout")
+}
+
+fn get(path: &str, cookie: Option<&str>) -> Req<Body> {
+    let mut req = Req::get(format!("https://reign.rs/{}", path));
+
+    if let Some(cookie) = cookie {
+        req = req.header(header::COOKIE, cookie);
+    }
+
+    req.body(Body::empty()).unwrap()
+}
+
+fn session_cookie(res: &reign_router::hyper::Response<Body>) -> Option<String> {
+    res.headers()
+        .get(header::SET_COOKIE)
+        .map(|x| x.to_str().unwrap().split(';').next().unwrap().to_string())
+}
+
+#[tokio::test]
+async fn test_session_memory_backend() {
+    let service = service(|r| {
+        r.pipe("app")
+            .add(Session::<User, _>::new(MemoryBackend::new(60)));
+
+        r.scope("").through(&["app"]).to(|r| {
+            r.get("login", login);
+            r.get("show", show);
+            r.get("logout", logout);
+        });
+    });
+
+    let res = service
+        .clone()
+        .call(get("show", None), "10.10.10.10:80".parse().unwrap())
+        .await
+        .unwrap();
+
+    assert_eq!(res.status(), StatusCode::OK);
+    assert!(!res.headers().contains_key(header::SET_COOKIE));
+    assert_eq!(to_bytes(res.into_body()).await.unwrap(), "anonymous");
+
+    let res = service
+        .clone()
+        .call(get("login", None), "10.10.10.10:80".parse().unwrap())
+        .await
+        .unwrap();
+
+    assert_eq!(res.status(), StatusCode::OK);
+
+    let cookie = session_cookie(&res).unwrap();
+
+    assert!(cookie.starts_with("_reign_session="));
+
+    let res = service
+        .clone()
+        .call(
+            get("show", Some(&cookie)),
+            "10.10.10.10:80".parse().unwrap(),
+        )
+        .await
+        .unwrap();
+
+    assert_eq!(to_bytes(res.into_body()).await.unwrap(), "John");
+
+    let res = service
+        .clone()
+        .call(
+            get("logout", Some(&cookie)),
+            "10.10.10.10:80".parse().unwrap(),
+        )
+        .await
+        .unwrap();
+
+    assert!(res.headers()[header::SET_COOKIE]
+        .to_str()
+        .unwrap()
+        .contains("max-age=0"));
+
+    let res = service
+        .call(
+            get("show", Some(&cookie)),
+            "10.10.10.10:80".parse().unwrap(),
+        )
+        .await
+        .unwrap();
+
+    assert_eq!(to_bytes(res.into_body()).await.unwrap(), "anonymous");
+}
+
+#[tokio::test]
+async fn test_session_memory_backend_expired() {
+    let service = service(|r| {
+        r.pipe("app")
+            .add(Session::<User, _>::new(MemoryBackend::new(0)));
+
+        r.scope("").through(&["app"]).to(|r| {
+            r.get("login", login);
+            r.get("show", show);
+        });
+    });
+
+    let res = service
+        .clone()
+        .call(get("login", None), "10.10.10.10:80".parse().unwrap())
+        .await
+        .unwrap();
+
+    let cookie = session_cookie(&res).unwrap();
+
+    let res = service
+        .call(
+            get("show", Some(&cookie)),
+            "10.10.10.10:80".parse().unwrap(),
+        )
+        .await
+        .unwrap();
+
+    assert_eq!(to_bytes(res.into_body()).await.unwrap(), "anonymous");
+}
+
+#[tokio::test]
+async fn test_session_stable_id() {
+    let service = service(|r| {
+        r.pipe("app")
+            .add(Session::<User, _>::new(MemoryBackend::new(60)));
+
+        r.scope("").through(&["app"]).to(|r| {
+            r.get("login", login);
+            r.get("rename", rename);
+            r.get("show", show);
+        });
+    });
+
+    let res = service
+        .clone()
+        .call(get("login", None), "10.10.10.10:80".parse().unwrap())
+        .await
+        .unwrap();
+
+    let cookie = session_cookie(&res).unwrap();
+
+    let res = service
+        .clone()
+        .call(
+            get("rename", Some(&cookie)),
+            "10.10.10.10:80".parse().unwrap(),
+        )
+        .await
+        .unwrap();
+
+    assert_eq!(session_cookie(&res).unwrap(), cookie);
+
+    let res = service
+        .clone()
+        .call(
+            get("show", Some(&cookie)),
+            "10.10.10.10:80".parse().unwrap(),
+        )
+        .await
+        .unwrap();
+
+    assert!(session_cookie(&res).is_none());
+    assert_eq!(to_bytes(res.into_body()).await.unwrap(), "Jane");
+}
+
+#[tokio::test]
+async fn test_session_unknown_id_not_reused() {
+    let service = service(|r| {
+        r.pipe("app")
+            .add(Session::<User, _>::new(MemoryBackend::new(60)));
+
+        r.scope("").through(&["app"]).to(|r| {
+            r.get("login", login);
+        });
+    });
+
+    let res = service
+        .call(
+            get("login", Some("_reign_session=fixated")),
+            "10.10.10.10:80".parse().unwrap(),
+        )
+        .await
+        .unwrap();
+
+    assert_ne!(session_cookie(&res).unwrap(), "_reign_session=fixated");
+}
+
+#[tokio::test]
+async fn test_session_regenerate() {
+    let service = service(|r| {
+        r.pipe("app")
+            .add(Session::<User, _>::new(MemoryBackend::new(60)));
+
+        r.scope("").through(&["app"]).to(|r| {
+            r.get("login", login);
+            r.get("rotate", rotate);
+            r.get("show", show);
+        });
+    });
+
+    let res = service
+        .clone()
+        .call(get("login", None), "10.10.10.10:80".parse().unwrap())
+        .await
+        .unwrap();
+
+    let old = session_cookie(&res).unwrap();
+
+    let res = service
+        .clone()
+        .call(get("rotate", Some(&old)), "10.10.10.10:80".parse().unwrap())
+        .await
+        .unwrap();
+
+    let new = session_cookie(&res).unwrap();
+
+    assert_ne!(old, new);
+
+    let res = service
+        .clone()
+        .call(get("show", Some(&new)), "10.10.10.10:80".parse().unwrap())
+        .await
+        .unwrap();
+
+    assert_eq!(to_bytes(res.into_body()).await.unwrap(), "John");
+
+    let res = service
+        .call(get("show", Some(&old)), "10.10.10.10:80".parse().unwrap())
+        .await
+        .unwrap();
+
+    assert_eq!(to_bytes(res.into_body()).await.unwrap(), "anonymous");
+}
+
+#[tokio::test]
+async fn test_session_sliding_expiry() {
+    let service = service(|r| {
+        r.pipe("app")
+            .add(Session::<User, _>::new(MemoryBackend::new(60)).expiry(Expiry::Sliding(30)));
+
+        r.scope("").through(&["app"]).to(|r| {
+            r.get("login", login);
+            r.get("show", show);
+        });
+    });
+
+    let res = service
+        .clone()
+        .call(get("login", None), "10.10.10.10:80".parse().unwrap())
+        .await
+        .unwrap();
+
+    let set_cookie = res.headers()[header::SET_COOKIE].to_str().unwrap();
+
+    assert!(
+        set_cookie.contains("; Max-Age=30; Expires=")
+            || set_cookie.contains("; Max-Age=29; Expires=")
+    );
+    assert!(set_cookie.ends_with(" GMT"));
+
+    let cookie = session_cookie(&res).unwrap();
+
+    let res = service
+        .call(
+            get("show", Some(&cookie)),
+            "10.10.10.10:80".parse().unwrap(),
+        )
+        .await
+        .unwrap();
+
+    assert_eq!(session_cookie(&res).unwrap(), cookie);
+    assert_eq!(to_bytes(res.into_body()).await.unwrap(), "John");
+}
+
+#[tokio::test]
+async fn test_session_absolute_expiry() {
+    let service = service(|r| {
+        r.pipe("app")
+            .add(Session::<User, _>::new(MemoryBackend::new(60)).expiry(Expiry::Absolute(0)));
+
+        r.scope("").through(&["app"]).to(|r| {
+            r.get("login", login);
+            r.get("show", show);
+        });
+    });
+
+    let res = service
+        .clone()
+        .call(get("login", None), "10.10.10.10:80".parse().unwrap())
+        .await
+        .unwrap();
+
+    assert!(res.headers()[header::SET_COOKIE]
+        .to_str()
+        .unwrap()
+        .contains("; Max-Age=0; Expires="));
+
+    let cookie = session_cookie(&res).unwrap();
+
+    let res = service
+        .call(
+            get("show", Some(&cookie)),
+            "10.10.10.10:80".parse().unwrap(),
+        )
+        .await
+        .unwrap();
+
+    assert_eq!(to_bytes(res.into_body()).await.unwrap(), "anonymous");
+}
@@ -0,0 +1,87 @@
+use reign::prelude::*;
+use reign_router::{
+    hyper::{body::to_bytes, Body, Request as Req, StatusCode},
+    service, Service,
+};
+
+#[derive(Clone)]
+struct Config {
+    name: &'static str,
+}
+
+#[derive(Clone)]
+struct Counter(u32);
+
+#[params]
+async fn show(
+    _req: &mut Request,
+    #[state] config: Config,
+    #[state] counter: Option<Counter>,
+) -> Result<impl Response, Error> {
+    Ok(format!("{} {:?}", config.name, counter.map(|x| x.0)))
+}
+
+async fn missing(req: &mut Request) -> Result<impl Response, Error> {
+    Ok(req.state::<Counter>()?.0.to_string())
+}
+
+async fn get(service: &Service, path: &str) -> (StatusCode, String) {
+    let res = service
+        .clone()
+        .call(
+            Req::get(format!("https://reign.rs{}", path))
+                .body(Body::empty())
+                .unwrap(),
+            "10.10.10.10:80".parse().unwrap(),
+        )
+        .await
+        .unwrap();
+
+    let status = res.status();
+    let body = to_bytes(res.into_body()).await.unwrap();
+
+    (status, String::from_utf8(body.to_vec()).unwrap())
+}
+
+#[tokio::test]
+async fn test_state() {
+    let service = service(|r| {
+        r.state(Config { name: "app" });
+        r.get("", show);
+        r.get("missing", missing);
+
+        r.scope("admin").to(|r| {
+            r.state(Config { name: "admin" });
+            r.state(Counter(2));
+            r.get("", show);
+        });
+    });
+
+    assert_eq!(
+        get(&service, "/").await,
+        (StatusCode::OK, "app None".into())
+    );
+    assert_eq!(
+        get(&service, "/admin").await,
+        (StatusCode::OK, "admin Some(2)".into())
+    );
+    assert_eq!(
+        get(&service, "/missing").await.0,
+        StatusCode::INTERNAL_SERVER_ERROR
+    );
+}
+
+#[tokio::test]
+async fn test_state_per_service() {
+    let first = service(|r| {
+        r.state(Config { name: "first" });
+        r.get("", show);
+    });
+    let second = service(|r| {
+        r.state(Config { name: "second" });
+        r.get("", show);
+    });
+
+    assert_eq!(get(&first, "/").await.1, "first None");
+    assert_eq!(get(&second, "/").await.1, "second None");
+}
@@ -0,0 +1,242 @@
+use reign_router::{
+    futures::future::{join, ready, BoxFuture, FutureExt},
+    hyper::{
+        body::{to_bytes, HttpBody},
+        header::HeaderValue,
+        service::Service as HyperService,
+        Body, Request as Req, Response as Res, StatusCode,
+    },
+    middleware::Tower,
+    service,
+    tower_layer::layer_fn,
+    Error, Request, Response,
+};
+
+use tokio::time::sleep;
+
+use std::{
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc, Mutex,
+    },
+    task::{Context, Poll, Waker},
+    time::Duration,
+};
+
+#[derive(Clone)]
+struct Stamp<S> {
+    inner: S,
+}
+
+impl<S> HyperService<Req<Body>> for Stamp<S>
+where
+    S: HyperService<Req<Body>, Response = Res<Body>> + Send + 'static,
+    S::Future: Send + 'static,
+    S::Error: Send,
+{
+    type Response = Res<Body>;
+    type Error = S::Error;
+    type Future = BoxFuture<'static, Result<Self::Response, Self::Error>>;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.inner.poll_ready(cx)
+    }
+
+    fn call(&mut self, mut req: Req<Body>) -> Self::Future {
+        if req.headers().contains_key("x-block") {
+            return ready(Ok(Res::builder()
+                .status(StatusCode::FORBIDDEN)
+                .body(Body::empty())
+                .unwrap()))
+            .boxed();
+        }
+
+        req.headers_mut()
+            .insert("x-stamp", HeaderValue::from_static("in"));
+
+        let future = self.inner.call(req);
+
+        async move {
+            let mut res = future.await?;
+            res.headers_mut()
+                .insert("x-stamp", HeaderValue::from_static("out"));
+
+            Ok(res)
+        }
+        .boxed()
+    }
+}
+
+/// Lets one request through at a time. Like the limits of tower, the count is created along
+/// with the service, so it only limits the requests handled by the clones of the service.
+struct Limit<S> {
+    inner: S,
+    state: Arc<Mutex<(usize, Vec<Waker>)>>,
+    ready: bool,
+}
+
+impl<S> Clone for Limit<S>
+where
+    S: Clone,
+{
+    fn clone(&self) -> Self {
+        Self {
+            inner: self.inner.clone(),
+            state: self.state.clone(),
+            ready: false,
+        }
+    }
+}
+
+impl<S> HyperService<Req<Body>> for Limit<S>
+where
+    S: HyperService<Req<Body>, Response = Res<Body>> + Send + 'static,
+    S::Future: Send + 'static,
+    S::Error: Send,
+{
+    type Response = Res<Body>;
+    type Error = S::Error;
+    type Future = BoxFuture<'static, Result<Self::Response, Self::Error>>;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        let mut state = self.state.lock().unwrap();
+
+        if !self.ready && state.0 == 1 {
+            state.1.push(cx.waker().clone());
+            return Poll::Pending;
+        }
+
+        if !self.ready {
+            state.0 += 1;
+            self.ready = true;
+        }
+
+        drop(state);
+        self.inner.poll_ready(cx)
+    }
+
+    fn call(&mut self, req: Req<Body>) -> Self::Future {
+        let future = self.inner.call(req);
+        let state = self.state.clone();
+
+        self.ready = false;
+
+        async move {
+            let res = future.await;
+            let mut state = state.lock().unwrap();
+
+            state.0 -= 1;
+            state.1.drain(..).for_each(Waker::wake);
+
+            res
+        }
+        .boxed()
+    }
+}
+
+static ACTIVE: AtomicUsize = AtomicUsize::new(0);
+static MAX_ACTIVE: AtomicUsize = AtomicUsize::new(0);
+
+async fn slow(_: &mut Request) -> Result<impl Response, Error> {
+    let active = ACTIVE.fetch_add(1, Ordering::SeqCst) + 1;
+
+    MAX_ACTIVE.fetch_max(active, Ordering::SeqCst);
+    sleep(Duration::from_millis(20)).await;
+    ACTIVE.fetch_sub(1, Ordering::SeqCst);
+
+    Ok("slow")
+}
+
+async fn stamp(req: &mut Request) -> Result<impl Response, Error> {
+    Ok(req
+        .headers()
+        .get("x-stamp")
+        .map_or("none", |x| x.to_str().unwrap())
+        .to_string())
+}
+
+#[tokio::test]
+async fn test_tower_layer() {
+    let service = service(|r| {
+        r.pipe("app")
+            .add(Tower::new(layer_fn(|inner| Stamp { inner })));
+
+        r.scope("").through(&["app"]).to(|r| {
+            r.get("", stamp);
+        });
+    });
+
+    let res = service
+        .clone()
+        .call(
+            Req::get("https://reign.rs").body(Body::empty()).unwrap(),
+            "10.10.10.10:80".parse().unwrap(),
+        )
+        .await
+        .unwrap();
+
+    assert_eq!(res.status(), StatusCode::OK);
+    assert_eq!(res.headers()["x-stamp"], "out");
+    assert_eq!(res.body().size_hint().exact(), Some(2));
+    assert_eq!(to_bytes(res.into_body()).await.unwrap(), "in");
+
+    let res = service
+        .call(
+            Req::get("https://reign.rs")
+                .header("x-block", "1")
+                .body(Body::empty())
+                .unwrap(),
+            "10.10.10.10:80".parse().unwrap(),
+        )
+        .await
+        .unwrap();
+
+    assert_eq!(res.status(), StatusCode::FORBIDDEN);
+}
+
+#[tokio::test]
+async fn test_tower_service() {
+    let mut service = service(|r| {
+        r.get("", stamp);
+    });
+
+    let res = HyperService::call(
+        &mut service,
+        Req::get("https://reign.rs")
+            .header("x-stamp", "direct")
+            .body(Body::empty())
+            .unwrap(),
+    )
+    .await
+    .unwrap();
+
+    assert_eq!(to_bytes(res.into_body()).await.unwrap(), "direct");
+}
+
+#[tokio::test]
+async fn test_tower_concurrency_limit() {
+    let service = service(|r| {
+        r.pipe("app").add(Tower::new(layer_fn(|inner| Limit {
+            inner,
+            state: Arc::default(),
+            ready: false,
+        })));
+
+        r.scope("").through(&["app"]).to(|r| {
+            r.get("", slow);
+        });
+    });
+
+    let call = || {
+        service.clone().call(
+            Req::get("https://reign.rs").body(Body::empty()).unwrap(),
+            "10.10.10.10:80".parse().unwrap(),
+        )
+    };
+
+    let (a, b) = join(call(), call()).await;
+
+    assert_eq!(a.unwrap().status(), StatusCode::OK);
+    assert_eq!(b.unwrap().status(), StatusCode::OK);
+    assert_eq!(MAX_ACTIVE.load(Ordering::SeqCst), 1);
+}
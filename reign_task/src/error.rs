@@ -18,6 +18,8 @@ pub enum Error {
     NoArgs(String),
     #[error("there is no task named {0}")]
     NoTask(String),
+    #[error("unknown argument {0}")]
+    UnknownArg(String),
 
     #[cfg(feature = "templating")]
     #[error(transparent)]
@@ -33,6 +35,7 @@ impl CliError for Error {
         match self {
             Self::NoArgs(name) => Self::NoArgs(format!("{}", ERR_YELLOW.apply_to(name))),
             Self::NoTask(name) => Self::NoTask(format!("{}", ERR_YELLOW.apply_to(name))),
+            Self::UnknownArg(arg) => Self::UnknownArg(format!("{}", ERR_YELLOW.apply_to(arg))),
             _ => self,
         }
     }
@@ -3,19 +3,23 @@
 
 #[doc(hidden)]
 pub mod error;
+#[cfg(feature = "router")]
+mod routes;
 mod task;
 mod tasks;
 #[cfg(feature = "templating")]
 mod templating;
 
 pub use error::Error;
+#[cfg(feature = "router")]
+pub use routes::Routes;
 pub use task::Task;
 pub use tasks::Tasks;
 #[cfg(feature = "templating")]
 pub use templating::Template;
 
 pub use oclif;
-#[cfg(feature = "templating")]
+#[cfg(any(feature = "templating", feature = "router"))]
 pub use serde_json;
 
 use std::{path::PathBuf, process::Command, str::from_utf8};
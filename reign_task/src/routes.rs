@@ -0,0 +1,134 @@
+use crate::{
+    serde_json::{json, to_string_pretty},
+    Error, Task,
+};
+
+use reign_router::{RouteInfo, Service};
+
+/// Reign task for listing the routes of the application
+///
+/// Prints a table by default and JSON when given `--json`.
+///
+/// ```no_run
+/// use reign_task::{Routes, Tasks};
+/// # use reign_router::{service, Router};
+/// # fn router(r: &mut Router) {}
+///
+/// Tasks::new("app")
+///     .task(Routes::new(&service(router)))
+///     .parse();
+/// ```
+pub struct Routes {
+    routes: Vec<RouteInfo>,
+}
+
+impl Routes {
+    pub fn new(service: &Service) -> Self {
+        Self {
+            routes: service.routes(),
+        }
+    }
+
+    fn rows(&self) -> Vec<[String; 5]> {
+        self.routes
+            .iter()
+            .map(|route| {
+                [
+                    if route.methods.is_empty() {
+                        "ANY".into()
+                    } else {
+                        route.methods.join("|")
+                    },
+                    route.path.clone(),
+                    route.name.clone().unwrap_or_default(),
+                    route.pipes.join(", "),
+                    route.constraints.join("; "),
+                ]
+            })
+            .collect()
+    }
+
+    fn table(&self) {
+        let header = ["METHODS", "PATH", "NAME", "PIPES", "CONSTRAINTS"].map(String::from);
+        let rows = self.rows();
+
+        // Get maximum length of each column
+        let max = rows.iter().fold([0; 5], |mut acc, row| {
+            for (i, x) in row.iter().enumerate() {
+                acc[i] = usize::max(acc[i], x.len());
+            }
+
+            acc
+        });
+        let max = header
+            .iter()
+            .enumerate()
+            .map(|(i, x)| usize::max(max[i], x.len()))
+            .collect::<Vec<_>>();
+
+        for row in Some(header).iter().chain(rows.iter()) {
+            let line = row
+                .iter()
+                .enumerate()
+                .map(|(i, x)| format!("{:width$}", x, width = max[i]))
+                .collect::<Vec<_>>();
+
+            println!("{}", line.join("\t").trim_end());
+        }
+    }
+
+    fn json(&self) -> Result<(), Error> {
+        let routes = self
+            .routes
+            .iter()
+            .map(|route| {
+                json!({
+                    "methods": route.methods,
+                    "path": route.path,
+                    "name": route.name,
+                    "pipes": route.pipes,
+                    "constraints": route.constraints,
+                })
+            })
+            .collect::<Vec<_>>();
+
+        println!(
+            "{}",
+            to_string_pretty(&routes).map_err(|e| Error::Other(e.into()))?
+        );
+
+        Ok(())
+    }
+}
+
+impl Task for Routes {
+    fn command(&self) -> String {
+        "routes".into()
+    }
+
+    fn short_about(&self) -> String {
+        "List all the routes of the application".into()
+    }
+
+    fn long_about(&self) -> String {
+        "List all the routes of the application in the order they are matched.\n\nUse `--json` to print them as JSON.".into()
+    }
+
+    fn run(&self, args: Vec<String>) -> Result<(), Error> {
+        let mut json = false;
+
+        for arg in args {
+            match arg.as_str() {
+                "--json" => json = true,
+                _ => return Err(Error::UnknownArg(arg)),
+            }
+        }
+
+        if json {
+            self.json()
+        } else {
+            self.table();
+            Ok(())
+        }
+    }
+}
@@ -0,0 +1,188 @@
+use std::{
+    collections::{hash_map::DefaultHasher, HashSet},
+    fs::{create_dir_all, read_dir, read_to_string, remove_file, rename, write},
+    hash::{Hash, Hasher},
+    path::{Path, PathBuf},
+    process,
+    sync::atomic::{AtomicUsize, Ordering},
+    time::{Duration, SystemTime},
+};
+
+/// How long the entries are kept after they were last used.
+const GRACE: Duration = Duration::from_secs(24 * 60 * 60);
+
+static WRITES: AtomicUsize = AtomicUsize::new(0);
+
+/// Cache of the generated code of the views which lives on the disk between builds.
+///
+/// The entries are keyed by the hash of whatever was used to generate them. Cargo and the
+/// editors can expand the views at the same time, so the entries are written atomically and
+/// the entries which were not used in a build are only removed once they expire.
+pub(crate) struct Cache {
+    dir: Option<PathBuf>,
+    used: HashSet<String>,
+    grace: Duration,
+}
+
+impl Cache {
+    pub(crate) fn new(dir: Option<&Path>) -> Self {
+        Self {
+            // Not being able to create the directory only means we can't cache
+            dir: dir
+                .filter(|x| create_dir_all(x).is_ok())
+                .map(Path::to_path_buf),
+            used: HashSet::new(),
+            grace: GRACE,
+        }
+    }
+
+    pub(crate) fn read(&mut self, key: &str) -> Option<String> {
+        let dir = self.dir.as_ref()?;
+        let content = read_to_string(dir.join(key)).ok()?;
+
+        // Writing the entry again keeps it from expiring while it is being used
+        if age(&dir.join(key)).map_or(true, |x| x > self.grace / 2) {
+            self.write(key, &content);
+        }
+
+        self.used.insert(key.to_string());
+        Some(content)
+    }
+
+    pub(crate) fn write(&mut self, key: &str, content: &str) {
+        if let Some(dir) = &self.dir {
+            let temp = dir.join(format!(
+                "{}.tmp-{}-{}",
+                key,
+                process::id(),
+                WRITES.fetch_add(1, Ordering::Relaxed)
+            ));
+
+            // Renaming replaces the entry at once, so it is never read half written
+            if write(&temp, content).is_ok() && rename(&temp, dir.join(key)).is_ok() {
+                self.used.insert(key.to_string());
+            } else {
+                let _ = remove_file(&temp);
+            }
+        }
+    }
+
+    /// Path of the entry if it was read or written in this build.
+    pub(crate) fn path(&self, key: &str) -> Option<PathBuf> {
+        match &self.dir {
+            Some(dir) if self.used.contains(key) => Some(dir.join(key)),
+            _ => None,
+        }
+    }
+
+    pub(crate) fn prune(&self) {
+        let entries = match self.dir.as_ref().and_then(|x| read_dir(x).ok()) {
+            Some(entries) => entries,
+            None => return,
+        };
+
+        for entry in entries.flatten() {
+            let name = entry.file_name().to_string_lossy().to_string();
+
+            if !self.used.contains(&name) && age(&entry.path()).map_or(false, |x| x > self.grace) {
+                let _ = remove_file(entry.path());
+            }
+        }
+    }
+}
+
+/// Time since the file was last written.
+fn age(path: &Path) -> Option<Duration> {
+    let modified = path.metadata().ok()?.modified().ok()?;
+
+    // A modification time in the future counts as new
+    Some(
+        SystemTime::now()
+            .duration_since(modified)
+            .unwrap_or_default(),
+    )
+}
+
+/// Hashes the given parts along with the version of this library since the generated
+/// code changes with it.
+pub(crate) fn hash<T: Hash + ?Sized>(parts: &T) -> String {
+    let mut hasher = DefaultHasher::new();
+
+    env!("CARGO_PKG_VERSION").hash(&mut hasher);
+    parts.hash(&mut hasher);
+
+    format!("{:016x}", hasher.finish())
+}
+
+#[cfg(test)]
+mod test {
+    use super::{hash, Cache};
+    use std::{
+        env::temp_dir,
+        fs::{read_dir, remove_dir_all},
+        thread::sleep,
+        time::Duration,
+    };
+
+    #[test]
+    fn test_hash() {
+        assert_eq!(hash("view"), hash("view"));
+        assert_ne!(hash("view"), hash("views"));
+        assert_eq!(hash("view").len(), 16);
+    }
+
+    #[test]
+    fn test_prune() {
+        let dir = temp_dir().join("reign_view_cache_prune");
+        let _ = remove_dir_all(&dir);
+
+        let mut cache = Cache::new(Some(&dir));
+
+        cache.write("a.rs", "a");
+        cache.write("b.rs", "b");
+
+        let mut cache = Cache::new(Some(&dir));
+
+        assert_eq!(cache.read("a.rs"), Some("a".to_string()));
+        assert_eq!(cache.read("c.rs"), None);
+
+        // Another build could still be using the entry
+        cache.prune();
+        assert_eq!(Cache::new(Some(&dir)).read("b.rs"), Some("b".to_string()));
+
+        let mut cache = Cache::new(Some(&dir));
+
+        cache.grace = Duration::ZERO;
+        assert_eq!(cache.read("a.rs"), Some("a".to_string()));
+        sleep(Duration::from_millis(10));
+        cache.prune();
+
+        let mut cache = Cache::new(Some(&dir));
+
+        assert_eq!(cache.read("a.rs"), Some("a".to_string()));
+        assert_eq!(cache.read("b.rs"), None);
+    }
+
+    #[test]
+    fn test_write() {
+        let dir = temp_dir().join("reign_view_cache_write");
+        let _ = remove_dir_all(&dir);
+
+        let mut cache = Cache::new(Some(&dir));
+
+        cache.write("a.rs", "a");
+        cache.write("a.rs", "b");
+
+        assert_eq!(cache.read("a.rs"), Some("b".to_string()));
+        assert_eq!(read_dir(&dir).unwrap().count(), 1);
+    }
+
+    #[test]
+    fn test_no_dir() {
+        let mut cache = Cache::new(None);
+
+        cache.write("a.rs", "a");
+
+        assert_eq!(cache.read("a.rs"), None);
+    }
+}
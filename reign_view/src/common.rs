@@ -1,59 +1,485 @@
 use crate::{
-    parse::{parse, tokenize},
+    cache::{hash, Cache},
+    parse::{
+        scoped_slots_ident, slot_field, tokenize, tokenize_located, Error as ParseError, Tokenized,
+        LOCATION,
+    },
     INTERNAL_ERR,
 };
 use inflector::cases::pascalcase::to_pascal_case;
 use once_cell::sync::Lazy;
 use proc_macro2::{Ident, Span, TokenStream};
-use quote::quote;
-use regex::Regex;
-use std::{collections::HashMap, fs::read_to_string, io::Error, path::Path};
+use quote::{format_ident, quote};
+use regex::{Captures, Regex};
+use std::{
+    collections::{BTreeMap, HashMap},
+    fmt,
+    fs::read_to_string,
+    io::Error as IoError,
+    path::{Path, PathBuf},
+    rc::Rc,
+};
 
 pub type Manifest = HashMap<String, Vec<(String, bool)>>;
 
+/// Fields of the views along with the props of the views which declare them. These are used
+/// when the views are used as components.
+#[derive(Default)]
+pub struct Signatures {
+    pub fields: Manifest,
+    /// Names of the props along with whether they are required
+    pub props: HashMap<String, Vec<(String, bool)>>,
+    /// Names of the scoped slots
+    pub slots: HashMap<String, Vec<String>>,
+    /// Names of the filters of the app
+    pub filters: Vec<String>,
+}
+
 pub static FILE_REGEX: Lazy<Regex> = Lazy::new(|| {
     Regex::new(r"^([[:alpha:]]([[:word:]]*[[:alnum:]])?)\.html$").expect(INTERNAL_ERR)
 });
 pub static FOLDER_REGEX: Lazy<Regex> =
     Lazy::new(|| Regex::new(r"^([[:alpha:]]([[:word:]]*[[:alnum:]])?)").expect(INTERNAL_ERR));
 
-pub fn tokenize_view(path: &Path, file_base_name: &str) -> (TokenStream, Vec<(Ident, bool)>) {
+/// Errors that can occur when building the views.
+#[derive(Debug)]
+pub enum Error {
+    Io(IoError),
+    Parse(ParseError),
+}
+
+impl From<IoError> for Error {
+    fn from(err: IoError) -> Self {
+        Error::Io(err)
+    }
+}
+
+impl From<ParseError> for Error {
+    fn from(err: ParseError) -> Self {
+        Error::Parse(err)
+    }
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Error::Io(err) => write!(f, "{}", err),
+            Error::Parse(err) => write!(f, "{}", err),
+        }
+    }
+}
+
+fn view_code(file_base_name: &str, tokenized: Tokenized) -> TokenStream {
     let cased = to_pascal_case(file_base_name);
     let ident = Ident::new(&cased, Span::call_site());
 
-    let (tokens, idents, types) = tokenize(
-        parse(
-            read_to_string(path)
-                .expect(INTERNAL_ERR)
-                .replace("\r\n", "\n"),
+    let Tokenized {
+        tokens,
+        fields,
+        types,
+        sections,
+        props,
+        slots,
+        ..
+    } = tokenized;
+
+    let new_idents: Vec<Ident> = fields.into_iter().map(|x| x.0).collect();
+
+    // Defaults of the props which are not required when used as a component
+    let defaults: Vec<TokenStream> = props
+        .unwrap_or_default()
+        .iter()
+        .filter(|x| !x.required())
+        .map(|x| {
+            let name = format_ident!("_default_{}", x.name);
+            let ty = x.ty();
+            let default = match &x.default {
+                Some(default) => quote! { #default },
+                None => quote! { ::std::default::Default::default() },
+            };
+
+            quote! {
+                pub fn #name() -> #ty {
+                    #default
+                }
+            }
+        })
+        .collect();
+
+    // Templates given by the parent for the scoped slots are called with the values of the slot
+    let (slots_type, slots_struct) = if slots.is_empty() {
+        (quote! { ::reign::view::Slots<'a> }, quote! {})
+    } else {
+        let scoped = scoped_slots_ident(&ident);
+        let fields = slots.iter().map(|(name, types)| {
+            let field = slot_field(name);
+
+            quote! {
+                pub #field: Option<Box<dyn Fn(&mut dyn std::fmt::Write, #(#types),*) -> std::fmt::Result + 'a>>
+            }
+        });
+
+        (
+            quote! { ::reign::view::Slots<'a, #scoped<'a>> },
+            quote! {
+                #[derive(Default)]
+                pub struct #scoped<'a> {
+                    #(#fields),*
+                }
+            },
         )
-        .expect(INTERNAL_ERR),
-    );
+    };
 
-    let new_idents: Vec<Ident> = idents.iter().map(|x| x.0.clone()).collect();
+    quote! {
+        pub struct #ident<'a> {
+            pub _slots: #slots_type,
+            #(pub #new_idents: #types),*
+        }
+
+        #slots_struct
+
+        #[allow(unused_variables)]
+        impl<'a> std::fmt::Display for #ident<'a> {
+            fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+                #tokens
+                Ok(())
+            }
+        }
 
-    (
-        quote! {
-            pub struct #ident<'a> {
-                pub _slots: ::reign::view::Slots<'a>,
-                #(pub #new_idents: #types),*
+        impl<'a> ::reign::view::Render for #ident<'a> {
+            fn render(&self, f: &mut dyn std::fmt::Write) -> std::fmt::Result {
+                write!(f, "{}", self)
             }
+        }
 
-            #[allow(unused_variables)]
-            impl<'a> std::fmt::Display for #ident<'a> {
-                fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
-                    #tokens
-                    Ok(())
+        #[allow(unused_variables)]
+        impl<'a> #ident<'a> {
+            pub fn _content(&'a self) -> ::reign::view::Slots<'a> {
+                ::reign::view::Slots {
+                    templates: ::reign::view::maplit::hashmap! {
+                        #sections
+                    },
+                    children: ::reign::view::slot_render(move |f: &mut dyn std::fmt::Write| {
+                        write!(f, "{}", self)
+                    }),
+                    scoped: (),
+                    phantom: ::std::marker::PhantomData,
                 }
             }
-        },
-        idents,
-    )
+
+            #(#defaults)*
+        }
+    }
 }
 
-pub fn recurse<O, I, P>(
+pub fn tokenize_view(
+    path: &Path,
+    file_base_name: &str,
+) -> Result<(TokenStream, Vec<(Ident, bool)>), Error> {
+    let tokenized = tokenize(read_to_string(path)?.replace("\r\n", "\n"), Rc::default())
+        .map_err(|e| e.in_file(path))?;
+
+    let idents = tokenized.fields.clone();
+
+    Ok((view_code(file_base_name, tokenized), idents))
+}
+
+/// View file along with the information needed to generate its code.
+struct View {
+    path: PathBuf,
+    file_base_name: String,
+    content: String,
+    hash: String,
+    fields: Vec<(String, bool)>,
+    props: Option<Vec<(String, bool)>>,
+    slots: Vec<String>,
+    components: Vec<String>,
+    code: Option<TokenStream>,
+    /// Cached file with the generated code
+    generated: Option<PathBuf>,
+}
+
+impl View {
+    fn new(path: PathBuf, file_base_name: &str) -> Self {
+        Self {
+            path,
+            file_base_name: file_base_name.to_string(),
+            content: String::new(),
+            hash: String::new(),
+            fields: vec![],
+            props: None,
+            slots: vec![],
+            components: vec![],
+            code: None,
+            generated: None,
+        }
+    }
+
+    /// Loads the fields of the view and the components it uses.
+    fn load(&mut self, cache: &mut Cache, filters: &[String]) -> Result<(), Error> {
+        self.content = read_to_string(&self.path)?.replace("\r\n", "\n");
+        self.hash = hash(&self.content);
+
+        // Views with the same content in different files still generate different structs
+        let key = format!(
+            "{}.fields",
+            hash(&(&self.file_base_name, &self.hash, filters))
+        );
+
+        if let Some(cached) = cache.read(&key) {
+            if self.read_fields(&cached) {
+                return Ok(());
+            }
+        }
+
+        let signatures = Signatures {
+            filters: filters.to_vec(),
+            ..Default::default()
+        };
+        let tokenized = tokenize_located(self.content.clone(), Rc::new(signatures))
+            .map_err(|e| e.in_file(&self.path))?;
+
+        self.fields = tokenized
+            .fields
+            .iter()
+            .map(|x| (x.0.to_string(), x.1))
+            .collect();
+        self.fields.sort();
+        self.props = tokenized.props.as_ref().map(|props| {
+            props
+                .iter()
+                .map(|x| (x.name.to_string(), x.required()))
+                .collect()
+        });
+        self.slots = tokenized.slots.iter().map(|x| x.0.clone()).collect();
+        self.components = tokenized.components.clone();
+
+        // The code does not depend on the other views when there are no components
+        if self.components.is_empty() {
+            self.code = Some(view_code(&self.file_base_name, tokenized));
+        }
+
+        cache.write(&key, &self.write_fields());
+        Ok(())
+    }
+
+    /// Generates the code of the view unless the view and the signatures of the components
+    /// it uses have not changed since the last time.
+    fn generate(
+        &mut self,
+        cache: &mut Cache,
+        signatures: &Rc<Signatures>,
+    ) -> Result<TokenStream, Error> {
+        let signature: Vec<_> = self
+            .components
+            .iter()
+            .map(|x| {
+                (
+                    x,
+                    signatures.fields.get(x),
+                    signatures.props.get(x),
+                    signatures.slots.get(x),
+                )
+            })
+            .collect();
+
+        let key = format!(
+            "{}.rs",
+            hash(&(
+                &self.file_base_name,
+                &self.hash,
+                signature,
+                &signatures.filters
+            ))
+        );
+
+        if let Some(code) = cache.read(&key).and_then(|x| x.parse().ok()) {
+            self.generated = cache.path(&key);
+            return Ok(code);
+        }
+
+        let code = match self.code.take() {
+            Some(code) => code,
+            None => {
+                let tokenized = tokenize_located(self.content.clone(), signatures.clone())
+                    .map_err(|e| e.in_file(&self.path))?;
+
+                view_code(&self.file_base_name, tokenized)
+            }
+        };
+
+        let code = located(&code.to_string(), &self.content, &self.path);
+
+        cache.write(&key, &code);
+        self.generated = cache.path(&key);
+        Ok(code.parse().expect(INTERNAL_ERR))
+    }
+
+    fn read_fields(&mut self, cached: &str) -> bool {
+        for line in cached.lines() {
+            match line.split(' ').collect::<Vec<_>>().as_slice() {
+                ["field", name, typed] => self.fields.push((name.to_string(), *typed == "1")),
+                ["props"] => self.props = Some(vec![]),
+                ["prop", name, required] => self
+                    .props
+                    .get_or_insert_with(Vec::new)
+                    .push((name.to_string(), *required == "1")),
+                ["slot", name] => self.slots.push(name.to_string()),
+                ["component", id] => self.components.push(id.to_string()),
+                _ => {
+                    self.fields.clear();
+                    self.props = None;
+                    self.slots.clear();
+                    self.components.clear();
+                    return false;
+                }
+            }
+        }
+
+        true
+    }
+
+    fn write_fields(&self) -> String {
+        let fields = self
+            .fields
+            .iter()
+            .map(|(name, typed)| format!("field {} {}\n", name, if *typed { 1 } else { 0 }));
+        let props = self.props.iter().flat_map(|props| {
+            let names = props.iter().map(|(name, required)| {
+                format!("prop {} {}\n", name, if *required { 1 } else { 0 })
+            });
+
+            std::iter::once("props\n".to_string()).chain(names)
+        });
+        let slots = self.slots.iter().map(|name| format!("slot {}\n", name));
+        let components = self
+            .components
+            .iter()
+            .map(|id| format!("component {}\n", id));
+
+        fields.chain(props).chain(slots).chain(components).collect()
+    }
+}
+
+static LOCATION_REGEX: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(&format!(r#"let\s*_\s*=\s*"{}(\d+)"\s*;"#, LOCATION)).expect(INTERNAL_ERR)
+});
+
+/// Replaces the markers in the code with comments which point at the elements in the view.
+///
+/// The code of every element starts on a new line after its comment, so that the errors
+/// of the compiler in the generated file can be traced back to the view.
+fn located(code: &str, content: &str, path: &Path) -> String {
+    LOCATION_REGEX
+        .replace_all(code, |caps: &Captures| {
+            let cursor: usize = caps[1].parse().expect(INTERNAL_ERR);
+            let before = content.get(..cursor).unwrap_or(content);
+            let line = before.matches('\n').count() + 1;
+            let column = before.chars().rev().take_while(|&c| c != '\n').count() + 1;
+
+            format!("\n// {}:{}:{}\n", path.display(), line, column)
+        })
+        .into_owned()
+}
+
+enum Entry {
+    Folder(Ident, PathBuf, Vec<Entry>),
+    File(String),
+}
+
+fn walk(
     path: &Path,
     relative_path: &str,
+    views: &mut BTreeMap<String, View>,
+) -> Result<Vec<Entry>, Error> {
+    let mut entries = vec![];
+
+    for entry in path.read_dir()?.flatten() {
+        let new_path = entry.path();
+        let file_name_os_str = entry.file_name();
+        let file_name = file_name_os_str.to_string_lossy();
+
+        if new_path.is_dir() {
+            if !FOLDER_REGEX.is_match(&file_name) {
+                continue;
+            }
+
+            let ident = Ident::new(&file_name, Span::call_site());
+            let sub_relative_path = format!("{}:{}", relative_path, file_name);
+            let sub_entries = walk(&new_path, &sub_relative_path, views)?;
+
+            entries.push(Entry::Folder(ident, new_path, sub_entries));
+            continue;
+        }
+
+        if !FILE_REGEX.is_match(&file_name) {
+            continue;
+        }
+
+        let file_base_name = file_name.trim_end_matches(".html");
+        let file_key = format!("{}:{}", relative_path, file_base_name)
+            .trim_start_matches(':')
+            .to_string();
+
+        views.insert(file_key.clone(), View::new(new_path, file_base_name));
+        entries.push(Entry::File(file_key));
+    }
+
+    Ok(entries)
+}
+
+fn build<O, I, P>(
+    path: &Path,
+    entries: Vec<Entry>,
+    codes: &mut HashMap<String, TokenStream>,
+    views: &BTreeMap<String, View>,
+    hooks: (O, I, P),
+) -> Result<Vec<TokenStream>, Error>
+where
+    O: Fn(Ident, Vec<TokenStream>) -> Result<TokenStream, Error> + Copy,
+    I: Fn(&Path, &str, TokenStream, Option<&Path>) -> Result<TokenStream, Error> + Copy,
+    P: Fn(&Path, Vec<TokenStream>) -> Result<Vec<TokenStream>, Error> + Copy,
+{
+    let (folder_hook, file_hook, path_hook) = hooks;
+    let mut tokens = vec![];
+
+    for entry in entries {
+        match entry {
+            Entry::Folder(ident, sub_path, sub_entries) => {
+                let sub_views = build(&sub_path, sub_entries, codes, views, hooks)?;
+
+                tokens.push(folder_hook(ident, sub_views)?);
+            }
+            Entry::File(key) => {
+                let code = codes.remove(&key).expect(INTERNAL_ERR);
+                let view = views.get(&key).expect(INTERNAL_ERR);
+
+                tokens.push(file_hook(
+                    path,
+                    &view.file_base_name,
+                    code,
+                    view.generated.as_deref(),
+                )?);
+            }
+        }
+    }
+
+    path_hook(path, tokens)
+}
+
+/// Generates the code of all the views in the given directory and collects their fields
+/// into the manifest.
+///
+/// The generated code is cached in the given cache directory. A view is only regenerated
+/// when its content or the fields of the components it uses change. The file hook is given
+/// the cached file of the view, in which the code of the elements is preceded by comments
+/// with their position in the view.
+///
+/// Only the built in filters and the given filters of the app are applied with `|`.
+pub fn recurse<O, I, P>(
+    path: &Path,
+    cache: Option<&Path>,
+    filters: &[String],
     manifest: &mut Manifest,
     folder_hook: O,
     file_hook: I,
@@ -61,57 +487,171 @@ pub fn recurse<O, I, P>(
 ) -> Result<Vec<TokenStream>, Error>
 where
     O: Fn(Ident, Vec<TokenStream>) -> Result<TokenStream, Error> + Copy,
-    I: Fn(&Path, &str, TokenStream) -> Result<TokenStream, Error> + Copy,
+    I: Fn(&Path, &str, TokenStream, Option<&Path>) -> Result<TokenStream, Error> + Copy,
     P: Fn(&Path, Vec<TokenStream>) -> Result<Vec<TokenStream>, Error> + Copy,
 {
-    let mut views = vec![];
+    let mut cache = Cache::new(cache);
+    let mut views = BTreeMap::new();
+    let mut codes = HashMap::new();
 
-    for entry in path.read_dir().expect(INTERNAL_ERR) {
-        if let Ok(entry) = entry {
-            let new_path = entry.path();
-            let file_name_os_str = entry.file_name();
-            let file_name = file_name_os_str.to_string_lossy();
+    let entries = walk(path, "", &mut views)?;
 
-            if new_path.is_dir() {
-                if !FOLDER_REGEX.is_match(&file_name) {
-                    continue;
-                }
+    let mut props = HashMap::new();
+    let mut slots = HashMap::new();
 
-                let ident = Ident::new(&file_name, Span::call_site());
-                let sub_relative_path = format!("{}:{}", relative_path, file_name);
+    for (key, view) in views.iter_mut() {
+        view.load(&mut cache, filters)?;
+        manifest.insert(key.clone(), view.fields.clone());
+        slots.insert(key.clone(), view.slots.clone());
 
-                let sub_views = recurse(
-                    &new_path,
-                    &sub_relative_path,
-                    manifest,
-                    folder_hook,
-                    file_hook,
-                    path_hook,
-                )?;
+        if let Some(view_props) = &view.props {
+            props.insert(key.clone(), view_props.clone());
+        }
+    }
 
-                views.push(folder_hook(ident, sub_views)?);
-                continue;
-            }
+    let shared = Rc::new(Signatures {
+        fields: manifest.clone(),
+        props,
+        slots,
+        filters: filters.to_vec(),
+    });
 
-            if !FILE_REGEX.is_match(&file_name) {
-                continue;
-            }
+    for (key, view) in views.iter_mut() {
+        codes.insert(key.clone(), view.generate(&mut cache, &shared)?);
+    }
+
+    cache.prune();
+
+    build(
+        path,
+        entries,
+        &mut codes,
+        &views,
+        (folder_hook, file_hook, path_hook),
+    )
+}
+
+#[cfg(test)]
+mod test {
+    use super::{recurse, Manifest};
+    use proc_macro2::TokenStream;
+    use std::{
+        cell::RefCell,
+        env::temp_dir,
+        fs::{create_dir_all, read_dir, read_to_string, remove_dir_all, write},
+        path::{Path, PathBuf},
+    };
+
+    fn build(views: &Path, cache: &Path) -> (String, Manifest) {
+        let mut manifest = Manifest::new();
+
+        let tokens = recurse(
+            views,
+            Some(cache),
+            &[],
+            &mut manifest,
+            |_, files| Ok(files.into_iter().collect()),
+            |_, _, file, _| Ok(file),
+            |_, views| Ok(views),
+        )
+        .unwrap();
+
+        (
+            tokens.into_iter().collect::<TokenStream>().to_string(),
+            manifest,
+        )
+    }
+
+    fn dirs(name: &str) -> (PathBuf, PathBuf) {
+        let dir = temp_dir().join("reign_view_recurse").join(name);
+        let _ = remove_dir_all(&dir);
+
+        create_dir_all(dir.join("views")).unwrap();
+        (dir.join("views"), dir.join("cache"))
+    }
 
-            let file_base_name = file_name.trim_end_matches(".html");
-            let (file_view, idents) = tokenize_view(&new_path, file_base_name);
+    #[test]
+    fn test_component_defaults() {
+        let (views, cache) = dirs("defaults");
 
-            let file_key = format!("{}:{}", relative_path, file_base_name)
-                .trim_start_matches(':')
-                .to_string();
+        write(views.join("app.html"), "<badge />").unwrap();
+        write(views.join("badge.html"), "<span>{{ label }}</span>").unwrap();
 
-            manifest.insert(
-                file_key,
-                idents.iter().map(|x| (format!("{}", x.0), x.1)).collect(),
-            );
+        let (code, manifest) = build(&views, &cache);
 
-            views.push(file_hook(path, file_base_name, file_view)?);
+        assert!(code.contains("label : :: std :: default :: Default :: default ()"));
+        assert_eq!(manifest["badge"], vec![("label".to_string(), false)]);
+        assert_eq!(manifest["app"], vec![]);
+    }
+
+    #[test]
+    fn test_cache() {
+        let (views, cache) = dirs("cache");
+
+        write(views.join("app.html"), "<badge />").unwrap();
+        write(views.join("badge.html"), "<span>{{ label }}</span>").unwrap();
+
+        let (first, _) = build(&views, &cache);
+        let (second, _) = build(&views, &cache);
+
+        assert_eq!(first, second);
+        assert_eq!(read_dir(&cache).unwrap().count(), 4);
+
+        // Changing the fields of the component regenerates the view using it
+        write(views.join("badge.html"), "<span>{{ text }}</span>").unwrap();
+
+        let (third, _) = build(&views, &cache);
+
+        assert!(third.contains("text : :: std :: default :: Default :: default ()"));
+        assert!(!third.contains("label"));
+        // The old entries are kept until they expire
+        assert_eq!(read_dir(&cache).unwrap().count(), 7);
+    }
+
+    #[test]
+    fn test_same_content() {
+        let (views, cache) = dirs("same");
+
+        write(views.join("about.html"), "<p>{{ name }}</p>").unwrap();
+        write(views.join("contact.html"), "<p>{{ name }}</p>").unwrap();
+
+        for _ in 0..2 {
+            let (code, _) = build(&views, &cache);
+
+            assert!(code.contains("pub struct About"));
+            assert!(code.contains("pub struct Contact"));
         }
     }
 
-    Ok(path_hook(path, views)?)
+    #[test]
+    fn test_generated_file() {
+        let (views, cache) = dirs("generated");
+        let generated = RefCell::new(vec![]);
+
+        write(
+            views.join("app.html"),
+            "<div>\n  <p !if=\"shown\">{{ name }}</p>\n</div>",
+        )
+        .unwrap();
+        recurse(
+            &views,
+            Some(&cache),
+            &[],
+            &mut Manifest::new(),
+            |_, files| Ok(files.into_iter().collect()),
+            |_, _, file, path| {
+                generated.borrow_mut().push(path.map(Path::to_path_buf));
+                Ok(file)
+            },
+            |_, views| Ok(views),
+        )
+        .unwrap();
+
+        let code = read_to_string(generated.borrow()[0].as_ref().unwrap()).unwrap();
+        let app = views.join("app.html");
+
+        assert!(code.contains(&format!("\n// {}:1:1\n", app.display())));
+        assert!(code.contains(&format!("\n// {}:2:3\n", app.display())));
+        assert!(!code.contains("reign:location"));
+    }
 }
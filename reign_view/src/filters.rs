@@ -0,0 +1,213 @@
+//! Filters which can be applied to the interpolations in the views.
+//!
+//! A filter is a function which takes a reference to the value being filtered followed by
+//! the arguments given to it in the view. For example, `{{ title | truncate(20) }}` calls
+//! `truncate(&title, 20)`.
+
+use chrono::{DateTime, NaiveDate, NaiveDateTime, NaiveTime, TimeZone};
+use std::fmt::{Display, Write};
+
+/// Converts the value to uppercase.
+pub fn upper<T: Display + ?Sized>(value: &T) -> String {
+    value.to_string().to_uppercase()
+}
+
+/// Converts the value to lowercase.
+pub fn lower<T: Display + ?Sized>(value: &T) -> String {
+    value.to_string().to_lowercase()
+}
+
+/// Shortens the value to the given number of characters followed by `...`.
+pub fn truncate<T: Display + ?Sized>(value: &T, len: usize) -> String {
+    let value = value.to_string();
+
+    if value.chars().count() <= len {
+        return value;
+    }
+
+    format!("{}...", value.chars().take(len).collect::<String>())
+}
+
+/// Uses the given default when the value is missing or empty.
+pub fn default<T: Fallback + ?Sized, D: Display>(value: &T, default: D) -> String {
+    value.present().unwrap_or_else(|| default.to_string())
+}
+
+/// Formats the date or time with the given [`chrono` format](chrono::format::strftime).
+///
+/// Missing dates and invalid formats give an empty string.
+pub fn date<T: Date + ?Sized>(value: &T, format: &str) -> String {
+    value.format_date(format)
+}
+
+// Formatting with an invalid format panics when using `to_string`
+fn write_date<D: Display>(date: D) -> String {
+    let mut out = String::new();
+
+    if write!(out, "{}", date).is_err() {
+        out.clear();
+    }
+
+    out
+}
+
+/// Uses the singular form when the count is one and the plural form otherwise.
+pub fn pluralize<T: Display + ?Sized>(count: &T, singular: &str, plural: &str) -> String {
+    if count.to_string() == "1" {
+        singular.to_string()
+    } else {
+        plural.to_string()
+    }
+}
+
+/// Formats the number as an amount with two decimals and the given symbol, like `$1,234.50`.
+pub fn currency<T: Number + ?Sized>(value: &T, symbol: &str) -> String {
+    let value = value.number();
+    let fixed = format!("{:.2}", value.abs());
+    let (whole, decimals) = fixed.split_at(fixed.len() - 3);
+    let mut grouped = String::new();
+
+    for (i, c) in whole.chars().enumerate() {
+        if i != 0 && (whole.len() - i) % 3 == 0 {
+            grouped.push(',');
+        }
+
+        grouped.push(c);
+    }
+
+    let sign = if value < 0.0 { "-" } else { "" };
+
+    format!("{}{}{}{}", sign, symbol, grouped, decimals)
+}
+
+/// Values which can be missing or empty for the [`default`] filter.
+pub trait Fallback {
+    /// Returns the value as a string unless it is missing or empty.
+    fn present(&self) -> Option<String>;
+}
+
+impl Fallback for str {
+    fn present(&self) -> Option<String> {
+        Some(self.to_string()).filter(|x| !x.is_empty())
+    }
+}
+
+impl Fallback for String {
+    fn present(&self) -> Option<String> {
+        self.as_str().present()
+    }
+}
+
+impl<T: Display> Fallback for Option<T> {
+    fn present(&self) -> Option<String> {
+        self.as_ref()
+            .map(ToString::to_string)
+            .filter(|x| !x.is_empty())
+    }
+}
+
+impl<T: Fallback + ?Sized> Fallback for &T {
+    fn present(&self) -> Option<String> {
+        (**self).present()
+    }
+}
+
+/// Dates and times for the [`date`] filter.
+pub trait Date {
+    fn format_date(&self, format: &str) -> String;
+}
+
+impl Date for NaiveDate {
+    fn format_date(&self, format: &str) -> String {
+        write_date(self.format(format))
+    }
+}
+
+impl Date for NaiveDateTime {
+    fn format_date(&self, format: &str) -> String {
+        write_date(self.format(format))
+    }
+}
+
+impl Date for NaiveTime {
+    fn format_date(&self, format: &str) -> String {
+        write_date(self.format(format))
+    }
+}
+
+impl<Tz: TimeZone> Date for DateTime<Tz>
+where
+    Tz::Offset: Display,
+{
+    fn format_date(&self, format: &str) -> String {
+        write_date(self.format(format))
+    }
+}
+
+impl<T: Date> Date for Option<T> {
+    fn format_date(&self, format: &str) -> String {
+        self.as_ref()
+            .map(|x| x.format_date(format))
+            .unwrap_or_default()
+    }
+}
+
+impl<T: Date + ?Sized> Date for &T {
+    fn format_date(&self, format: &str) -> String {
+        (**self).format_date(format)
+    }
+}
+
+/// Numbers for the [`currency`] filter.
+pub trait Number {
+    fn number(&self) -> f64;
+}
+
+macro_rules! number {
+    ($($ty:ty),*) => {
+        $(
+            impl Number for $ty {
+                fn number(&self) -> f64 {
+                    *self as f64
+                }
+            }
+        )*
+    };
+}
+
+number!(u8, u16, u32, u64, u128, usize, i8, i16, i32, i64, i128, isize, f32, f64);
+
+impl<T: Number + ?Sized> Number for &T {
+    fn number(&self) -> f64 {
+        (**self).number()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_filters() {
+        assert_eq!(upper("hi"), "HI");
+        assert_eq!(truncate("hello", 3), "hel...");
+        assert_eq!(truncate("hello", 5), "hello");
+        assert_eq!(default(&None::<u8>, "n/a"), "n/a");
+        assert_eq!(default(&"", "n/a"), "n/a");
+        assert_eq!(default(&Some(2), "n/a"), "2");
+        assert_eq!(pluralize(&1, "item", "items"), "item");
+        assert_eq!(pluralize(&2, "item", "items"), "items");
+        assert_eq!(currency(&1234.5, "$"), "$1,234.50");
+        assert_eq!(currency(&-12, "$"), "-$12.00");
+        assert_eq!(
+            date(&NaiveDate::from_ymd_opt(2020, 1, 2).unwrap(), "%Y-%m-%d"),
+            "2020-01-02"
+        );
+        assert_eq!(date(&NaiveDate::from_ymd_opt(2020, 1, 2), "%Y"), "2020");
+        assert_eq!(default(&date(&None::<NaiveDate>, "%Y"), "n/a"), "n/a");
+        assert_eq!(
+            date(&NaiveDate::from_ymd_opt(2020, 1, 2).unwrap(), "%Q"),
+            ""
+        );
+    }
+}
@@ -4,12 +4,21 @@
 #[doc(hidden)]
 pub use maplit;
 
+mod cache;
 #[doc(hidden)]
 pub mod common;
+pub mod filters;
+mod loop_meta;
 #[doc(hidden)]
 pub mod parse;
+mod render;
 mod slots;
 
+#[cfg(feature = "hot-reload")]
+pub use parse::interpret;
+
+pub use loop_meta::Loop;
+pub use render::Render;
 #[doc(hidden)]
 pub use slots::{slot_render, Slots};
 
@@ -0,0 +1,10 @@
+/// Information about the current iteration of a `!for` loop.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Loop {
+    /// Index of the iteration starting from zero.
+    pub index: usize,
+    /// Whether this is the first iteration.
+    pub first: bool,
+    /// Whether this is the last iteration.
+    pub last: bool,
+}
@@ -16,6 +16,10 @@ impl Parse for ControlAttribute {
             value: {
                 if name == "for" {
                     Code::parse_for(input)?
+                } else if name == "case" {
+                    Code::parse_pat(input)?
+                } else if name == "let" {
+                    Code::parse_let(input)?
                 } else {
                     Code::parse_expr(input)?
                 }
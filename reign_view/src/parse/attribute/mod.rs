@@ -12,7 +12,7 @@ pub use control::ControlAttribute;
 use dynamic::DynamicAttribute;
 pub use normal::NormalAttribute;
 pub use value::AttributeValue;
-use variable::VariableAttribute;
+pub use variable::VariableAttribute;
 
 #[allow(clippy::large_enum_variant)]
 #[derive(Debug)]
@@ -1,4 +1,7 @@
-use super::{attribute::AttributeValue, Error, Expr, For, ParseStream, Tokenize, ViewFields};
+use super::{
+    attribute::AttributeValue, Error, Expr, Filtered, For, Let, ParseStream, Pat, Tokenize,
+    ViewFields,
+};
 use proc_macro2::TokenStream;
 use std::fmt::{Debug, Error as FError, Formatter};
 use syn::parse_str;
@@ -6,6 +9,9 @@ use syn::parse_str;
 pub enum Code {
     For(For),
     Expr(Expr),
+    Pat(Pat),
+    Let(Let),
+    Filtered(Filtered),
 }
 
 impl Code {
@@ -19,6 +25,16 @@ impl Code {
         Self::parse_expr_from_str(input, &string)
     }
 
+    pub fn parse_pat(input: &mut ParseStream) -> Result<Self, Error> {
+        let string = AttributeValue::parse_to_str(input)?;
+        Self::parse_pat_from_str(input, &string)
+    }
+
+    pub fn parse_let(input: &mut ParseStream) -> Result<Self, Error> {
+        let string = AttributeValue::parse_to_str(input)?;
+        Self::parse_let_from_str(input, &string)
+    }
+
     pub fn parse_for_from_str(input: &mut ParseStream, text: &str) -> Result<Self, Error> {
         let parsed = parse_str::<For>(text);
 
@@ -29,6 +45,26 @@ impl Code {
         }
     }
 
+    pub fn parse_pat_from_str(input: &ParseStream, text: &str) -> Result<Self, Error> {
+        let parsed = parse_str::<Pat>(text);
+
+        if let Ok(code) = parsed {
+            Ok(Code::Pat(code))
+        } else {
+            Err(input.error("expected pattern"))
+        }
+    }
+
+    pub fn parse_let_from_str(input: &ParseStream, text: &str) -> Result<Self, Error> {
+        let parsed = parse_str::<Let>(text);
+
+        if let Ok(code) = parsed {
+            Ok(Code::Let(code))
+        } else {
+            Err(input.error("expected pattern = expression"))
+        }
+    }
+
     pub fn parse_expr_from_str(input: &ParseStream, text: &str) -> Result<Self, Error> {
         let parsed = parse_str::<Expr>(text);
 
@@ -38,6 +74,17 @@ impl Code {
             Err(input.error("expected expression"))
         }
     }
+
+    /// Parses the expression of an interpolation which can be followed by filters.
+    pub fn parse_interpolation_from_str(input: &ParseStream, text: &str) -> Result<Self, Error> {
+        let parsed = Filtered::parse_str(text, &input.filters);
+
+        match parsed {
+            Ok(code) if code.filters.is_empty() => Ok(Code::Expr(code.expr)),
+            Ok(code) => Ok(Code::Filtered(code)),
+            Err(_) => Err(input.error("expected expression")),
+        }
+    }
 }
 
 impl Tokenize for Code {
@@ -45,6 +92,9 @@ impl Tokenize for Code {
         match self {
             Code::For(f) => f.tokenize(tokens, idents, scopes),
             Code::Expr(e) => e.tokenize(tokens, idents, scopes),
+            Code::Pat(p) => p.tokenize(tokens, &mut ViewFields::new(), scopes),
+            Code::Let(l) => l.tokenize(tokens, idents, scopes),
+            Code::Filtered(f) => f.tokenize(tokens, idents, scopes),
         }
     }
 }
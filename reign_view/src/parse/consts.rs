@@ -2,14 +2,14 @@ pub const DOCTYPE: &str = "(?i)<!DOCTYPE [^>]+>";
 pub const TAG_NAME: &str = "[[:alpha:]](:?[a-zA-Z0-9\\-]*[[:alnum:]])?";
 pub const ATTR_NAME: &str = "[^\\s\"\'>/=]+";
 pub const VAR_ATTR_SYMBOL: &str = ":";
-pub const CTRL_ATTR: &str = "!(for|if|else-if|else)";
+pub const CTRL_ATTR: &str = "!(for|if|else-if|else|match|case|let)";
 pub const DY_ATTR_NAME_PART: &str = "[^\\{}\\s\"\'>/=]*";
 pub const DY_ATTR_EXPR: &str = "\\{\\{([^=]+)}}";
 pub const ATTR_VALUE_DOUBLE_QUOTED: &str = "\"([^\"]*)\"";
 pub const ATTR_VALUE_SINGLE_QUOTED: &str = "'([^']*)'";
 pub const ATTR_VALUE_UNQUOTED: &str = "[^\\s\"'=<>`]+";
 
-pub const HTML_TAGS: [&str; 116] = [
+pub const HTML_TAGS: [&str; 118] = [
     "html",
     "body",
     "base",
@@ -126,6 +126,8 @@ pub const HTML_TAGS: [&str; 116] = [
     "blockquote",
     "iframe",
     "tfoot",
+    "search",
+    "math",
 ];
 
 pub const VOID_TAGS: [&str; 14] = [
@@ -133,6 +135,68 @@ pub const VOID_TAGS: [&str; 14] = [
     "track", "wbr",
 ];
 
+/// Elements whose content is kept as it is without being parsed.
+pub const RAW_TEXT_TAGS: [&str; 2] = ["script", "style"];
+
+/// Elements whose content can have interpolations but no elements.
+pub const ESCAPABLE_RAW_TEXT_TAGS: [&str; 1] = ["textarea"];
+
+/// Elements containing foreign content, where any element is written as it is instead of
+/// being a component.
+pub const FOREIGN_TAGS: [&str; 2] = ["svg", "math"];
+
+/// Elements whose end tag can be left out, along with the start tags which close them.
+/// They are also closed by the end tag of any other element or the end of the view.
+pub const OPTIONAL_END_TAGS: [(&str, &[&str]); 13] = [
+    ("li", &["li"]),
+    ("dt", &["dt", "dd"]),
+    ("dd", &["dt", "dd"]),
+    (
+        "p",
+        &[
+            "address",
+            "article",
+            "aside",
+            "blockquote",
+            "details",
+            "div",
+            "dl",
+            "fieldset",
+            "figcaption",
+            "figure",
+            "footer",
+            "form",
+            "h1",
+            "h2",
+            "h3",
+            "h4",
+            "h5",
+            "h6",
+            "header",
+            "hgroup",
+            "hr",
+            "main",
+            "menu",
+            "nav",
+            "ol",
+            "p",
+            "pre",
+            "section",
+            "table",
+            "ul",
+        ],
+    ),
+    ("rt", &["rt", "rp"]),
+    ("rp", &["rt", "rp"]),
+    ("optgroup", &["optgroup"]),
+    ("option", &["option", "optgroup"]),
+    ("thead", &["tbody", "tfoot"]),
+    ("tbody", &["tbody", "tfoot"]),
+    ("tr", &["tr", "tbody", "tfoot"]),
+    ("td", &["td", "th", "tr", "tbody", "tfoot"]),
+    ("th", &["td", "th", "tr", "tbody", "tfoot"]),
+];
+
 pub const SVG_TAGS: [&str; 30] = [
     "svg",
     "animate",
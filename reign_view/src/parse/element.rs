@@ -1,22 +1,28 @@
 use super::consts::*;
 use super::{
-    attribute::{ControlAttribute, NormalAttribute},
-    tag_name_regex, Attribute, Code, Error, Node, Parse, ParseStream, Tokenize, ViewFields,
+    attribute::{ControlAttribute, NormalAttribute, VariableAttribute},
+    scoped_slots_ident, slot_field, tag_name_regex, Args, Attribute, Code, Error, Expr, For, Node,
+    Parse, ParseStream, StringPart, Text, Tokenize, ViewFields, CASE_WITHOUT_MATCH,
+    ELSE_WITHOUT_IF, LOCATION,
 };
+use crate::INTERNAL_ERR;
 use inflector::cases::{pascalcase::to_pascal_case, snakecase::to_snake_case};
 use proc_macro2::{Punct, Spacing, Span, TokenStream};
-use quote::{quote, TokenStreamExt};
-use syn::{Ident, LitStr};
+use quote::{format_ident, quote, ToTokens, TokenStreamExt};
+use syn::{parse_str, Ident, LitStr};
 
 #[derive(Debug)]
 pub struct Element {
     pub name: String,
     pub attrs: Vec<Attribute>,
     pub children: Vec<Node>,
+    pub cursor: usize,
+    /// Whether the element is inside `<svg>` or `<math>`
+    pub foreign: bool,
 }
 
 impl Element {
-    fn control_attr(&self, name: &str) -> Option<&ControlAttribute> {
+    pub(super) fn control_attr(&self, name: &str) -> Option<&ControlAttribute> {
         for attr in &self.attrs {
             if let Attribute::Control(control) = attr {
                 if control.name == name {
@@ -28,7 +34,7 @@ impl Element {
         None
     }
 
-    fn normal_attr(&self, name: &str) -> Option<&NormalAttribute> {
+    pub(super) fn normal_attr(&self, name: &str) -> Option<&NormalAttribute> {
         for attr in &self.attrs {
             if let Attribute::Normal(normal) = attr {
                 if normal.name == name {
@@ -40,25 +46,74 @@ impl Element {
         None
     }
 
-    fn slot_name(&self) -> String {
-        if let Some(attr) = self.normal_attr("name") {
-            if let Some(name) = attr.value.value() {
-                return name;
-            } else {
-                // TODO:(view:err) Show the error position
-                panic!("slot name should not have expression");
+    pub(super) fn variable_attr(&self, name: &str) -> Option<&VariableAttribute> {
+        for attr in &self.attrs {
+            if let Attribute::Variable(variable) = attr {
+                if variable.name == name {
+                    return Some(variable);
+                }
             }
         }
 
+        None
+    }
+
+    pub(super) fn slot_name(&self) -> String {
+        if let Some(attr) = self.normal_attr("name") {
+            // Slot names with expressions are rejected when parsing
+            return attr.value.value().expect(INTERNAL_ERR);
+        }
+
         "default".to_string()
     }
 
-    fn template_name(&self) -> Option<String> {
+    /// Whether the element can be followed by an `!else` or `!else-if` element.
+    fn starts_chain(&self) -> bool {
+        self.control_attr("if").is_some()
+            || self.control_attr("else-if").is_some()
+            || self.control_attr("for").is_some()
+    }
+
+    /// Checks the control attributes of the child against the ones of this element.
+    fn check_child(&self, child: &Element, chain: bool) -> Option<&'static str> {
+        let is_else =
+            child.control_attr("else").is_some() || child.control_attr("else-if").is_some();
+        let is_case = child.control_attr("case").is_some();
+
+        if self.control_attr("match").is_some() {
+            if !is_case {
+                return Some("expected only `!case` elements inside `!match`");
+            }
+
+            if child.control_attr("for").is_some() || is_else {
+                return Some("expected no `!for`, `!else` or `!else-if` on `!case` element");
+            }
+        } else if is_case {
+            return Some(CASE_WITHOUT_MATCH);
+        } else if is_else && !chain {
+            return Some(ELSE_WITHOUT_IF);
+        }
+
+        None
+    }
+
+    /// Values given by the slot to the template of the parent, which makes it a scoped slot.
+    pub(super) fn slot_props(&self) -> Vec<&VariableAttribute> {
+        self.attrs
+            .iter()
+            .filter_map(|x| match x {
+                Attribute::Variable(v) => Some(v),
+                _ => None,
+            })
+            .collect()
+    }
+
+    fn template_attr(&self) -> Option<&NormalAttribute> {
         if self.name == "template" {
             for attr in &self.attrs {
                 if let Attribute::Normal(n) = attr {
                     if n.name.starts_with('#') {
-                        return Some(n.name.clone());
+                        return Some(n);
                     }
                 }
             }
@@ -67,7 +122,23 @@ impl Element {
         None
     }
 
-    fn templates(
+    pub(super) fn template_name(&self) -> Option<String> {
+        self.template_attr().map(|x| x.name.clone())
+    }
+
+    /// Patterns of the values when the template is given to a scoped slot like `#row="item"`.
+    pub(super) fn slot_args(&self) -> Option<Args> {
+        let value = self.template_attr()?.value.value()?;
+
+        if value == "\"\"" || value.trim().is_empty() {
+            return None;
+        }
+
+        // Invalid patterns are rejected when parsing
+        Some(parse_str(&value).expect(INTERNAL_ERR))
+    }
+
+    pub(super) fn templates(
         &self,
         idents: &mut ViewFields,
         scopes: &ViewFields,
@@ -77,11 +148,13 @@ impl Element {
 
         for child in &self.children {
             if let Node::Element(e) = child {
+                if e.slot_args().is_some() {
+                    continue;
+                }
+
                 if let Some(name) = e.template_name() {
                     let name = name.get(1..).unwrap();
-                    let mut ts = TokenStream::new();
-
-                    child.tokenize(&mut ts, idents, scopes);
+                    let ts = self.child_tokens(child, idents, scopes, false);
 
                     names.push(LitStr::new(name, Span::call_site()));
                     templates.push(ts);
@@ -92,42 +165,158 @@ impl Element {
         (names, templates)
     }
 
-    // TODO: Build a DAG out of the views, and use default() if the attrs are not defined
-    // It would be even better if we could compile each html file into `.rs` file and use
-    // it to speed up compile times.
-    //
-    // If we have the DAG, and we see an html file was changed, we rebuild that view, and
-    // if any of it fields have changed, we need to go up in the DAG and recompile all the
-    // views that depend on this.
-    //
-    // After having DAG, we can also look into intelligently forwarding the types of the
-    // view fields into each components.
-    fn component_attrs(&self, idents: &mut ViewFields, scopes: &ViewFields) -> Vec<TokenStream> {
+    // TODO: Intelligently forward the types of the view fields into each components
+    fn component_attrs(
+        &self,
+        id: String,
+        path: &[Ident],
+        idents: &mut ViewFields,
+        scopes: &ViewFields,
+    ) -> Vec<TokenStream> {
         let mut attrs = vec![];
+        let mut names = vec![];
 
         for attr in &self.attrs {
             let mut tokens = TokenStream::new();
 
-            match attr {
+            let name = match attr {
                 Attribute::Normal(n) => {
-                    tokens.append(Ident::new(&to_snake_case(&n.name), Span::call_site()));
+                    let name = to_snake_case(&n.name);
+
+                    tokens.append(Ident::new(&name, Span::call_site()));
                     tokens.append(Punct::new(':', Spacing::Alone));
                     n.value.tokenize(&mut tokens, idents, scopes);
+                    name
                 }
                 Attribute::Variable(v) => {
-                    tokens.append(Ident::new(&to_snake_case(&v.name), Span::call_site()));
+                    let name = to_snake_case(&v.name);
+
+                    tokens.append(Ident::new(&name, Span::call_site()));
                     tokens.append(Punct::new(':', Spacing::Alone));
                     v.value.tokenize(&mut tokens, idents, scopes);
+                    name
                 }
                 _ => continue,
-            }
+            };
 
+            names.push(name);
             attrs.push(tokens);
         }
 
+        let signatures = idents.signatures.clone();
+
+        if let Some(props) = signatures.props.get(&id) {
+            for name in &names {
+                if !props.iter().any(|x| x.0 == *name) {
+                    idents.errors.push((
+                        Some(self.cursor),
+                        format!("component `{}` has no prop `{}`", id, name),
+                    ));
+                }
+            }
+
+            for (prop, required) in props {
+                if names.contains(prop) {
+                    continue;
+                }
+
+                if *required {
+                    idents.errors.push((
+                        Some(self.cursor),
+                        format!("component `{}` needs the prop `{}`", id, prop),
+                    ));
+                } else {
+                    let ident = Ident::new(prop, Span::call_site());
+                    let default = format_ident!("_default_{}", prop);
+
+                    attrs.push(quote! {
+                        #ident: crate::views::#(#path)::*::#default()
+                    });
+                }
+            }
+        } else if let Some(fields) = signatures.fields.get(&id) {
+            // The fields of the component which are not given are defaulted
+            for (field, _) in fields {
+                if !names.contains(field) {
+                    let ident = Ident::new(field, Span::call_site());
+
+                    attrs.push(quote! {
+                        #ident: ::std::default::Default::default()
+                    });
+                }
+            }
+        }
+
+        idents.components.push(id);
         attrs
     }
 
+    /// Templates with patterns which are given to the scoped slots of the component.
+    fn scoped_tokens(
+        &self,
+        id: &str,
+        path: &[Ident],
+        idents: &mut ViewFields,
+        scopes: &ViewFields,
+    ) -> TokenStream {
+        let mut fields = vec![];
+        let mut names = vec![];
+
+        for child in &self.children {
+            if let Node::Element(e) = child {
+                if let (Some(name), Some(Args(pats))) = (e.template_name(), e.slot_args()) {
+                    let name = name[1..].to_string();
+                    let field = slot_field(&name);
+                    let mut new_scopes = scopes.clone();
+                    let mut args = vec![];
+
+                    for pat in &pats {
+                        let mut ts = TokenStream::new();
+
+                        pat.tokenize(&mut ts, &mut ViewFields::new(), scopes);
+                        new_scopes.append(pat.declared());
+                        args.push(ts);
+                    }
+
+                    let body = self.child_tokens(child, idents, &new_scopes, false);
+
+                    fields.push(quote! {
+                        #field: Some(Box::new(|f: &mut dyn std::fmt::Write, #(#args),*| {
+                            #body
+                            Ok(())
+                        }))
+                    });
+                    names.push((name, e.cursor));
+                }
+            }
+        }
+
+        if names.is_empty() {
+            return quote! { ::std::default::Default::default() };
+        }
+
+        if let Some(slots) = idents.signatures.clone().slots.get(id) {
+            for (name, cursor) in &names {
+                if !slots.contains(name) {
+                    idents.errors.push((
+                        Some(*cursor),
+                        format!("component `{}` has no scoped slot `{}`", id, name),
+                    ));
+                }
+            }
+        }
+
+        let (last, modules) = path.split_last().expect(INTERNAL_ERR);
+        let ident = scoped_slots_ident(last);
+
+        quote! {
+            crate::views::#(#modules::)*#ident {
+                #(#fields,)*
+                ..::std::default::Default::default()
+            }
+        }
+    }
+
     fn end_tokens(&self) -> TokenStream {
         if !VOID_TAGS.contains(&self.name.as_str()) {
             let end_tag = LitStr::new(&format!("</{}>", &self.name), Span::call_site());
@@ -152,6 +341,99 @@ impl Element {
             .collect()
     }
 
+    // Errors from the text and the attributes are located at this element while the child
+    // elements locate their own errors
+    fn child_tokens(
+        &self,
+        child: &Node,
+        idents: &mut ViewFields,
+        scopes: &ViewFields,
+        chained: bool,
+    ) -> TokenStream {
+        let mut ts = TokenStream::new();
+
+        idents.locate(self.cursor);
+
+        match child {
+            Node::Element(e) if chained => e.tokenize_chained(&mut ts, idents, scopes, true),
+            _ => child.tokenize(&mut ts, idents, scopes),
+        }
+
+        idents.locate(self.cursor);
+        ts
+    }
+
+    fn inner_tokens(&self, idents: &mut ViewFields, scopes: &ViewFields) -> Vec<TokenStream> {
+        if let Some(r_match) = self.control_attr("match") {
+            vec![self.match_tokens(r_match, idents, scopes)]
+        } else {
+            self.children_tokens(idents, scopes)
+        }
+    }
+
+    fn match_tokens(
+        &self,
+        r_match: &ControlAttribute,
+        idents: &mut ViewFields,
+        scopes: &ViewFields,
+    ) -> TokenStream {
+        let mut expr = TokenStream::new();
+        let mut arms = vec![];
+
+        r_match.value.tokenize(&mut expr, idents, scopes);
+        idents.locate(self.cursor);
+
+        for child in &self.children {
+            // Named templates are the only other elements allowed here
+            let (e, r_case) = match child {
+                Node::Element(e) => match e.control_attr("case") {
+                    Some(r_case) => (e, r_case),
+                    None => continue,
+                },
+                _ => continue,
+            };
+
+            let mut pat = TokenStream::new();
+            let mut new_scopes = scopes.clone();
+
+            if let Code::Pat(p) = &r_case.value {
+                new_scopes.append(p.declared());
+            }
+
+            r_case.value.tokenize(&mut pat, idents, scopes);
+
+            // An `!if` on the case is its guard
+            let guard = if let Some(r_if) = e.control_attr("if") {
+                let mut if_expr = TokenStream::new();
+                r_if.value.tokenize(&mut if_expr, idents, &new_scopes);
+
+                quote! { if #if_expr }
+            } else {
+                quote! {}
+            };
+
+            let body = e.body_tokens(idents, &new_scopes);
+            let location = e.location_tokens(idents);
+
+            idents.locate(e.cursor);
+            arms.push(quote! {
+                #pat #guard => {
+                    #location
+                    #body
+                }
+            });
+        }
+
+        // Values not matched by any case render nothing
+        quote! {
+            match &(#expr) {
+                #(#arms)*
+                #[allow(unreachable_patterns)]
+                _ => {}
+            }
+        }
+    }
+
     fn children_tokens(&self, idents: &mut ViewFields, scopes: &ViewFields) -> Vec<TokenStream> {
         let mut tokens = vec![];
         let mut iter = self.children.iter();
@@ -166,7 +448,7 @@ impl Element {
                     continue;
                 }
 
-                if e.control_attr("if").is_some() {
+                if e.control_attr("if").is_some() || e.control_attr("for").is_some() {
                     let mut after_if = vec![child];
                     let mut next = iter.next();
                     let (mut has_else, mut has_else_if) = (false, false);
@@ -204,11 +486,11 @@ impl Element {
 
                     after_if = clean_if_else_group(after_if, has_else, has_else_if);
 
-                    for i in after_if {
-                        let mut ts = TokenStream::new();
+                    // A loop followed by `!else` needs to tell whether it was empty
+                    let chained = has_else || has_else_if;
 
-                        i.tokenize(&mut ts, idents, scopes);
-                        tokens.push(ts);
+                    for (n, i) in after_if.into_iter().enumerate() {
+                        tokens.push(self.child_tokens(i, idents, scopes, chained && n == 0));
                     }
 
                     // If at the end, break out
@@ -218,17 +500,9 @@ impl Element {
 
                     continue;
                 }
-
-                if e.control_attr("else").is_some() || e.control_attr("else-if").is_some() {
-                    // TODO:(view:err) Show the error position
-                    panic!("expected `!if` element before `!else` or `!else-if`");
-                }
             }
 
-            let mut ts = TokenStream::new();
-
-            child.tokenize(&mut ts, idents, scopes);
-            tokens.push(ts);
+            tokens.push(self.child_tokens(child, idents, scopes, false));
             child_option = iter.next();
         }
 
@@ -238,80 +512,244 @@ impl Element {
 
 impl Parse for Element {
     fn parse(input: &mut ParseStream) -> Result<Self, Error> {
+        let cursor = input.cursor;
         let name = input.capture(&tag_name_regex(), 1)?;
+        let mut attrs = vec![];
+        let mut children = vec![];
 
-        Ok(Element {
+        input.skip_spaces()?;
+
+        while !input.peek("/>") && !input.peek(">") {
+            attrs.push(input.parse()?);
+            input.skip_spaces()?;
+        }
+
+        let element = Element {
             name: name.to_lowercase(),
-            attrs: {
-                let mut attrs = vec![];
-                input.skip_spaces()?;
+            attrs,
+            children: vec![],
+            cursor,
+            foreign: false,
+        };
 
-                while !input.peek("/>") && !input.peek(">") {
-                    attrs.push(input.parse()?);
-                    input.skip_spaces()?;
+        if element.name == "slot" {
+            if let Some(attr) = element.normal_attr("name") {
+                if attr.value.has_expr() {
+                    return Err(input.error_at(cursor, "slot name should not have expression"));
                 }
+            }
+        }
 
-                attrs
-            },
-            children: {
-                let mut children = vec![];
+        if element.name == "component" {
+            let only_is = element.attrs.iter().all(|x| match x {
+                Attribute::Variable(v) => v.name == "is",
+                Attribute::Normal(_) => false,
+                _ => true,
+            });
+
+            if element.variable_attr("is").is_none() || !only_is {
+                return Err(input.error_at(
+                    cursor,
+                    "expected only `:is` attribute on `component` element",
+                ));
+            }
+        }
 
-                if input.peek("/>") {
-                    input.step("/>")?;
-                } else {
-                    // input.peek(">") is true here
-                    input.step(">")?;
+        if let Some(attr) = element.template_attr() {
+            let valid = match attr.value.value() {
+                Some(value) => value == "\"\"" || parse_str::<Args>(&value).is_ok(),
+                None => false,
+            };
 
-                    // TODO:(view:html) Tags that can be left open according to HTML spec
-                    if !VOID_TAGS.contains(&name.as_str()) {
-                        let closing_tag = format!("</{}", name);
+            if !valid {
+                return Err(input.error_at(cursor, "expected patterns for the values of the slot"));
+            }
+        }
 
-                        while !input.peek(&closing_tag) {
-                            let child = input.parse()?;
-                            children.push(child);
-                        }
+        if input.peek("/>") {
+            input.step("/>")?;
+        } else {
+            // input.peek(">") is true here
+            input.step(">")?;
+
+            let closing_tag = format!("</{}", name);
+
+            if RAW_TEXT_TAGS.contains(&element.name.as_str()) {
+                let content = input.until_end_tag(&name)?;
+
+                if !content.is_empty() {
+                    children.push(Node::Text(Text {
+                        content: vec![StringPart::Normal(content)],
+                    }));
+                }
+
+                input.step_end_tag(&name)?;
+            } else if ESCAPABLE_RAW_TEXT_TAGS.contains(&element.name.as_str()) {
+                let start = input.cursor;
+                let content = input.until_end_tag(&name)?;
+                let end = input.cursor;
+
+                // Point the errors in the content to where it starts
+                input.cursor = start;
+
+                let parts = StringPart::parse(input, &content, true)?;
+
+                if !parts.is_empty() {
+                    children.push(Node::Text(Text { content: parts }));
+                }
+
+                input.cursor = end;
+                input.step_end_tag(&name)?;
+            } else if !VOID_TAGS.contains(&element.name.as_str()) {
+                // Whether the previous element can be followed by `!else`
+                let mut chain = false;
+                // Whether the element was closed without an end tag
+                let mut implied = false;
+
+                while !input.is_match(&format!("{}[\\s>]", closing_tag)) {
+                    if element.is_closed_by(input) {
+                        implied = true;
+                        break;
+                    }
+
+                    let start = input.cursor;
+                    let child = input.parse()?;
+
+                    if let Node::Element(e) = &child {
+                        if e.template_name().is_none() {
+                            if let Some(message) = element.check_child(e, chain) {
+                                return Err(input.error_at(start, message));
+                            }
 
-                        input.step(&closing_tag)?;
-                        input.skip_spaces()?;
-                        input.step(">")?;
+                            chain = e.starts_chain();
+                        }
                     }
+
+                    children.push(child);
                 }
 
-                children
-            },
+                if !implied {
+                    input.step(&closing_tag)?;
+                    input.skip_spaces()?;
+                    input.step(">")?;
+                }
+            }
+        }
+
+        if FOREIGN_TAGS.contains(&element.name.as_str()) {
+            mark_foreign(&mut children);
+        }
+
+        Ok(Element {
+            children,
+            ..element
         })
     }
 }
 
-impl Tokenize for Element {
-    #[allow(clippy::cognitive_complexity)]
-    fn tokenize(&self, tokens: &mut TokenStream, idents: &mut ViewFields, scopes: &ViewFields) {
-        let tag_pieces: Vec<&str> = self.name.split(':').collect();
-        let mut new_scopes = scopes.clone();
+impl Element {
+    /// Whether the element ends here without an end tag, which is allowed by the HTML spec
+    /// for some elements when they are followed by certain start tags.
+    fn is_closed_by(&self, input: &ParseStream) -> bool {
+        let closers = match OPTIONAL_END_TAGS.iter().find(|(tag, _)| *tag == self.name) {
+            Some((_, closers)) => closers,
+            None => return false,
+        };
 
-        // Check for loop to see what variables are defined for this loop (`scopes`)
-        if let Some(attr_for) = self.control_attr("for") {
-            if let Code::For(for_) = &attr_for.value {
-                new_scopes.append(for_.declared());
+        input.cursor >= input.content.len()
+            || input.peek("</")
+            || input.is_match(&format!("(?i)<({})[\\s/>]", closers.join("|")))
+    }
+
+    /// Whether the element is written as it is instead of rendering a component.
+    ///
+    /// Custom elements, which have a dash in their name, are only components when there
+    /// is a view with the same name.
+    pub(super) fn is_plain(&self, is_view: impl Fn(&str) -> bool) -> bool {
+        if self.name.contains(':') {
+            return false;
+        }
+
+        self.foreign
+            || is_reserved_tag(&self.name)
+            || (self.name.contains('-') && !is_view(&to_snake_case(&self.name)))
+    }
+
+    /// Marker with the position of the element which is replaced by a comment in the
+    /// generated file of the view.
+    fn location_tokens(&self, idents: &ViewFields) -> TokenStream {
+        if !idents.locations {
+            return TokenStream::new();
+        }
+
+        let marker = LitStr::new(&format!("{}{}", LOCATION, self.cursor), Span::call_site());
+
+        quote! {
+            let _ = #marker;
+        }
+    }
+
+    fn body_tokens(&self, idents: &mut ViewFields, scopes: &ViewFields) -> TokenStream {
+        // Bindings are only visible to the element itself
+        if let Some(r_let) = self.control_attr("let") {
+            let mut new_scopes = scopes.clone();
+            let mut let_expr = TokenStream::new();
+
+            if let Code::Let(let_) = &r_let.value {
+                new_scopes.append(let_.declared());
             }
+
+            r_let.value.tokenize(&mut let_expr, idents, scopes);
+
+            let elem = self.element_tokens(idents, &new_scopes);
+
+            return quote! {
+                {
+                    let #let_expr;
+                    #elem
+                }
+            };
         }
 
-        let mut elem = if self.name == "template" {
-            let children = self.children_tokens(idents, &new_scopes);
+        self.element_tokens(idents, scopes)
+    }
+
+    fn element_tokens(&self, idents: &mut ViewFields, scopes: &ViewFields) -> TokenStream {
+        let tag_pieces: Vec<&str> = self.name.split(':').collect();
+
+        if self.name == "template" {
+            let children = self.inner_tokens(idents, scopes);
 
             quote! {
                 #(#children)*
             }
+        } else if self.name == "slot" && !self.slot_props().is_empty() {
+            self.scoped_slot_tokens(idents, scopes)
         } else if self.name == "slot" {
             let name = LitStr::new(&self.slot_name(), Span::call_site());
 
             quote! {
                 self._slots.render(f, #name)?;
             }
-        } else if tag_pieces.len() == 1 && is_reserved_tag(&self.name) {
+        } else if self.name == "component" {
+            let mut view = TokenStream::new();
+
+            // The attribute is checked when parsing
+            let is = self.variable_attr("is").expect(INTERNAL_ERR);
+            is.value.tokenize(&mut view, idents, scopes);
+
+            quote! {
+                ::reign::view::Render::render(&(#view), f)?;
+            }
+        } else if self.is_plain(|id| idents.signatures.fields.contains_key(id)) {
+            // A view added with the name of the custom element should regenerate this one
+            if self.name.contains('-') && !self.foreign {
+                idents.components.push(to_snake_case(&self.name));
+            }
+
             let start_tag = LitStr::new(&format!("<{}", &self.name), Span::call_site());
-            let attrs = self.attrs_tokens(idents, &new_scopes);
-            let children = self.children_tokens(idents, &new_scopes);
+            let attrs = self.attrs_tokens(idents, scopes);
+            let children = self.inner_tokens(idents, scopes);
             let end_tokens = self.end_tokens();
 
             quote! {
@@ -322,10 +760,16 @@ impl Tokenize for Element {
                 #end_tokens
             }
         } else {
+            let id = tag_pieces
+                .iter()
+                .map(|x| to_snake_case(x))
+                .collect::<Vec<_>>()
+                .join(":");
             let path = convert_tag_name(tag_pieces);
-            let attrs = self.component_attrs(idents, &new_scopes);
-            let (names, templates) = self.templates(idents, &new_scopes);
-            let children = self.children_tokens(idents, &new_scopes);
+            let scoped = self.scoped_tokens(&id, &path, idents, scopes);
+            let attrs = self.component_attrs(id, &path, idents, scopes);
+            let (names, templates) = self.templates(idents, scopes);
+            let children = self.inner_tokens(idents, scopes);
 
             quote! {
                 write!(f, "{}", crate::views::#(#path)::* {
@@ -340,22 +784,163 @@ impl Tokenize for Element {
                             #(#children)*
                             Ok(())
                         }),
+                        scoped: #scoped,
                         phantom: ::std::marker::PhantomData,
                     },
                     #(#attrs),*
                 })?;
             }
+        }
+    }
+
+    fn scoped_slot_tokens(&self, idents: &mut ViewFields, scopes: &ViewFields) -> TokenStream {
+        let name = self.slot_name();
+        let field = slot_field(&name);
+        let mut values = vec![];
+        let mut types = vec![];
+
+        for prop in self.slot_props() {
+            let mut ts = TokenStream::new();
+
+            prop.value.tokenize(&mut ts, idents, scopes);
+            values.push(ts);
+            types.push(self.slot_value_type(&name, prop, idents, scopes));
+        }
+
+        if !idents.slots.iter().any(|x| x.0 == name) {
+            idents.slots.push((name, types));
+        }
+
+        quote! {
+            if let Some(slot) = &self._slots.scoped.#field {
+                slot(f, #(#values),*)?;
+            }
+        }
+    }
+
+    /// Type of a value given to a scoped slot, which is taken from the annotation or the
+    /// field. Other values are strings, except for the variables of the template which
+    /// need an annotation since their types are not known.
+    fn slot_value_type(
+        &self,
+        name: &str,
+        prop: &VariableAttribute,
+        idents: &mut ViewFields,
+        scopes: &ViewFields,
+    ) -> TokenStream {
+        let ident = match &prop.value {
+            Code::Expr(Expr::Type(t)) => return t.ty.to_token_stream(),
+            Code::Expr(Expr::Path(p)) => p.path.get_ident(),
+            _ => None,
         };
 
-        elem = if let Some(r_for) = self.control_attr("for") {
-            // For loop
-            let mut for_expr = TokenStream::new();
-            r_for.value.tokenize(&mut for_expr, idents, scopes);
+        match ident {
+            Some(ident) if scopes.contains(ident) => {
+                idents.errors.push((
+                    Some(self.cursor),
+                    format!(
+                        "value `{0}` of the scoped slot `{1}` needs a type, like `:{2}=\"{0}: &Type\"`",
+                        ident, name, prop.name
+                    ),
+                ));
+                quote! { &str }
+            }
+            Some(ident) => match idents.fields.get(ident) {
+                Some(Some(ty)) => ty.clone(),
+                _ => quote! { &str },
+            },
+            None => quote! { &str },
+        }
+    }
 
-            quote! {
-                for #for_expr {
+    fn loop_tokens(
+        &self,
+        for_: &For,
+        elem: TokenStream,
+        idents: &mut ViewFields,
+        scopes: &ViewFields,
+        chained: bool,
+    ) -> TokenStream {
+        let mut pat = TokenStream::new();
+        let mut expr = TokenStream::new();
+
+        for_.pat.tokenize(&mut pat, &mut ViewFields::new(), scopes);
+        for_.expr.tokenize(&mut expr, idents, scopes);
+
+        if for_.meta.is_none() && !chained {
+            return quote! {
+                for #pat in #expr {
                     #elem
                 }
+            };
+        }
+
+        let iter = Ident::new("iter", Span::mixed_site());
+        let index = Ident::new("index", Span::mixed_site());
+
+        let meta = for_.meta.as_ref().map(|meta| {
+            quote! {
+                let #meta = ::reign::view::Loop {
+                    index: #index,
+                    first: #index == 0,
+                    last: #iter.peek().is_none(),
+                };
+            }
+        });
+
+        // The following `!else` or `!else-if` continues this condition
+        let empty = if chained {
+            quote! {
+                if #index != 0 {}
+            }
+        } else {
+            quote! {}
+        };
+
+        quote! {
+            let mut #iter = ::std::iter::IntoIterator::into_iter(#expr).peekable();
+            let mut #index: usize = 0;
+
+            while let Some(#pat) = #iter.next() {
+                #meta
+                #elem
+                #index += 1;
+            }
+
+            #empty
+        }
+    }
+
+    fn tokenize_chained(
+        &self,
+        tokens: &mut TokenStream,
+        idents: &mut ViewFields,
+        scopes: &ViewFields,
+        chained: bool,
+    ) {
+        let mut new_scopes = scopes.clone();
+
+        // Check for loop to see what variables are defined for this loop (`scopes`)
+        if let Some(attr_for) = self.control_attr("for") {
+            if let Code::For(for_) = &attr_for.value {
+                new_scopes.append(for_.declared());
+            }
+        }
+
+        let mut elem = self.body_tokens(idents, &new_scopes);
+        let location = self.location_tokens(idents);
+        // Nothing can be put between the branches of an if
+        let branch = self.control_attr("else-if").is_some() || self.control_attr("else").is_some();
+
+        if branch {
+            elem = quote! { #location #elem };
+        }
+
+        elem = if let Some(r_for) = self.control_attr("for") {
+            // For loop
+            match &r_for.value {
+                Code::For(for_) => self.loop_tokens(for_, elem, idents, scopes, chained),
+                _ => unreachable!("{}", INTERNAL_ERR),
             }
         } else if let Some(r_if) = self.control_attr("if") {
             // If condition
@@ -388,10 +973,21 @@ impl Tokenize for Element {
             elem
         };
 
+        if !branch {
+            elem = quote! { #location #elem };
+        }
+
+        idents.locate(self.cursor);
         tokens.append_all(elem);
     }
 }
 
+impl Tokenize for Element {
+    fn tokenize(&self, tokens: &mut TokenStream, idents: &mut ViewFields, scopes: &ViewFields) {
+        self.tokenize_chained(tokens, idents, scopes, false);
+    }
+}
+
 fn clean_if_else_group(group: Vec<&Node>, has_else: bool, has_else_if: bool) -> Vec<&Node> {
     if has_else {
         // Clean completely
@@ -456,6 +1052,15 @@ fn convert_tag_name(tag: Vec<&str>) -> Vec<Ident> {
     idents
 }
 
-fn is_reserved_tag(tag: &str) -> bool {
+fn mark_foreign(nodes: &mut [Node]) {
+    for node in nodes {
+        if let Node::Element(e) = node {
+            e.foreign = true;
+            mark_foreign(&mut e.children);
+        }
+    }
+}
+
+pub(super) fn is_reserved_tag(tag: &str) -> bool {
     SVG_TAGS.contains(&tag) || HTML_TAGS.contains(&tag)
 }
@@ -1,13 +1,41 @@
-use std::fmt;
+use std::{
+    error, fmt,
+    path::{Path, PathBuf},
+};
 
-// TODO:(view:err) Filename for error
 pub struct Error {
+    pub file: Option<PathBuf>,
     pub content: String,
     pub cursor: usize,
     pub message: String,
 }
 
 impl Error {
+    pub(super) fn new(content: String, cursor: usize, message: &str) -> Self {
+        Error {
+            file: None,
+            content,
+            cursor,
+            message: message.to_string(),
+        }
+    }
+
+    /// Set the path of the view file in which the error occurred.
+    pub fn in_file(mut self, file: &Path) -> Self {
+        self.file = Some(file.to_path_buf());
+        self
+    }
+
+    /// Line number of the error, starting from 1.
+    pub fn line(&self) -> usize {
+        self.get_line().1
+    }
+
+    /// Column number of the error, starting from 1.
+    pub fn column(&self) -> usize {
+        self.get_line().0 + 1
+    }
+
     fn get_line(&self) -> (usize, usize, String) {
         if self.cursor > self.content.len() {
             panic!(
@@ -54,6 +82,19 @@ impl Error {
         };
 
         writeln!(f)?;
+
+        if let Some(file) = &self.file {
+            writeln!(
+                f,
+                "{:>4$} {}:{}:{}",
+                "-->",
+                file.display(),
+                info.1,
+                info.0 + 1,
+                line_number.len() + 3
+            )?;
+        }
+
         writeln!(f, "{}", start)?;
         writeln!(f, "{} | {}", line_number, info.2)?;
         writeln!(
@@ -76,9 +117,18 @@ impl fmt::Debug for Error {
     }
 }
 
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        self.print(f)
+    }
+}
+
+impl error::Error for Error {}
+
 #[cfg(test)]
 mod test {
     use super::super::ParseStream;
+    use std::path::Path;
 
     #[test]
     fn test_fmt_start() {
@@ -213,6 +263,31 @@ mod test {
   |      |
   |      out of bounds when trying to find `!`
   |
+"
+        );
+    }
+
+    #[test]
+    fn test_fmt_file() {
+        let mut ps = ParseStream::new("Hello\nWorld".to_string());
+        ps.step("Hello").unwrap();
+        ps.skip_spaces().unwrap();
+        let err = ps
+            .step("or")
+            .unwrap_err()
+            .in_file(Path::new("views/app.html"));
+
+        assert_eq!((err.line(), err.column()), (2, 1));
+        assert_eq!(
+            format!("{}", err),
+            "
+ --> views/app.html:2:1
+  |
+2 | World
+  | -^^^^^
+  | |
+  | expected `or`
+  |
 "
         );
     }
@@ -0,0 +1,195 @@
+use super::{Expr, Tokenize, ViewFields};
+use proc_macro2::{Delimiter, Spacing, TokenStream, TokenTree};
+use quote::{quote, TokenStreamExt};
+use syn::{
+    parenthesized,
+    parse::{Parse, ParseStream, Result},
+    parse2, parse_str,
+    punctuated::Punctuated,
+    token::{Comma, Paren},
+    Ident,
+};
+
+/// Filters which are provided by the `reign::view::filters` module.
+pub const BUILTIN_FILTERS: [&str; 7] = [
+    "currency",
+    "date",
+    "default",
+    "lower",
+    "pluralize",
+    "truncate",
+    "upper",
+];
+
+/// Filter applied to the value of an interpolation like `date("%Y-%m-%d")`.
+pub struct Filter {
+    pub name: Ident,
+    pub args: Punctuated<Expr, Comma>,
+}
+
+impl Filter {
+    /// Path of the function, which is in the `filters` module of the app unless it is built in.
+    fn path(&self) -> TokenStream {
+        let name = &self.name;
+
+        if BUILTIN_FILTERS.contains(&name.to_string().as_str()) {
+            quote! { ::reign::view::filters::#name }
+        } else {
+            quote! { crate::filters::#name }
+        }
+    }
+}
+
+impl Parse for Filter {
+    fn parse(input: ParseStream) -> Result<Self> {
+        let name = input.parse()?;
+        let args = if input.peek(Paren) {
+            let content;
+
+            parenthesized!(content in input);
+            Punctuated::parse_terminated(&content)?
+        } else {
+            Punctuated::new()
+        };
+
+        Ok(Filter { name, args })
+    }
+}
+
+/// Expression of an interpolation along with the filters applied to it in order.
+pub struct Filtered {
+    pub expr: Expr,
+    pub filters: Vec<Filter>,
+}
+
+impl Filtered {
+    /// Parses the interpolation, where only the names of the built in filters and the given
+    /// filters of the app start a filter so that `is_admin | is_owner` is still a bitwise or.
+    pub fn parse_str(text: &str, filters: &[String]) -> Result<Self> {
+        let mut parts = split(parse_str(text)?, filters).into_iter();
+
+        // There is always at least one part
+        let expr = parse2(parts.next().unwrap_or_default())?;
+        let filters = parts.map(parse2).collect::<Result<_>>()?;
+
+        Ok(Filtered { expr, filters })
+    }
+}
+
+impl Tokenize for Filtered {
+    fn tokenize(&self, tokens: &mut TokenStream, idents: &mut ViewFields, scopes: &ViewFields) {
+        let mut value = TokenStream::new();
+
+        self.expr.tokenize(&mut value, idents, scopes);
+
+        for filter in &self.filters {
+            let path = filter.path();
+            let args: Vec<TokenStream> = filter
+                .args
+                .iter()
+                .map(|x| {
+                    let mut ts = TokenStream::new();
+
+                    x.tokenize(&mut ts, idents, scopes);
+                    ts
+                })
+                .collect();
+
+            value = quote! {
+                #path(&(#value) #(, #args)*)
+            };
+        }
+
+        tokens.append_all(value);
+    }
+}
+
+/// Splits the tokens at the `|` which are not a part of `||` or `|=` and are followed by the
+/// name of a filter.
+fn split(tokens: TokenStream, filters: &[String]) -> Vec<TokenStream> {
+    let tokens: Vec<TokenTree> = tokens.into_iter().collect();
+    let mut parts = vec![TokenStream::new()];
+    let mut joint = false;
+
+    for (i, tt) in tokens.iter().enumerate() {
+        let pipe = match tt {
+            TokenTree::Punct(p) => !joint && p.as_char() == '|' && p.spacing() == Spacing::Alone,
+            _ => false,
+        };
+
+        joint = matches!(tt, TokenTree::Punct(p) if p.spacing() == Spacing::Joint);
+
+        if pipe && is_filter(&tokens[i + 1..], filters) {
+            parts.push(TokenStream::new());
+        } else {
+            parts.last_mut().unwrap().append(tt.clone());
+        }
+    }
+
+    parts
+}
+
+// A filter is a known name which is either the last token or followed by its arguments
+// or the next filter, so that `a | mask(x)` and `a | crate::MASK` stay expressions.
+fn is_filter(rest: &[TokenTree], filters: &[String]) -> bool {
+    let name = match rest.first() {
+        Some(TokenTree::Ident(i)) => i.to_string(),
+        Some(_) => return false,
+        None => return true,
+    };
+
+    let known = BUILTIN_FILTERS.contains(&name.as_str()) || filters.contains(&name);
+
+    known
+        && match rest.get(1) {
+            None => true,
+            Some(TokenTree::Group(g)) => g.delimiter() == Delimiter::Parenthesis,
+            Some(TokenTree::Punct(p)) => p.as_char() == '|',
+            Some(_) => false,
+        }
+}
+
+#[cfg(test)]
+mod test {
+    use super::Filtered;
+
+    fn filters(text: &str, app: &[&str]) -> Vec<String> {
+        let app: Vec<String> = app.iter().map(|x| x.to_string()).collect();
+
+        Filtered::parse_str(text, &app)
+            .unwrap()
+            .filters
+            .iter()
+            .map(|x| x.name.to_string())
+            .collect()
+    }
+
+    #[test]
+    fn test_filtered() {
+        let filtered = Filtered::parse_str("a || b | date(\"%Y\", 1) | upper", &[]).unwrap();
+
+        assert_eq!(filtered.filters.len(), 2);
+        assert_eq!(filtered.filters[0].args.len(), 2);
+        assert_eq!(
+            filters("a || b | date(\"%Y\", 1) | upper", &[]),
+            ["date", "upper"]
+        );
+        assert_eq!(
+            filters("name | shout | upper", &["shout"]),
+            ["shout", "upper"]
+        );
+        assert!(filters("name | shout", &[]).is_empty());
+        assert!(filters("flags | MASK", &[]).is_empty());
+        assert!(filters("flags | crate::MASK", &[]).is_empty());
+        assert!(filters("a | 1", &[]).is_empty());
+        assert!(Filtered::parse_str("a |", &[]).is_err());
+    }
+
+    #[test]
+    fn test_bitwise_or() {
+        assert!(filters("a | b", &[]).is_empty());
+        assert!(filters("is_admin | is_owner", &["shout"]).is_empty());
+        assert!(filters("a | mask(x)", &[]).is_empty());
+        assert_eq!(filters("a | b | upper", &[]), ["upper"]);
+    }
+}
@@ -0,0 +1,615 @@
+use super::{
+    super::{expr::KEYWORDS, pat::Pat, Code, Expr, Filtered},
+    Context, Error, Evaluate,
+};
+use crate::filters;
+use chrono::{DateTime, NaiveDate, NaiveDateTime, NaiveTime};
+use serde_json::{Map, Number, Value};
+use std::cmp::Ordering;
+use syn::{BinOp, Lit, Member, Path, RangeLimits, UnOp};
+
+enum Num {
+    Int(i64),
+    Float(f64),
+}
+
+impl Num {
+    fn from(value: &Value) -> Option<Self> {
+        let number = value.as_number()?;
+
+        number
+            .as_i64()
+            .map(Num::Int)
+            .or_else(|| number.as_f64().map(Num::Float))
+    }
+
+    fn float(&self) -> f64 {
+        match *self {
+            Num::Int(i) => i as f64,
+            Num::Float(f) => f,
+        }
+    }
+}
+
+fn float(f: f64) -> Result<Value, Error> {
+    Number::from_f64(f)
+        .map(Value::Number)
+        .ok_or_else(|| Error::new(format!("`{}` is not a valid number", f)))
+}
+
+fn int(value: &Value) -> Result<i64, Error> {
+    value
+        .as_i64()
+        .ok_or_else(|| Error::new(format!("expected an integer but got `{}`", value)))
+}
+
+fn string(value: &Value) -> Result<&str, Error> {
+    value
+        .as_str()
+        .ok_or_else(|| Error::new(format!("expected a string but got `{}`", value)))
+}
+
+fn array(value: Value) -> Result<Vec<Value>, Error> {
+    match value {
+        Value::Array(items) => Ok(items),
+        value => Err(Error::new(format!("expected a list but got `{}`", value))),
+    }
+}
+
+/// Display a value the way the compiled view would display it.
+pub(super) fn display(value: &Value) -> String {
+    match value {
+        Value::Null => String::new(),
+        Value::String(s) => s.clone(),
+        value => value.to_string(),
+    }
+}
+
+/// Bind the values to the identifiers declared in the pattern which should always match.
+pub(super) fn bind(pat: &Pat, value: &Value, scope: &mut Map<String, Value>) -> Result<(), Error> {
+    if matches(pat, value, scope)? {
+        Ok(())
+    } else {
+        Err(Error::new(format!("pattern does not match `{}`", value)))
+    }
+}
+
+/// Checks whether the value matches the pattern while binding the values to the identifiers
+/// declared in it. Enums are matched the way serde represents them.
+pub(super) fn matches(
+    pat: &Pat,
+    value: &Value,
+    scope: &mut Map<String, Value>,
+) -> Result<bool, Error> {
+    Ok(match pat {
+        // Capitalized identifiers are unit variants like in Rust
+        Pat::Ident(p) if p.ident.to_string().starts_with(char::is_uppercase) => {
+            variant(&p.ident.to_string(), value)
+        }
+        Pat::Ident(p) => {
+            scope.insert(p.ident.to_string(), value.clone());
+            true
+        }
+        Pat::Lit(p) => compare(&literal(p)?, value) == Some(Ordering::Equal),
+        Pat::Path(p) => variant(&path_name(p), value),
+        Pat::Reference(p) => matches(&p.pat, value, scope)?,
+        Pat::Rest(_) | Pat::Wild(_) => true,
+        Pat::Tuple(p) => matches_tuple(p.elems.iter().collect(), value, scope)?,
+        Pat::TupleStruct(p) => {
+            let name = path_name(&p.path);
+
+            let inner = match (name.as_str(), value) {
+                ("Some", Value::Null) => return Ok(false),
+                ("Some" | "Ok", value) => value,
+                (_, Value::Object(map)) if map.len() == 1 && map.contains_key(&name) => &map[&name],
+                _ => return Ok(false),
+            };
+
+            if p.pat.elems.len() == 1 {
+                matches(&p.pat.elems[0], inner, scope)?
+            } else {
+                matches_tuple(p.pat.elems.iter().collect(), inner, scope)?
+            }
+        }
+        Pat::Struct(p) => {
+            let name = path_name(&p.path);
+
+            let map = match value {
+                // Struct variants are wrapped in their name
+                Value::Object(map) if map.len() == 1 && is_variant(map.keys().next()) => {
+                    match map.get(&name) {
+                        Some(Value::Object(inner)) => inner,
+                        _ => return Ok(false),
+                    }
+                }
+                Value::Object(map) => map,
+                _ => return Ok(false),
+            };
+
+            for field in &p.fields {
+                let name = member(&field.member);
+                let value = map
+                    .get(&name)
+                    .ok_or_else(|| Error::new(format!("no field `{}` in struct", name)))?;
+
+                if !matches(&field.pat, value, scope)? {
+                    return Ok(false);
+                }
+            }
+
+            true
+        }
+    })
+}
+
+fn matches_tuple(
+    pats: Vec<&Pat>,
+    value: &Value,
+    scope: &mut Map<String, Value>,
+) -> Result<bool, Error> {
+    let items = match value {
+        Value::Array(items) => items,
+        value => return Err(Error::new(format!("expected a list but got `{}`", value))),
+    };
+
+    let rest = pats.iter().position(|x| matches!(x, Pat::Rest(_)));
+
+    let (before, after) = match rest {
+        Some(i) => (&pats[..i], &pats[i + 1..]),
+        None => (&pats[..], &pats[pats.len()..]),
+    };
+
+    if items.len() < before.len() + after.len() || rest.is_none() && items.len() != before.len() {
+        return Err(Error::new(format!(
+            "expected a tuple of {} elements but got {}",
+            pats.len(),
+            items.len()
+        )));
+    }
+
+    let skip = items.len() - after.len();
+
+    for (i, item) in items.iter().enumerate() {
+        let matched = if i < before.len() {
+            matches(before[i], item, scope)?
+        } else if i >= skip {
+            matches(after[i - skip], item, scope)?
+        } else {
+            true
+        };
+
+        if !matched {
+            return Ok(false);
+        }
+    }
+
+    Ok(true)
+}
+
+fn path_name(path: &Path) -> String {
+    path.segments
+        .last()
+        .map(|x| x.ident.to_string())
+        .unwrap_or_default()
+}
+
+fn is_variant(key: Option<&String>) -> bool {
+    key.map_or(false, |x| x.starts_with(char::is_uppercase))
+}
+
+/// Whether the value is the unit variant with the given name.
+fn variant(name: &str, value: &Value) -> bool {
+    match value {
+        Value::Null => name == "None",
+        Value::String(s) => s == name,
+        _ => false,
+    }
+}
+
+impl Evaluate for Code {
+    fn evaluate(&self, ctx: &Context) -> Result<Value, Error> {
+        match self {
+            Code::Expr(e) => e.evaluate(ctx),
+            Code::For(_) => Err(Error::new("expected an expression but got a loop")),
+            Code::Pat(_) => Err(Error::new("expected an expression but got a pattern")),
+            Code::Let(_) => Err(Error::new("expected an expression but got a binding")),
+            Code::Filtered(f) => f.evaluate(ctx),
+        }
+    }
+}
+
+impl Evaluate for Filtered {
+    fn evaluate(&self, ctx: &Context) -> Result<Value, Error> {
+        let mut value = self.expr.evaluate(ctx)?;
+
+        for f in &self.filters {
+            let args = f
+                .args
+                .iter()
+                .map(|x| x.evaluate(ctx))
+                .collect::<Result<Vec<_>, _>>()?;
+
+            value = filter(value, &f.name.to_string(), args)?;
+        }
+
+        Ok(value)
+    }
+}
+
+impl Evaluate for Expr {
+    fn evaluate(&self, ctx: &Context) -> Result<Value, Error> {
+        match self {
+            Expr::Array(e) => Ok(Value::Array(
+                e.elems
+                    .iter()
+                    .map(|x| x.evaluate(ctx))
+                    .collect::<Result<_, _>>()?,
+            )),
+            Expr::Binary(e) => binary(ctx, &e.left, &e.op, &e.right),
+            Expr::Call(e) => {
+                let name = match &*e.func {
+                    Expr::Path(path) => path.path.get_ident().map(|x| x.to_string()),
+                    _ => None,
+                };
+
+                match name.as_deref() {
+                    Some("Some" | "Ok") if e.args.len() == 1 => e.args[0].evaluate(ctx),
+                    _ => Err(
+                        Error::new("only `Some(..)` and `Ok(..)` calls are supported").compiled(),
+                    ),
+                }
+            }
+            Expr::Cast(e) => e.expr.evaluate(ctx),
+            Expr::Field(e) => {
+                let base = e.base.evaluate(ctx)?;
+
+                let value = match &e.member {
+                    Member::Named(ident) => base.get(ident.to_string()),
+                    Member::Unnamed(index) => base.get(index.index as usize),
+                };
+
+                value.cloned().ok_or_else(|| {
+                    Error::new(format!("no field `{}` on `{}`", member(&e.member), base))
+                })
+            }
+            Expr::Group(e) => e.expr.evaluate(ctx),
+            Expr::Index(e) => {
+                let base = e.expr.evaluate(ctx)?;
+                let index = e.index.evaluate(ctx)?;
+
+                let value = match &index {
+                    Value::String(key) => base.get(key),
+                    index => base.get(int(index)? as usize),
+                };
+
+                value.cloned().ok_or_else(|| {
+                    Error::new(format!("index `{}` out of bounds on `{}`", index, base))
+                })
+            }
+            Expr::MethodCall(e) => {
+                let receiver = e.receiver.evaluate(ctx)?;
+                let args = e
+                    .args
+                    .iter()
+                    .map(|x| x.evaluate(ctx))
+                    .collect::<Result<Vec<_>, _>>()?;
+
+                method(receiver, &e.method.to_string(), args)
+            }
+            Expr::Paren(e) => e.expr.evaluate(ctx),
+            Expr::Path(path) => {
+                let ident = path
+                    .path
+                    .get_ident()
+                    .ok_or_else(|| {
+                        Error::new("only field names are supported as paths").compiled()
+                    })?
+                    .to_string();
+
+                if let Some(value) = ctx.lookup(&ident) {
+                    Ok(value.clone())
+                } else if ident == "None" {
+                    Ok(Value::Null)
+                } else if KEYWORDS.contains(&ident.as_str()) {
+                    Err(Error::new(format!("`{}` needs to be called", ident)))
+                } else if ctx.defaults {
+                    Ok(Value::Null)
+                } else {
+                    Err(Error::new(format!("unknown field `{}`", ident)))
+                }
+            }
+            Expr::Range(e) => {
+                let from = match &e.from {
+                    Some(from) => int(&from.evaluate(ctx)?)?,
+                    None => 0,
+                };
+                let to = match &e.to {
+                    Some(to) => int(&to.evaluate(ctx)?)?,
+                    None => return Err(Error::new("ranges need to have an end")),
+                };
+
+                let range: Vec<Value> = match e.limits {
+                    RangeLimits::HalfOpen(_) => (from..to).map(Value::from).collect(),
+                    RangeLimits::Closed(_) => (from..=to).map(Value::from).collect(),
+                };
+
+                Ok(Value::Array(range))
+            }
+            Expr::Reference(e) => e.expr.evaluate(ctx),
+            Expr::Repeat(e) => {
+                let value = e.expr.evaluate(ctx)?;
+                let len = int(&e.len.evaluate(ctx)?)?;
+
+                Ok(Value::Array(vec![value; len.max(0) as usize]))
+            }
+            Expr::Tuple(e) => Ok(Value::Array(
+                e.elems
+                    .iter()
+                    .map(|x| x.evaluate(ctx))
+                    .collect::<Result<_, _>>()?,
+            )),
+            Expr::Type(e) => e.expr.evaluate(ctx),
+            Expr::Unary(e) => {
+                let value = e.expr.evaluate(ctx)?;
+
+                match (&e.op, &value) {
+                    (UnOp::Deref(_), _) => Ok(value),
+                    (UnOp::Not(_), Value::Bool(b)) => Ok(Value::Bool(!b)),
+                    (UnOp::Not(_), _) => Ok(Value::from(!int(&value)?)),
+                    (UnOp::Neg(_), _) => match Num::from(&value) {
+                        Some(Num::Int(i)) => Ok(Value::from(-i)),
+                        Some(Num::Float(f)) => float(-f),
+                        None => Err(Error::new(format!("cannot negate `{}`", value))),
+                    },
+                }
+            }
+            Expr::Lit(lit) => literal(lit),
+            Expr::Struct(_) => Err(Error::new("struct expressions are not supported").compiled()),
+            Expr::Macro(_) => Err(Error::new("macros are not supported").compiled()),
+        }
+    }
+}
+
+fn member(member: &Member) -> String {
+    match member {
+        Member::Named(ident) => ident.to_string(),
+        Member::Unnamed(index) => index.index.to_string(),
+    }
+}
+
+fn literal(lit: &Lit) -> Result<Value, Error> {
+    let invalid = |e: syn::Error| Error::new(format!("invalid literal: {}", e));
+
+    Ok(match lit {
+        Lit::Str(s) => Value::String(s.value()),
+        Lit::Char(c) => Value::String(c.value().to_string()),
+        Lit::Bool(b) => Value::Bool(b.value),
+        Lit::Int(i) => Value::from(i.base10_parse::<i64>().map_err(invalid)?),
+        Lit::Float(f) => float(f.base10_parse::<f64>().map_err(invalid)?)?,
+        _ => return Err(Error::new("unsupported literal")),
+    })
+}
+
+fn compare(left: &Value, right: &Value) -> Option<Ordering> {
+    match (Num::from(left), Num::from(right)) {
+        (Some(Num::Int(l)), Some(Num::Int(r))) => Some(l.cmp(&r)),
+        (Some(l), Some(r)) => l.float().partial_cmp(&r.float()),
+        _ => match (left, right) {
+            (Value::String(l), Value::String(r)) => Some(l.cmp(r)),
+            (Value::Bool(l), Value::Bool(r)) => Some(l.cmp(r)),
+            _ if left == right => Some(Ordering::Equal),
+            _ => None,
+        },
+    }
+}
+
+fn binary(ctx: &Context, left: &Expr, op: &BinOp, right: &Expr) -> Result<Value, Error> {
+    let left = left.evaluate(ctx)?;
+
+    // Short circuit the boolean operators
+    if let BinOp::And(_) | BinOp::Or(_) = op {
+        let l = left
+            .as_bool()
+            .ok_or_else(|| Error::new(format!("expected a boolean but got `{}`", left)))?;
+
+        if matches!(op, BinOp::And(_)) != l {
+            return Ok(Value::Bool(l));
+        }
+
+        let right = right.evaluate(ctx)?;
+
+        return right
+            .as_bool()
+            .map(Value::Bool)
+            .ok_or_else(|| Error::new(format!("expected a boolean but got `{}`", right)));
+    }
+
+    let right = right.evaluate(ctx)?;
+    let ordering = compare(&left, &right);
+
+    let result = match op {
+        BinOp::Eq(_) => Some(Value::Bool(ordering == Some(Ordering::Equal))),
+        BinOp::Ne(_) => Some(Value::Bool(ordering != Some(Ordering::Equal))),
+        BinOp::Lt(_) => ordering.map(|x| Value::Bool(x == Ordering::Less)),
+        BinOp::Le(_) => ordering.map(|x| Value::Bool(x != Ordering::Greater)),
+        BinOp::Gt(_) => ordering.map(|x| Value::Bool(x == Ordering::Greater)),
+        BinOp::Ge(_) => ordering.map(|x| Value::Bool(x != Ordering::Less)),
+        BinOp::Add(_) if left.is_string() => Some(Value::String(format!(
+            "{}{}",
+            display(&left),
+            string(&right)?
+        ))),
+        _ => match (Num::from(&left), Num::from(&right)) {
+            (Some(Num::Int(l)), Some(Num::Int(r))) => match op {
+                BinOp::Add(_) => l.checked_add(r),
+                BinOp::Sub(_) => l.checked_sub(r),
+                BinOp::Mul(_) => l.checked_mul(r),
+                BinOp::Div(_) => l.checked_div(r),
+                BinOp::Rem(_) => l.checked_rem(r),
+                BinOp::BitAnd(_) => Some(l & r),
+                BinOp::BitOr(_) => Some(l | r),
+                BinOp::BitXor(_) => Some(l ^ r),
+                BinOp::Shl(_) => u32::try_from(r).ok().and_then(|r| l.checked_shl(r)),
+                BinOp::Shr(_) => u32::try_from(r).ok().and_then(|r| l.checked_shr(r)),
+                _ => None,
+            }
+            .map(Value::from),
+            (Some(l), Some(r)) => {
+                let (l, r) = (l.float(), r.float());
+
+                match op {
+                    BinOp::Add(_) => Some(float(l + r)?),
+                    BinOp::Sub(_) => Some(float(l - r)?),
+                    BinOp::Mul(_) => Some(float(l * r)?),
+                    BinOp::Div(_) => Some(float(l / r)?),
+                    BinOp::Rem(_) => Some(float(l % r)?),
+                    _ => None,
+                }
+            }
+            _ => match (op, &left, &right) {
+                (BinOp::BitAnd(_), Value::Bool(l), Value::Bool(r)) => Some(Value::Bool(*l & *r)),
+                (BinOp::BitOr(_), Value::Bool(l), Value::Bool(r)) => Some(Value::Bool(*l | *r)),
+                (BinOp::BitXor(_), Value::Bool(l), Value::Bool(r)) => Some(Value::Bool(*l ^ *r)),
+                _ => None,
+            },
+        },
+    };
+
+    result.ok_or_else(|| {
+        Error::new(format!(
+            "unable to apply binary operation on `{}` and `{}`",
+            left, right
+        ))
+    })
+}
+
+fn method(receiver: Value, name: &str, args: Vec<Value>) -> Result<Value, Error> {
+    // Methods of the types of the app and the ones not known here need the compiled view
+    let unsupported =
+        || Error::new(format!("unsupported method `{}` on `{}`", name, receiver)).compiled();
+    let arg = |i: usize| {
+        args.get(i)
+            .ok_or_else(|| Error::new(format!("method `{}` expects more arguments", name)))
+    };
+
+    Ok(match (name, &receiver) {
+        (
+            "as_ref" | "as_str" | "as_slice" | "borrow" | "clone" | "deref" | "into_iter" | "iter"
+            | "to_owned" | "to_vec",
+            _,
+        ) => receiver,
+        ("to_string", _) => Value::String(display(&receiver)),
+        ("len", Value::String(s)) => Value::from(s.len()),
+        ("len", Value::Array(items)) => Value::from(items.len()),
+        ("len", Value::Object(map)) => Value::from(map.len()),
+        ("is_empty", Value::String(s)) => Value::Bool(s.is_empty()),
+        ("is_empty", Value::Array(items)) => Value::Bool(items.is_empty()),
+        ("is_empty", Value::Object(map)) => Value::Bool(map.is_empty()),
+        ("is_some", _) => Value::Bool(!receiver.is_null()),
+        ("is_none", _) => Value::Bool(receiver.is_null()),
+        ("unwrap" | "expect", Value::Null) => {
+            return Err(Error::new(format!("called `{}` on a `None` value", name)))
+        }
+        ("unwrap" | "expect", _) => receiver,
+        ("unwrap_or", Value::Null) => arg(0)?.clone(),
+        ("unwrap_or", _) => receiver,
+        ("to_uppercase", Value::String(s)) => Value::String(s.to_uppercase()),
+        ("to_lowercase", Value::String(s)) => Value::String(s.to_lowercase()),
+        ("trim", Value::String(s)) => Value::String(s.trim().to_string()),
+        ("trim_start", Value::String(s)) => Value::String(s.trim_start().to_string()),
+        ("trim_end", Value::String(s)) => Value::String(s.trim_end().to_string()),
+        ("contains", Value::String(s)) => Value::Bool(s.contains(string(arg(0)?)?)),
+        ("contains", Value::Array(items)) => Value::Bool(items.contains(arg(0)?)),
+        ("contains_key", Value::Object(map)) => Value::Bool(map.contains_key(string(arg(0)?)?)),
+        ("starts_with", Value::String(s)) => Value::Bool(s.starts_with(string(arg(0)?)?)),
+        ("ends_with", Value::String(s)) => Value::Bool(s.ends_with(string(arg(0)?)?)),
+        ("chars", Value::String(s)) => {
+            Value::Array(s.chars().map(|x| Value::String(x.to_string())).collect())
+        }
+        ("join", Value::Array(items)) => Value::String(
+            items
+                .iter()
+                .map(display)
+                .collect::<Vec<_>>()
+                .join(string(arg(0)?)?),
+        ),
+        ("enumerate", Value::Array(_)) => Value::Array(
+            array(receiver)?
+                .into_iter()
+                .enumerate()
+                .map(|(i, x)| Value::Array(vec![Value::from(i), x]))
+                .collect(),
+        ),
+        ("rev", Value::Array(_)) => {
+            let mut items = array(receiver)?;
+
+            items.reverse();
+            Value::Array(items)
+        }
+        ("first", Value::Array(items)) => items.first().cloned().unwrap_or_default(),
+        ("last", Value::Array(items)) => items.last().cloned().unwrap_or_default(),
+        ("get", Value::Array(items)) => items
+            .get(int(arg(0)?)? as usize)
+            .cloned()
+            .unwrap_or_default(),
+        ("get", Value::Object(map)) => map.get(string(arg(0)?)?).cloned().unwrap_or_default(),
+        ("keys", Value::Object(map)) => {
+            Value::Array(map.keys().map(|x| Value::String(x.clone())).collect())
+        }
+        ("values", Value::Object(map)) => Value::Array(map.values().cloned().collect()),
+        _ => return Err(unsupported()),
+    })
+}
+
+/// Applies the built in filter, since the ones of the app can't be called when interpreting.
+fn filter(value: Value, name: &str, args: Vec<Value>) -> Result<Value, Error> {
+    let arg = |i: usize| {
+        args.get(i)
+            .ok_or_else(|| Error::new(format!("filter `{}` expects more arguments", name)))
+    };
+
+    Ok(Value::String(match name {
+        "upper" => filters::upper(&display(&value)),
+        "lower" => filters::lower(&display(&value)),
+        "truncate" => filters::truncate(&display(&value), int(arg(0)?)? as usize),
+        "default" => match &value {
+            Value::Null => display(arg(0)?),
+            Value::String(s) if s.is_empty() => display(arg(0)?),
+            _ => display(&value),
+        },
+        "date" => date(&value, string(arg(0)?)?)?,
+        "pluralize" => filters::pluralize(&display(&value), string(arg(0)?)?, string(arg(1)?)?),
+        "currency" => match value.as_f64() {
+            Some(n) => filters::currency(&n, string(arg(0)?)?),
+            None => return Err(Error::new(format!("expected a number but got `{}`", value))),
+        },
+        _ => {
+            return Err(Error::new(format!(
+                "unknown filter `{}`, only the built in filters are available when hot reloading",
+                name
+            ))
+            .compiled())
+        }
+    }))
+}
+
+/// Formats the date from the way it is serialized by `chrono`.
+fn date(value: &Value, format: &str) -> Result<String, Error> {
+    if value.is_null() {
+        return Ok(String::new());
+    }
+
+    let text = string(value)?;
+
+    if let Ok(date) = DateTime::parse_from_rfc3339(text) {
+        Ok(filters::date(&date, format))
+    } else if let Ok(date) = text.parse::<NaiveDateTime>() {
+        Ok(filters::date(&date, format))
+    } else if let Ok(date) = text.parse::<NaiveDate>() {
+        Ok(filters::date(&date, format))
+    } else if let Ok(time) = text.parse::<NaiveTime>() {
+        Ok(filters::date(&time, format))
+    } else {
+        Err(Error::new(format!("expected a date but got `{}`", value)))
+    }
+}
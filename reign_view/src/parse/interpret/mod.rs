@@ -0,0 +1,976 @@
+//! Interpreter for rendering views from their templates at runtime.
+//!
+//! This is used by the `hot-reload` feature so that changes to the templates are visible
+//! without recompiling the application. The values given to the views are converted into
+//! [`serde_json::Value`] and the expressions in the templates are evaluated against them.
+
+use super::{parse, Code, Expr, Node, Prop};
+use inflector::cases::snakecase::to_snake_case;
+use quote::ToTokens;
+use serde::Serialize;
+use serde_json::{json, to_value, Map, Value};
+use std::{
+    borrow::Cow,
+    collections::HashMap,
+    fmt::{self, Display, Formatter},
+    fs::read_to_string,
+    path::{Path, PathBuf},
+    rc::Rc,
+};
+use syn::{parse2, Type};
+
+mod expr;
+mod node;
+
+use expr::bind;
+
+trait Interpret {
+    fn interpret(&self, out: &mut String, ctx: &mut Context) -> Result<(), Error>;
+}
+
+trait Evaluate {
+    fn evaluate(&self, ctx: &Context) -> Result<Value, Error>;
+}
+
+/// Error that occurred while interpreting a view.
+#[derive(Debug)]
+pub struct Error {
+    file: Option<PathBuf>,
+    message: String,
+    /// Whether only the compiled view can render the template
+    compiled: bool,
+}
+
+impl Error {
+    fn new<S>(message: S) -> Self
+    where
+        S: Into<String>,
+    {
+        Self {
+            file: None,
+            message: message.into(),
+            compiled: false,
+        }
+    }
+
+    fn compiled(mut self) -> Self {
+        self.compiled = true;
+        self
+    }
+
+    fn in_file(mut self, file: &Path) -> Self {
+        if self.file.is_none() {
+            self.file = Some(file.to_path_buf());
+        }
+
+        self
+    }
+}
+
+impl Display for Error {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        if let Some(file) = &self.file {
+            write!(f, "{}: ", file.display())?;
+        }
+
+        write!(f, "{}", self.message)
+    }
+}
+
+/// Template given by the parent for a scoped slot which is rendered with the values of the slot.
+type Scoped<'a> = Rc<dyn Fn(&[Value], &mut String) -> Result<(), Error> + 'a>;
+
+/// Directory of the views along with the names of the filters of the app.
+#[derive(Clone, Copy)]
+struct Templates<'a> {
+    dir: &'a Path,
+    filters: &'a [String],
+}
+
+impl Templates<'_> {
+    fn read(&self, path: &[String]) -> Result<(PathBuf, Option<Vec<Prop>>, Node), Error> {
+        let file = file(self.dir, path);
+        let content = read_to_string(&file)
+            .map_err(|e| Error::new(format!("unable to read the view: {}", e)).in_file(&file))?
+            .replace("\r\n", "\n");
+
+        match parse(content, self.filters) {
+            Ok((props, node)) => Ok((file, props, node)),
+            Err(e) => Err(Error::new(format!("{:?}", e)).in_file(&file)),
+        }
+    }
+}
+
+struct Context<'a> {
+    templates: Templates<'a>,
+    fields: &'a Map<String, Value>,
+    scopes: Vec<Map<String, Value>>,
+    slots: HashMap<String, String>,
+    scoped: HashMap<String, Scoped<'a>>,
+    /// Whether the missing fields are defaulted, which is the case for components
+    defaults: bool,
+}
+
+impl<'a> Context<'a> {
+    /// Context with the same values for rendering the templates given to the scoped slots.
+    fn fork(&self) -> Context<'a> {
+        Context {
+            templates: self.templates,
+            fields: self.fields,
+            scopes: self.scopes.clone(),
+            slots: self.slots.clone(),
+            scoped: self.scoped.clone(),
+            defaults: self.defaults,
+        }
+    }
+
+    fn lookup(&self, name: &str) -> Option<&Value> {
+        self.scopes
+            .iter()
+            .rev()
+            .find_map(|scope| scope.get(name))
+            .or_else(|| self.fields.get(name))
+    }
+
+    fn condition(&self, code: &Code) -> Result<bool, Error> {
+        match code.evaluate(self)? {
+            Value::Bool(b) => Ok(b),
+            // Defaulted fields are null
+            Value::Null if self.defaults => Ok(false),
+            value => Err(Error::new(format!(
+                "expected a boolean condition but got `{}`",
+                value
+            ))),
+        }
+    }
+
+    /// Renders the loop and returns whether it had any items.
+    fn iterate<F>(&mut self, code: &Code, mut f: F) -> Result<bool, Error>
+    where
+        F: FnMut(&mut Self) -> Result<(), Error>,
+    {
+        let for_ = match code {
+            Code::For(for_) => for_,
+            _ => return Err(Error::new("expected a `pat in expr` loop")),
+        };
+
+        let items = match for_.expr.evaluate(self)? {
+            Value::Array(items) => items,
+            Value::Object(map) => map
+                .into_iter()
+                .map(|(k, v)| Value::Array(vec![Value::String(k), v]))
+                .collect(),
+            value => return Err(Error::new(format!("cannot iterate over `{}`", value))),
+        };
+
+        let len = items.len();
+
+        for (index, item) in items.iter().enumerate() {
+            let mut scope = Map::new();
+
+            bind(&for_.pat, item, &mut scope)?;
+
+            if let Some(meta) = &for_.meta {
+                scope.insert(
+                    meta.to_string(),
+                    json!({
+                        "index": index,
+                        "first": index == 0,
+                        "last": index + 1 == len,
+                    }),
+                );
+            }
+
+            self.scopes.push(scope);
+            let result = f(self);
+            self.scopes.pop();
+
+            result?;
+        }
+
+        Ok(len != 0)
+    }
+
+    fn component(
+        &self,
+        path: &[String],
+        fields: &Map<String, Value>,
+        slots: HashMap<String, String>,
+        scoped: HashMap<String, Scoped>,
+        out: &mut String,
+    ) -> Result<(), Error> {
+        let path: Vec<String> = path.iter().map(|x| to_snake_case(x)).collect();
+
+        render(self.templates, &path, fields, slots, scoped, true, out)
+    }
+}
+
+fn file(dir: &Path, path: &[String]) -> PathBuf {
+    let mut file = dir.to_path_buf();
+
+    for part in path {
+        file.push(part);
+    }
+
+    file.set_extension("html");
+    file
+}
+
+fn render(
+    templates: Templates,
+    path: &[String],
+    fields: &Map<String, Value>,
+    slots: HashMap<String, String>,
+    scoped: HashMap<String, Scoped>,
+    defaults: bool,
+    out: &mut String,
+) -> Result<(), Error> {
+    let (file, props, node) = templates.read(path)?;
+
+    let fields = match (props, defaults) {
+        (Some(props), true) => {
+            Cow::Owned(component_props(templates, &props, fields).map_err(|e| e.in_file(&file))?)
+        }
+        _ => Cow::Borrowed(fields),
+    };
+
+    let mut ctx = Context {
+        templates,
+        fields: &fields,
+        scopes: vec![],
+        slots,
+        scoped,
+        defaults,
+    };
+
+    node.interpret(out, &mut ctx).map_err(|e| e.in_file(&file))
+}
+
+/// Checks the fields given to a component against its props and adds the defaults of the
+/// props which are not given.
+fn component_props(
+    templates: Templates,
+    props: &[Prop],
+    given: &Map<String, Value>,
+) -> Result<Map<String, Value>, Error> {
+    for name in given.keys() {
+        if !props.iter().any(|x| x.name == name) {
+            return Err(Error::new(format!("component has no prop `{}`", name)));
+        }
+    }
+
+    let mut fields = given.clone();
+    let empty = Map::new();
+
+    let ctx = Context {
+        templates,
+        fields: &empty,
+        scopes: vec![],
+        slots: HashMap::new(),
+        scoped: HashMap::new(),
+        defaults: false,
+    };
+
+    for prop in props {
+        let name = prop.name.to_string();
+
+        if fields.contains_key(&name) {
+            continue;
+        }
+
+        let value = match &prop.default {
+            Some(default) => parse2::<Expr>(default.to_token_stream())
+                .map_err(|e| Error::new(format!("unsupported default of prop `{}`: {}", name, e)))?
+                .evaluate(&ctx)?,
+            None if prop.optional => default_value(&prop.ty),
+            None => return Err(Error::new(format!("component needs the prop `{}`", name))),
+        };
+
+        fields.insert(name, value);
+    }
+
+    Ok(fields)
+}
+
+/// Default value of the type as far as it can be known from its name.
+fn default_value(ty: &Type) -> Value {
+    let name = match ty {
+        Type::Reference(r) => return default_value(&r.elem),
+        Type::Paren(p) => return default_value(&p.elem),
+        Type::Slice(_) | Type::Array(_) => return Value::Array(vec![]),
+        Type::Path(p) => p.path.segments.last().map(|x| x.ident.to_string()),
+        _ => None,
+    };
+
+    match name.as_deref() {
+        Some(
+            "u8" | "u16" | "u32" | "u64" | "u128" | "usize" | "i8" | "i16" | "i32" | "i64" | "i128"
+            | "isize",
+        ) => Value::from(0),
+        Some("f32" | "f64") => Value::from(0.0),
+        Some("bool") => Value::Bool(false),
+        Some("str" | "String") => Value::String(String::new()),
+        Some("Vec" | "VecDeque" | "HashSet" | "BTreeSet") => Value::Array(vec![]),
+        Some("HashMap" | "BTreeMap") => Value::Object(Map::new()),
+        _ => Value::Null,
+    }
+}
+
+/// Renders the view as the default slot along with the named templates in its root template
+/// as the other slots.
+fn sections(
+    templates: Templates,
+    path: &[String],
+    fields: &Map<String, Value>,
+) -> Result<HashMap<String, String>, Error> {
+    let (file, _, node) = templates.read(path)?;
+
+    let mut ctx = Context {
+        templates,
+        fields,
+        scopes: vec![],
+        slots: HashMap::new(),
+        scoped: HashMap::new(),
+        defaults: false,
+    };
+
+    let mut slots = HashMap::new();
+    let mut default = String::new();
+
+    node.interpret(&mut default, &mut ctx)
+        .map_err(|e| e.in_file(&file))?;
+
+    if let Node::Element(e) = &node {
+        if e.name == "template" {
+            for child in &e.children {
+                if let Node::Element(section) = child {
+                    if section.slot_args().is_some() {
+                        continue;
+                    }
+
+                    if let Some(name) = section.template_name() {
+                        let mut slot = String::new();
+
+                        child
+                            .interpret(&mut slot, &mut ctx)
+                            .map_err(|e| e.in_file(&file))?;
+                        slots.insert(name[1..].to_string(), slot);
+                    }
+                }
+            }
+        }
+    }
+
+    slots.insert("default".to_string(), default);
+    Ok(slots)
+}
+
+/// View that is interpreted from its template every time it is rendered.
+///
+/// This is what the `render!` macro builds in debug mode when the `hot-reload` feature is
+/// enabled. The values of the fields should implement [`serde::Serialize`].
+///
+/// Any error that occurs while rendering is displayed instead of the view. Templates which
+/// use the filters or methods of the application can't be interpreted, see
+/// [`View::interpret`]. The filters of the application still need to be given with
+/// [`View::filters`] so that the template is read the same way as the compiled view.
+///
+/// # Examples
+///
+/// ```no_run
+/// use reign::view::interpret::View;
+///
+/// let html = View::new("src/views", "pages:about")
+///     .field("name", "John")
+///     .field("age", &28)
+///     .to_string();
+/// ```
+pub struct View {
+    dir: PathBuf,
+    id: Vec<String>,
+    layout: Option<Vec<String>>,
+    filters: Vec<String>,
+    fields: Map<String, Value>,
+    error: Option<Error>,
+}
+
+impl View {
+    /// Create a view for the template with the given id in the given views directory.
+    ///
+    /// The id is the path of the template relative to the directory joined with `:`.
+    pub fn new<P>(dir: P, id: &str) -> Self
+    where
+        P: Into<PathBuf>,
+    {
+        Self {
+            dir: dir.into(),
+            id: id.split(':').map(String::from).collect(),
+            layout: None,
+            filters: vec![],
+            fields: Map::new(),
+            error: None,
+        }
+    }
+
+    /// Wrap the view in the layout with the given id.
+    ///
+    /// The fields are shared with the layout. The view is given to the layout as the default
+    /// slot and the named templates in the root template of the view as the named slots.
+    #[inline]
+    pub fn layout(mut self, id: &str) -> Self {
+        self.layout = Some(id.split(':').map(String::from).collect());
+        self
+    }
+
+    /// Set the names of the filters in the `filters` module of the application.
+    ///
+    /// A `|` followed by anything other than a filter is a bitwise or.
+    #[inline]
+    pub fn filters(mut self, names: &[&str]) -> Self {
+        self.filters = names.iter().map(|x| x.to_string()).collect();
+        self
+    }
+
+    /// Set the value of a field used in the template.
+    #[inline]
+    pub fn field<T>(mut self, name: &str, value: &T) -> Self
+    where
+        T: Serialize + ?Sized,
+    {
+        match to_value(value) {
+            Ok(value) => {
+                self.fields.insert(name.to_string(), value);
+            }
+            Err(e) => {
+                self.error = Some(Error::new(format!(
+                    "unable to serialize field `{}`: {}",
+                    name, e
+                )));
+            }
+        }
+
+        self
+    }
+
+    /// Render the view into a string.
+    pub fn render(&self) -> Result<String, Error> {
+        if let Some(error) = &self.error {
+            return Err(Error::new(error.message.clone()));
+        }
+
+        let mut out = String::new();
+        let templates = Templates {
+            dir: &self.dir,
+            filters: &self.filters,
+        };
+
+        if let Some(layout) = &self.layout {
+            let slots = sections(templates, &self.id, &self.fields)?;
+
+            render(
+                templates,
+                layout,
+                &self.fields,
+                slots,
+                HashMap::new(),
+                false,
+                &mut out,
+            )?;
+        } else {
+            render(
+                templates,
+                &self.id,
+                &self.fields,
+                HashMap::new(),
+                HashMap::new(),
+                false,
+                &mut out,
+            )?;
+        }
+
+        Ok(out)
+    }
+
+    /// Render the view, or return `None` if the template needs the compiled view.
+    ///
+    /// The interpreter only knows the built in filters and some common methods, so the
+    /// `render!` macro falls back to the compiled view when the template uses anything else,
+    /// like a filter or a method of the application.
+    pub fn interpret(self) -> Option<Interpreted> {
+        match self.render() {
+            Err(e) if e.compiled => None,
+            result => Some(Interpreted {
+                id: self.id.join(":"),
+                result,
+            }),
+        }
+    }
+}
+
+impl Display for View {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        let interpreted = Interpreted {
+            id: self.id.join(":"),
+            result: self.render(),
+        };
+
+        write!(f, "{}", interpreted)
+    }
+}
+
+/// Result of interpreting a [`View`], which displays the error instead of the view.
+pub struct Interpreted {
+    id: String,
+    result: Result<String, Error>,
+}
+
+impl Display for Interpreted {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        match &self.result {
+            Ok(out) => write!(f, "{}", out),
+            Err(e) => write!(
+                f,
+                "<pre>Unable to render view `{}`\n\n{}</pre>",
+                self.id,
+                e.to_string()
+                    .replace('&', "&amp;")
+                    .replace('<', "&lt;")
+                    .replace('>', "&gt;")
+            ),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::View;
+    use crate::Render;
+    use serde_json::json;
+    use std::{
+        env::temp_dir,
+        fmt::{Result, Write},
+        fs::write,
+        path::PathBuf,
+    };
+
+    fn dir(name: &str, files: &[(&str, &str)]) -> PathBuf {
+        let dir = temp_dir().join(format!("reign_view_interpret_{}", name));
+
+        for (file, content) in files {
+            let path = dir.join(file);
+
+            std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+            write(path, content).unwrap();
+        }
+
+        dir
+    }
+
+    #[test]
+    fn test_interpolation() {
+        let dir = dir(
+            "interpolation",
+            &[(
+                "app.html",
+                "<p title=\"{{ name }}\" :id=\"id\">{{ name.to_uppercase() }} is {{ age + 1 }}</p>",
+            )],
+        );
+
+        assert_eq!(
+            View::new(dir, "app")
+                .field("name", "John")
+                .field("id", "user")
+                .field("age", &27)
+                .to_string(),
+            "<p title=\"John\" id=\"user\">JOHN is 28</p>"
+        );
+    }
+
+    #[test]
+    fn test_control() {
+        let dir = dir(
+            "control",
+            &[(
+                "app.html",
+                "<ul>\n  <li !for=\"(i, user) in users.iter().enumerate()\">{{ i }}{{ user.name }}</li>\n  \
+                <b !if=\"users.is_empty()\">none</b>\n  <b !else-if=\"users.len() == 1\">one</b>\n  \
+                <b !else>many</b>\n</ul>",
+            )],
+        );
+
+        assert_eq!(
+            View::new(dir, "app")
+                .field("users", &json!([{ "name": "a" }, { "name": "b" }]))
+                .to_string(),
+            "<ul>\n  <li>0a</li><li>1b</li>\n  <b>many</b>\n</ul>"
+        );
+    }
+
+    #[test]
+    fn test_for_else() {
+        let dir = dir(
+            "for_else",
+            &[(
+                "app.html",
+                "<ul>\n  <li !for=\"user in users, meta\" :class=\"meta.last\">{{ meta.index }}{{ user }}</li>\n  \
+                <li !else>none</li>\n</ul>",
+            )],
+        );
+
+        assert_eq!(
+            View::new(&dir, "app")
+                .field("users", &["a", "b"])
+                .to_string(),
+            "<ul>\n  <li class=\"false\">0a</li><li class=\"true\">1b</li>\n</ul>"
+        );
+
+        assert_eq!(
+            View::new(&dir, "app")
+                .field("users", &Vec::<String>::new())
+                .to_string(),
+            "<ul>\n  <li>none</li>\n</ul>"
+        );
+    }
+
+    #[test]
+    fn test_match() {
+        let dir = dir(
+            "match",
+            &[(
+                "app.html",
+                "<p !match=\"status\">\n  <b !case=\"Status::Draft\">draft</b>\n  \
+                <b !case=\"Status::Published(date)\" !if=\"!hidden\">{{ date }}</b>\n  \
+                <b !case=\"Status::Archived { reason, .. }\">{{ reason }}</b>\n  <b !case=\"_\">other</b>\n</p>",
+            )],
+        );
+
+        let render = |status: serde_json::Value, hidden: bool| {
+            View::new(&dir, "app")
+                .field("status", &status)
+                .field("hidden", &hidden)
+                .to_string()
+        };
+
+        assert_eq!(render(json!("Draft"), false), "<p><b>draft</b></p>");
+        assert_eq!(
+            render(json!({ "Published": "today" }), false),
+            "<p><b>today</b></p>"
+        );
+        assert_eq!(
+            render(json!({ "Published": "today" }), true),
+            "<p><b>other</b></p>"
+        );
+        assert_eq!(
+            render(
+                json!({ "Archived": { "reason": "old", "by": "me" } }),
+                false
+            ),
+            "<p><b>old</b></p>"
+        );
+    }
+
+    #[test]
+    fn test_let() {
+        let dir = dir(
+            "let",
+            &[(
+                "app.html",
+                "<p !let=\"name = user.to_uppercase()\">{{ name }}<b !for=\"item in items\" !let=\"(a, b) = item\">{{ a }}{{ b }}{{ name }}</b></p>",
+            )],
+        );
+
+        assert_eq!(
+            View::new(dir, "app")
+                .field("user", "john")
+                .field("items", &[(1, 2), (3, 4)])
+                .to_string(),
+            "<p>JOHN<b>12JOHN</b><b>34JOHN</b></p>"
+        );
+    }
+
+    #[test]
+    fn test_component() {
+        let dir = dir(
+            "component",
+            &[
+                (
+                    "app.html",
+                    "<shared:card title=\"Hi\" :count=\"n\"><template #footer>{{ n }}</template>body</shared:card>",
+                ),
+                (
+                    "shared/card.html",
+                    "<div>{{ title }}{{ count }}<slot></slot><slot name=\"footer\"></slot></div>",
+                ),
+            ],
+        );
+
+        assert_eq!(
+            View::new(dir, "app").field("n", &2).to_string(),
+            "<div>Hi2body2</div>"
+        );
+    }
+
+    #[test]
+    fn test_component_defaults() {
+        let dir = dir(
+            "component_defaults",
+            &[
+                ("app.html", "<badge />"),
+                ("badge.html", "<i><b !if=\"active\">on</b>{{ label }}</i>"),
+            ],
+        );
+
+        assert_eq!(View::new(dir, "app").to_string(), "<i></i>");
+    }
+
+    #[test]
+    fn test_component_props() {
+        let dir = dir(
+            "component_props",
+            &[
+                ("app.html", "<badge :count=\"n\" />"),
+                ("typo.html", "<badge :cuont=\"n\" />"),
+                (
+                    "badge.html",
+                    "<props>count: u8, label: &str = \"new\", active?: bool</props><i !if=\"!active\">{{ label }} {{ count }}</i>",
+                ),
+            ],
+        );
+
+        assert_eq!(
+            View::new(&dir, "app").field("n", &2).to_string(),
+            "<i>new 2</i>"
+        );
+
+        assert!(View::new(&dir, "typo")
+            .field("n", &2)
+            .render()
+            .unwrap_err()
+            .to_string()
+            .ends_with("component has no prop `cuont`"));
+    }
+
+    #[test]
+    fn test_scoped_slots() {
+        let dir = dir(
+            "scoped_slots",
+            &[
+                (
+                    "app.html",
+                    "<list :rows=\"rows\"><template #row=\"(a, b), i\">{{ i }}:{{ a }}{{ b }}{{ sep }}</template>end</list>",
+                ),
+                (
+                    "list.html",
+                    "<ul><li !for=\"row in rows, meta\"><slot name=\"row\" :item=\"row\" :index=\"meta.index\"></slot></li><slot></slot></ul>",
+                ),
+            ],
+        );
+
+        assert_eq!(
+            View::new(&dir, "app")
+                .field("rows", &[(1, 2), (3, 4)])
+                .field("sep", ";")
+                .to_string(),
+            "<ul><li>0:12;</li><li>1:34;</li>end</ul>"
+        );
+    }
+
+    #[test]
+    fn test_filters() {
+        let dir = dir(
+            "filters",
+            &[
+                (
+                    "app.html",
+                    "<p title=\"{{ name | upper }}\">{{ name | truncate(2) }} {{ missing | default(\"n/a\") }} {{ at | date(\"%d/%m/%Y\") }} {{ n }} {{ n | pluralize(\"item\", \"items\") }} {{ price | currency(\"$\") }} {{ a || b }}</p>",
+                ),
+                ("custom.html", "<p>{{ name | shout }}</p>"),
+                ("or.html", "<p>{{ is_admin | is_owner }}</p>"),
+                (
+                    "option.html",
+                    "<p>{{ at | date(\"%Y\") | default(\"n/a\") }} {{ flags | MASK }}</p>",
+                ),
+            ],
+        );
+
+        assert_eq!(
+            View::new(&dir, "app")
+                .field("name", "john")
+                .field("missing", &None::<&str>)
+                .field("at", "2020-01-02T03:04:05Z")
+                .field("n", &1)
+                .field("price", &1234.5)
+                .field("a", &false)
+                .field("b", &true)
+                .to_string(),
+            "<p title=\"JOHN\">jo... n/a 02/01/2020 1 item $1,234.50 true</p>"
+        );
+
+        assert_eq!(
+            View::new(&dir, "option")
+                .field("at", &None::<&str>)
+                .field("flags", &6)
+                .field("MASK", &3)
+                .to_string(),
+            "<p>n/a 7</p>"
+        );
+
+        assert_eq!(
+            View::new(&dir, "or")
+                .filters(&["shout"])
+                .field("is_admin", &false)
+                .field("is_owner", &true)
+                .to_string(),
+            "<p>true</p>"
+        );
+
+        assert!(View::new(&dir, "custom")
+            .filters(&["shout"])
+            .field("name", "john")
+            .render()
+            .unwrap_err()
+            .to_string()
+            .ends_with(
+                "unknown filter `shout`, only the built in filters are available when hot reloading"
+            ));
+        assert!(View::new(&dir, "custom")
+            .filters(&["shout"])
+            .field("name", "john")
+            .interpret()
+            .is_none());
+        assert_eq!(
+            View::new(&dir, "app")
+                .field("name", "jo")
+                .field("missing", "x")
+                .field("at", "2020-01-02")
+                .field("n", &2)
+                .field("price", &1)
+                .field("a", &true)
+                .field("b", &false)
+                .interpret()
+                .unwrap()
+                .to_string(),
+            "<p title=\"JO\">jo x 02/01/2020 2 items $1.00 true</p>"
+        );
+    }
+
+    #[test]
+    fn test_compiled_fallback() {
+        let dir = dir(
+            "compiled_fallback",
+            &[
+                ("method.html", "<p>{{ article.summary() }}</p>"),
+                ("path.html", "<p>{{ flags | crate::MASK }}</p>"),
+                ("missing.html", "<p>{{ article.title }}</p>"),
+            ],
+        );
+        let article = json!({ "title": "Hello" });
+
+        assert!(View::new(&dir, "method")
+            .field("article", &article)
+            .interpret()
+            .is_none());
+        assert!(View::new(&dir, "path")
+            .field("flags", &1)
+            .interpret()
+            .is_none());
+
+        // Errors in the template are still shown
+        assert!(View::new(&dir, "missing")
+            .interpret()
+            .unwrap()
+            .to_string()
+            .contains("unknown field `article`"));
+    }
+
+    #[test]
+    fn test_html_spec() {
+        let dir = dir(
+            "html_spec",
+            &[
+                (
+                    "app.html",
+                    "<div><ul><li>{{ a }}<li>b</ul><script>x = \"{{ a }}\";</script>\
+                    <svg><g :id=\"a\" /></svg><fancy-box></fancy-box><user-card /></div>",
+                ),
+                ("user_card.html", "<i>card</i>"),
+            ],
+        );
+
+        assert_eq!(
+            View::new(&dir, "app").field("a", "one").to_string(),
+            "<div><ul><li>one</li><li>b</li></ul><script>x = \"{{ a }}\";</script>\
+            <svg><g id=\"one\"></g></svg><fancy-box></fancy-box><i>card</i></div>"
+        );
+    }
+
+    struct Widget(&'static str);
+
+    impl Render for Widget {
+        fn render(&self, f: &mut dyn Write) -> Result {
+            write!(f, "<b>{}</b>", self.0)
+        }
+    }
+
+    #[test]
+    fn test_dynamic_component() {
+        let dir = dir(
+            "dynamic_component",
+            &[(
+                "app.html",
+                "<div><component !for=\"widget in widgets\" :is=\"widget\" /></div>",
+            )],
+        );
+        let widgets: Vec<Box<dyn Render>> = vec![Box::new(Widget("a")), Box::new(Widget("b"))];
+
+        assert_eq!(
+            View::new(&dir, "app")
+                .field("widgets", &widgets)
+                .to_string(),
+            "<div><b>a</b><b>b</b></div>"
+        );
+
+        assert!(View::new(&dir, "app")
+            .field("widgets", &[1])
+            .render()
+            .unwrap_err()
+            .to_string()
+            .ends_with("expected a view for the `:is` attribute"));
+    }
+
+    #[test]
+    fn test_layout() {
+        let dir = dir(
+            "layout",
+            &[
+                (
+                    "layouts/app.html",
+                    "<html><head><slot name=\"head\"></slot></head><body><slot></slot></body></html>",
+                ),
+                (
+                    "page.html",
+                    "<template><template #head><title>{{ title }}</title></template><p>{{ title }}</p></template>",
+                ),
+            ],
+        );
+
+        assert_eq!(
+            View::new(dir, "page")
+                .layout("layouts:app")
+                .field("title", "Home")
+                .to_string(),
+            "<html><head><title>Home</title></head><body><p>Home</p></body></html>"
+        );
+    }
+
+    #[test]
+    fn test_error() {
+        let dir = dir("error", &[("app.html", "<p>{{ missing }}</p>")]);
+
+        assert!(View::new(dir, "app")
+            .render()
+            .unwrap_err()
+            .to_string()
+            .ends_with("unknown field `missing`"));
+    }
+}
@@ -0,0 +1,380 @@
+use super::{
+    super::{
+        attribute::AttributeValue, consts::VOID_TAGS, Args, Attribute, Code, Comment, Doctype,
+        Element, Node, StringPart, Text,
+    },
+    expr::display,
+    expr::{bind, matches},
+    file, Context, Error, Evaluate, Interpret, Scoped,
+};
+use crate::INTERNAL_ERR;
+use inflector::cases::snakecase::to_snake_case;
+use serde_json::{Map, Value};
+use std::{collections::HashMap, rc::Rc};
+
+impl Interpret for Node {
+    fn interpret(&self, out: &mut String, ctx: &mut Context) -> Result<(), Error> {
+        match self {
+            Node::Element(e) => e.interpret(out, ctx),
+            Node::Comment(c) => c.interpret(out, ctx),
+            Node::Text(t) => t.interpret(out, ctx),
+            Node::Doctype(d) => d.interpret(out, ctx),
+        }
+    }
+}
+
+impl Interpret for Comment {
+    fn interpret(&self, out: &mut String, _: &mut Context) -> Result<(), Error> {
+        out.push_str("<!--");
+        out.push_str(&self.content);
+        out.push_str("-->");
+        Ok(())
+    }
+}
+
+impl Interpret for Doctype {
+    fn interpret(&self, out: &mut String, _: &mut Context) -> Result<(), Error> {
+        out.push_str(&self.content);
+        Ok(())
+    }
+}
+
+impl Interpret for Text {
+    fn interpret(&self, out: &mut String, ctx: &mut Context) -> Result<(), Error> {
+        out.push_str(&parts(&self.content, ctx)?);
+        Ok(())
+    }
+}
+
+fn parts(parts: &[StringPart], ctx: &Context) -> Result<String, Error> {
+    let mut string = String::new();
+
+    for part in parts {
+        match part {
+            StringPart::Normal(n) => string.push_str(n),
+            StringPart::Expr(e) => string.push_str(&display(&e.evaluate(ctx)?)),
+        }
+    }
+
+    Ok(string)
+}
+
+fn attribute_value(value: &AttributeValue, ctx: &Context) -> Result<String, Error> {
+    if !value.has_expr() {
+        let string = value.value().unwrap_or_default();
+
+        if string == "\"\"" {
+            Ok(String::new())
+        } else {
+            Ok(string)
+        }
+    } else {
+        parts(&value.parts, ctx)
+    }
+}
+
+impl Interpret for Attribute {
+    fn interpret(&self, out: &mut String, ctx: &mut Context) -> Result<(), Error> {
+        let (name, value) = match self {
+            Attribute::Normal(n) => (n.name.clone(), attribute_value(&n.value, ctx)?),
+            Attribute::Dynamic(d) => (
+                format!(
+                    "{}{}{}",
+                    d.prefix,
+                    display(&d.name.evaluate(ctx)?),
+                    d.suffix
+                ),
+                display(&d.value.evaluate(ctx)?),
+            ),
+            Attribute::Variable(v) => (v.name.clone(), display(&v.value.evaluate(ctx)?)),
+            Attribute::Control(_) => return Ok(()),
+        };
+
+        out.push_str(&format!(" {}=\"{}\"", name, value));
+        Ok(())
+    }
+}
+
+impl Element {
+    fn body(&self, out: &mut String, ctx: &mut Context) -> Result<(), Error> {
+        // Bindings are only visible to the element itself
+        if let Some(Code::Let(let_)) = self.control_attr("let").map(|x| &x.value) {
+            let value = let_.expr.evaluate(ctx)?;
+            let mut scope = Map::new();
+
+            bind(&let_.pat, &value, &mut scope)?;
+
+            ctx.scopes.push(scope);
+            let result = self.element(out, ctx);
+            ctx.scopes.pop();
+
+            return result;
+        }
+
+        self.element(out, ctx)
+    }
+
+    fn element(&self, out: &mut String, ctx: &mut Context) -> Result<(), Error> {
+        let tag_pieces: Vec<&str> = self.name.split(':').collect();
+
+        if self.name == "template" {
+            self.inner(out, ctx)
+        } else if self.name == "slot" {
+            let props = self.slot_props();
+
+            if props.is_empty() {
+                if let Some(slot) = ctx.slots.get(&self.slot_name()) {
+                    out.push_str(slot);
+                }
+            } else if let Some(slot) = ctx.scoped.get(&self.slot_name()).cloned() {
+                let values = props
+                    .iter()
+                    .map(|x| x.value.evaluate(ctx))
+                    .collect::<Result<Vec<_>, _>>()?;
+
+                slot(&values, out)?;
+            }
+
+            Ok(())
+        } else if self.name == "component" {
+            let is = self.variable_attr("is").expect(INTERNAL_ERR);
+
+            // The views are given to the interpreter as their HTML
+            match is.value.evaluate(ctx)? {
+                Value::String(html) => {
+                    out.push_str(&html);
+                    Ok(())
+                }
+                _ => Err(Error::new("expected a view for the `:is` attribute")),
+            }
+        } else if self.is_plain(|id| file(ctx.templates.dir, &[id.to_string()]).is_file()) {
+            out.push('<');
+            out.push_str(&self.name);
+
+            for attr in &self.attrs {
+                attr.interpret(out, ctx)?;
+            }
+
+            out.push('>');
+            self.inner(out, ctx)?;
+
+            if !VOID_TAGS.contains(&self.name.as_str()) {
+                out.push_str(&format!("</{}>", self.name));
+            }
+
+            Ok(())
+        } else {
+            let mut fields = Map::new();
+            let mut slots = HashMap::new();
+
+            for attr in &self.attrs {
+                match attr {
+                    Attribute::Normal(n) => {
+                        let value = attribute_value(&n.value, ctx)?;
+
+                        fields.insert(to_snake_case(&n.name), Value::String(value));
+                    }
+                    Attribute::Variable(v) => {
+                        fields.insert(to_snake_case(&v.name), v.value.evaluate(ctx)?);
+                    }
+                    _ => {}
+                }
+            }
+
+            for child in &self.children {
+                if let Node::Element(e) = child {
+                    if e.slot_args().is_some() {
+                        continue;
+                    }
+
+                    if let Some(name) = e.template_name() {
+                        let mut slot = String::new();
+
+                        child.interpret(&mut slot, ctx)?;
+                        slots.insert(name[1..].to_string(), slot);
+                    }
+                }
+            }
+
+            let mut children = String::new();
+
+            self.inner(&mut children, ctx)?;
+            slots.entry("default".to_string()).or_insert(children);
+
+            let path: Vec<String> = tag_pieces.into_iter().map(String::from).collect();
+            let parent: &Context = ctx;
+            let mut scoped: HashMap<String, Scoped> = HashMap::new();
+
+            // Templates with patterns are rendered by the component with the values of the slot
+            for child in &self.children {
+                if let Node::Element(e) = child {
+                    if let (Some(name), Some(Args(pats))) = (e.template_name(), e.slot_args()) {
+                        let render = move |values: &[Value], out: &mut String| {
+                            if values.len() != pats.len() {
+                                return Err(Error::new(format!(
+                                    "expected {} values from the slot but got {}",
+                                    pats.len(),
+                                    values.len()
+                                )));
+                            }
+
+                            let mut scope = Map::new();
+
+                            for (pat, value) in pats.iter().zip(values) {
+                                bind(pat, value, &mut scope)?;
+                            }
+
+                            let mut ctx = parent.fork();
+
+                            ctx.scopes.push(scope);
+                            e.interpret(out, &mut ctx)
+                        };
+
+                        scoped.insert(name[1..].to_string(), Rc::new(render));
+                    }
+                }
+            }
+
+            parent.component(&path, &fields, slots, scoped, out)
+        }
+    }
+
+    fn inner(&self, out: &mut String, ctx: &mut Context) -> Result<(), Error> {
+        match self.control_attr("match") {
+            Some(attr) => self.cases(&attr.value.evaluate(ctx)?, out, ctx),
+            None => self.children(out, ctx),
+        }
+    }
+
+    /// Renders the first `!case` child which matches the value.
+    fn cases(&self, value: &Value, out: &mut String, ctx: &mut Context) -> Result<(), Error> {
+        for child in &self.children {
+            let (e, pat) = match child {
+                Node::Element(e) => match e.control_attr("case").map(|x| &x.value) {
+                    Some(Code::Pat(pat)) => (e, pat),
+                    _ => continue,
+                },
+                _ => continue,
+            };
+
+            let mut scope = Map::new();
+
+            if !matches(pat, value, &mut scope)? {
+                continue;
+            }
+
+            ctx.scopes.push(scope);
+
+            // An `!if` on the case is its guard
+            let result = match e.control_attr("if") {
+                Some(attr) => ctx.condition(&attr.value),
+                None => Ok(true),
+            }
+            .and_then(|guard| {
+                if guard {
+                    e.body(out, ctx)?;
+                }
+
+                Ok(guard)
+            });
+
+            ctx.scopes.pop();
+
+            if result? {
+                break;
+            }
+        }
+
+        Ok(())
+    }
+
+    fn children(&self, out: &mut String, ctx: &mut Context) -> Result<(), Error> {
+        // Whether a branch was taken in the current `!if` chain
+        let mut taken = None;
+
+        for (i, child) in self.children.iter().enumerate() {
+            let e = match child {
+                Node::Element(e) => e,
+                _ => {
+                    // Text between the branches of a chain is not rendered
+                    if taken.is_none() || !self.continues_chain(i + 1) {
+                        child.interpret(out, ctx)?;
+                    }
+
+                    continue;
+                }
+            };
+
+            if e.template_name().is_some() {
+                continue;
+            }
+
+            let else_if = e.control_attr("else-if");
+
+            if else_if.is_some() || e.control_attr("else").is_some() {
+                let done = taken.ok_or_else(|| {
+                    Error::new("expected `!if` or `!for` element before `!else` or `!else-if`")
+                })?;
+
+                if done {
+                    continue;
+                }
+
+                if let Some(attr) = else_if {
+                    if !ctx.condition(&attr.value)? {
+                        continue;
+                    }
+                }
+
+                taken = Some(true);
+                e.body(out, ctx)?;
+            } else if let Some(attr) = e.control_attr("for") {
+                taken = Some(ctx.iterate(&attr.value, |ctx| e.body(out, ctx))?);
+            } else if let Some(attr) = e.control_attr("if") {
+                let condition = ctx.condition(&attr.value)?;
+
+                taken = Some(condition);
+
+                if condition {
+                    e.body(out, ctx)?;
+                }
+            } else {
+                taken = None;
+                e.interpret(out, ctx)?;
+            }
+        }
+
+        Ok(())
+    }
+
+    fn continues_chain(&self, from: usize) -> bool {
+        for child in &self.children[from..] {
+            if let Node::Element(e) = child {
+                if e.template_name().is_some() {
+                    continue;
+                }
+
+                return e.control_attr("else").is_some() || e.control_attr("else-if").is_some();
+            }
+        }
+
+        false
+    }
+}
+
+impl Interpret for Element {
+    fn interpret(&self, out: &mut String, ctx: &mut Context) -> Result<(), Error> {
+        if let Some(attr) = self.control_attr("for") {
+            ctx.iterate(&attr.value, |ctx| self.body(out, ctx))
+                .map(|_| ())
+        } else if let Some(attr) = self.control_attr("if") {
+            if ctx.condition(&attr.value)? {
+                self.body(out, ctx)?;
+            }
+
+            Ok(())
+        } else {
+            self.body(out, ctx)
+        }
+    }
+}
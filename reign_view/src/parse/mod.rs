@@ -1,5 +1,8 @@
+use crate::common::Signatures;
+use inflector::cases::snakecase::to_snake_case;
 use proc_macro2::TokenStream;
-use quote::ToTokens;
+use quote::{format_ident, quote, ToTokens};
+use std::rc::Rc;
 use syn::{
     punctuated::{Pair, Punctuated},
     Ident, Member,
@@ -13,9 +16,13 @@ mod doctype;
 mod element;
 mod error;
 mod expr;
+mod filter;
+#[cfg(feature = "hot-reload")]
+pub mod interpret;
 mod node;
 mod parse_stream;
 mod pat;
+mod props;
 mod string_part;
 mod text;
 mod view_fields;
@@ -25,15 +32,20 @@ use code::Code;
 use comment::Comment;
 use doctype::Doctype;
 use element::Element;
-use error::Error;
+pub use error::Error;
 use expr::Expr;
+use filter::Filtered;
 use node::Node;
 use parse_stream::ParseStream;
-use pat::For;
+use pat::{Args, For, Let, Pat};
+pub use props::Prop;
 use string_part::StringPart;
 use text::Text;
 use view_fields::ViewFields;
 
+const ELSE_WITHOUT_IF: &str = "expected `!if` or `!for` element before `!else` or `!else-if`";
+const CASE_WITHOUT_MATCH: &str = "expected `!match` element around `!case`";
+
 fn tag_name_regex() -> String {
     format!("<({0}(:?:{0})*)", consts::TAG_NAME)
 }
@@ -46,16 +58,40 @@ trait Tokenize {
     fn tokenize(&self, tokens: &mut TokenStream, idents: &mut ViewFields, scopes: &ViewFields);
 }
 
-pub fn parse(data: String) -> Result<Node, Error> {
+/// Parses the view into its props, if it declares them, and its root node.
+///
+/// A `|` in an interpolation only applies a filter when it is followed by the name of a built
+/// in filter or one of the given filters of the app.
+pub fn parse(data: String, filters: &[String]) -> Result<(Option<Vec<Prop>>, Node), Error> {
     let mut ps = ParseStream::new(data);
+
+    ps.filters = filters.to_vec();
+
+    ps.skip_spaces()?;
+
+    let props = props::parse(&mut ps)?;
+
+    ps.skip_spaces()?;
+
+    let start = ps.cursor;
     let node: Node = ps.parse()?;
 
+    if let Node::Element(e) = &node {
+        if e.control_attr("else").is_some() || e.control_attr("else-if").is_some() {
+            return Err(ps.error_at(start, ELSE_WITHOUT_IF));
+        }
+
+        if e.control_attr("case").is_some() {
+            return Err(ps.error_at(start, CASE_WITHOUT_MATCH));
+        }
+    }
+
     ps.skip_spaces()?;
 
     if ps.content.len() != ps.cursor {
         Err(ps.error("only one top-level node is allowed"))
     } else {
-        Ok(node)
+        Ok((props, node))
     }
 }
 
@@ -96,14 +132,100 @@ where
     }
 }
 
-pub fn tokenize(node: Node) -> (TokenStream, Vec<(Ident, bool)>, Vec<TokenStream>) {
+/// Tokens of the view along with the information needed to build its struct.
+pub struct Tokenized {
+    pub tokens: TokenStream,
+    pub fields: Vec<(Ident, bool)>,
+    pub types: Vec<TokenStream>,
+    pub sections: TokenStream,
+    pub components: Vec<String>,
+    pub props: Option<Vec<Prop>>,
+    pub slots: Vec<(String, Vec<TokenStream>)>,
+}
+
+/// Prefix of the string in `let _ = "reign:location:<cursor>";` which precedes the code of
+/// every element when tokenizing with [`tokenize_located`].
+pub const LOCATION: &str = "reign:location:";
+
+/// Parses the view and builds the tokens for it. The signatures of the other views are used
+/// to check and fill in the attributes of the components.
+pub fn tokenize(data: String, signatures: Rc<Signatures>) -> Result<Tokenized, Error> {
+    tokenize_with(data, signatures, false)
+}
+
+/// Same as [`tokenize`], but the code of every element is preceded by a marker with the
+/// position of the element in the view, so that the code can be mapped back to it.
+pub fn tokenize_located(data: String, signatures: Rc<Signatures>) -> Result<Tokenized, Error> {
+    tokenize_with(data, signatures, true)
+}
+
+fn tokenize_with(
+    data: String,
+    signatures: Rc<Signatures>,
+    locations: bool,
+) -> Result<Tokenized, Error> {
+    let (props, node) = parse(data.clone(), &signatures.filters)?;
     let mut tokens = TokenStream::new();
-    let mut idents = ViewFields::new();
+    let mut idents = ViewFields {
+        signatures,
+        locations,
+        ..ViewFields::new()
+    };
     let scopes = ViewFields::new();
 
+    // Only the declared props can be used as fields
+    if let Some(props) = &props {
+        for prop in props {
+            idents.insert(prop.name.clone(), Some(prop.ty().to_token_stream()));
+        }
+
+        idents.strict = true;
+    }
+
     node.tokenize(&mut tokens, &mut idents, &scopes);
 
-    (tokens, idents.keys(), idents.values())
+    // Named templates in the root template are the sections given to the layout
+    let (names, templates) = match &node {
+        Node::Element(e) if e.name == "template" => e.templates(&mut idents, &scopes),
+        _ => (vec![], vec![]),
+    };
+
+    let sections = quote! {
+        #(#names => ::reign::view::slot_render(move |f: &mut dyn std::fmt::Write| {
+            #templates
+            Ok(())
+        })),*
+    };
+
+    // Errors outside of any element are at the start of the view
+    if let Some((cursor, message)) = idents.errors.first() {
+        return Err(Error::new(data, cursor.unwrap_or_default(), message));
+    }
+
+    let mut components = idents.components.clone();
+
+    components.sort();
+    components.dedup();
+
+    Ok(Tokenized {
+        tokens,
+        fields: idents.keys(),
+        types: idents.values(),
+        sections,
+        components,
+        props,
+        slots: idents.slots.clone(),
+    })
+}
+
+/// Field which holds the template given by the parent for the scoped slot.
+pub fn slot_field(name: &str) -> Ident {
+    format_ident!("{}", to_snake_case(name))
+}
+
+/// Struct which holds the templates given by the parent for the scoped slots of the view.
+pub fn scoped_slots_ident(view: &Ident) -> Ident {
+    format_ident!("{}ScopedSlots", view)
 }
 
 fn is_member_named(member: &Member) -> bool {
@@ -5,6 +5,8 @@ use regex::Regex;
 pub struct ParseStream {
     pub content: String,
     pub cursor: usize,
+    /// Names of the filters of the app
+    pub filters: Vec<String>,
 }
 
 // TODO:(perf) This is not really efficient since getting to the
@@ -14,15 +16,19 @@ pub struct ParseStream {
 // What we can do is consume the string as we keep parsing.
 impl ParseStream {
     pub(super) fn new(content: String) -> Self {
-        ParseStream { content, cursor: 0 }
+        ParseStream {
+            content,
+            cursor: 0,
+            filters: vec![],
+        }
     }
 
     pub(super) fn error(&self, msg: &str) -> Error {
-        Error {
-            content: self.content.clone(),
-            cursor: self.cursor,
-            message: msg.to_string(),
-        }
+        self.error_at(self.cursor, msg)
+    }
+
+    pub(super) fn error_at(&self, cursor: usize, msg: &str) -> Error {
+        Error::new(self.content.clone(), cursor, msg)
     }
 
     pub(super) fn parse<T>(&mut self) -> Result<T, Error>
@@ -126,6 +132,30 @@ impl ParseStream {
         Ok(sub_string.unwrap().to_string())
     }
 
+    /// Returns the text until the end tag of the given raw text element, which matches
+    /// ignoring the case only when the name is followed by a space, `/` or `>`.
+    pub(super) fn until_end_tag(&mut self, name: &str) -> Result<String, Error> {
+        let reg = Regex::new(&format!("(?i)</{}[\\s/>]", regex::escape(name))).unwrap();
+        let mat = reg.find(self.content.get(self.cursor..).unwrap());
+
+        if mat.is_none() {
+            return Err(self.error(&format!("expected `</{}`", name)));
+        }
+
+        let index = self.cursor + mat.unwrap().start();
+        let sub_string = self.content.get(self.cursor..index);
+
+        self.cursor = index;
+        Ok(sub_string.unwrap().to_string())
+    }
+
+    /// Steps over the end tag found by [`until_end_tag`](Self::until_end_tag).
+    pub(super) fn step_end_tag(&mut self, name: &str) -> Result<(), Error> {
+        self.matched(&format!("(?i)</{}[\\s/]*", regex::escape(name)))?;
+        self.step(">")?;
+        Ok(())
+    }
+
     pub(super) fn skip_spaces(&mut self) -> Result<(), Error> {
         self.matched("\\s*")?;
         Ok(())
@@ -149,6 +179,7 @@ mod test {
         let mut ps = ParseStream {
             content: "<b>Hello</b>".to_string(),
             cursor: 3,
+            filters: vec![],
         };
 
         let parts = ps.parse_text().unwrap();
@@ -247,6 +278,7 @@ mod test {
         let ps = ParseStream {
             content: "Hello World".to_string(),
             cursor: 1,
+            filters: vec![],
         };
 
         assert!(ps.is_match("[a-z]+"));
@@ -258,6 +290,7 @@ mod test {
         let mut ps = ParseStream {
             content: "Hello World".to_string(),
             cursor: 2,
+            filters: vec![],
         };
 
         let val = ps.matched("[a-z]+").unwrap();
@@ -271,6 +304,7 @@ mod test {
         let mut ps = ParseStream {
             content: "Hello World".to_string(),
             cursor: 1,
+            filters: vec![],
         };
 
         let err = ps.matched("[A-Z]+").unwrap_err();
@@ -285,6 +319,7 @@ mod test {
         let mut ps = ParseStream {
             content: "Hello World".to_string(),
             cursor: 3,
+            filters: vec![],
         };
 
         let val = ps.capture("([a-z])([a-z])", 2).unwrap();
@@ -298,6 +333,7 @@ mod test {
         let mut ps = ParseStream {
             content: "Hello World".to_string(),
             cursor: 1,
+            filters: vec![],
         };
 
         let err = ps.capture("[A-Z]+", 1).unwrap_err();
@@ -312,6 +348,7 @@ mod test {
         let mut ps = ParseStream {
             content: "Hello World".to_string(),
             cursor: 1,
+            filters: vec![],
         };
 
         let err = ps.capture("([a-z])([a-z])", 3).unwrap_err();
@@ -329,6 +366,7 @@ mod test {
         let ps = ParseStream {
             content: "Hello".to_string(),
             cursor: 1,
+            filters: vec![],
         };
 
         assert!(ps.peek("ello"));
@@ -340,6 +378,7 @@ mod test {
         let mut ps = ParseStream {
             content: "Hello".to_string(),
             cursor: 1,
+            filters: vec![],
         };
 
         let val = ps.step("el").unwrap();
@@ -353,6 +392,7 @@ mod test {
         let mut ps = ParseStream {
             content: "Hello".to_string(),
             cursor: 1,
+            filters: vec![],
         };
 
         let err = ps.step("Hel").unwrap_err();
@@ -367,6 +407,7 @@ mod test {
         let mut ps = ParseStream {
             content: "Hello".to_string(),
             cursor: 1,
+            filters: vec![],
         };
 
         let err = ps.step("Hello").unwrap_err();
@@ -384,6 +425,7 @@ mod test {
         let ps = ParseStream {
             content: "Hello".to_string(),
             cursor: 1,
+            filters: vec![],
         };
 
         let index = ps.seek("lo").unwrap();
@@ -397,6 +439,7 @@ mod test {
         let ps = ParseStream {
             content: "Hello".to_string(),
             cursor: 1,
+            filters: vec![],
         };
 
         let err = ps.seek("H").unwrap_err();
@@ -411,6 +454,7 @@ mod test {
         let mut ps = ParseStream {
             content: "Hello World".to_string(),
             cursor: 1,
+            filters: vec![],
         };
 
         let val = ps.until("lo", true).unwrap();
@@ -424,6 +468,7 @@ mod test {
         let mut ps = ParseStream {
             content: "Hello World".to_string(),
             cursor: 1,
+            filters: vec![],
         };
 
         let val = ps.until("lo", false).unwrap();
@@ -437,6 +482,7 @@ mod test {
         let mut ps = ParseStream {
             content: "Hello World".to_string(),
             cursor: 1,
+            filters: vec![],
         };
 
         let err = ps.until("H", true).unwrap_err();
@@ -445,4 +491,33 @@ mod test {
         assert_eq!(err.cursor, 1);
         assert_eq!(err.message, "expected `H`".to_string());
     }
+
+    #[test]
+    fn test_until_end_tag() {
+        let mut ps = ParseStream {
+            content: "a </scripts> </scriptx </SCRIPT >".to_string(),
+            cursor: 0,
+            filters: vec![],
+        };
+
+        let val = ps.until_end_tag("script").unwrap();
+
+        assert_eq!(val, "a </scripts> </scriptx ".to_string());
+        ps.step_end_tag("script").unwrap();
+        assert_eq!(ps.cursor, ps.content.len());
+    }
+
+    #[test]
+    fn test_until_end_tag_error() {
+        let mut ps = ParseStream {
+            content: "a </scripts>".to_string(),
+            cursor: 0,
+            filters: vec![],
+        };
+
+        let err = ps.until_end_tag("script").unwrap_err();
+
+        assert_eq!(ps.cursor, 0);
+        assert_eq!(err.message, "expected `</script`".to_string());
+    }
 }
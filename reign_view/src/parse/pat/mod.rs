@@ -6,7 +6,7 @@ use syn::{
     parse::{Parse, ParseStream, Result},
     punctuated::Punctuated,
     token::{Brace, Comma, Dot2, In, Paren},
-    Ident, PatRest, PatWild, Path, Token,
+    Ident, Lit, PatRest, PatWild, Path, Token,
 };
 
 mod field_pat;
@@ -23,9 +23,11 @@ use struct_::PatStruct;
 use tuple::PatTuple;
 use tuple_struct::PatTupleStruct;
 
-// TODO:(view:pat) Slice, Range
+// TODO:(view:pat) Slice, Range, Or
 pub enum Pat {
     Ident(PatIdent),
+    Lit(Lit),
+    Path(Path),
     Reference(PatReference),
     Rest(PatRest),
     Struct(PatStruct),
@@ -49,6 +51,8 @@ impl Parse for Pat {
             || input.peek(Token![crate])
         {
             pat_struct_or_tuple_struct(input)
+        } else if lookahead.peek(Lit) {
+            Ok(Pat::Lit(input.parse()?))
         } else if lookahead.peek(Token![_]) {
             Ok(Pat::Wild(PatWild {
                 attrs: Vec::new(),
@@ -75,6 +79,8 @@ impl Tokenize for Pat {
     fn tokenize(&self, tokens: &mut TokenStream, idents: &mut ViewFields, scopes: &ViewFields) {
         match self {
             Pat::Ident(p) => p.tokenize(tokens, idents, scopes),
+            Pat::Lit(p) => p.to_tokens(tokens),
+            Pat::Path(p) => p.to_tokens(tokens),
             Pat::Reference(p) => p.tokenize(tokens, idents, scopes),
             Pat::Rest(p) => p.to_tokens(tokens),
             Pat::Struct(p) => p.tokenize(tokens, idents, scopes),
@@ -85,19 +91,32 @@ impl Tokenize for Pat {
     }
 }
 
+impl Pat {
+    pub fn declared(&self) -> ViewFields {
+        let mut declared = ViewFields::new();
+        let mut tokens = TokenStream::new();
+        let scopes = ViewFields::new();
+
+        self.tokenize(&mut tokens, &mut declared, &scopes);
+        declared
+    }
+}
+
 pub struct For {
     pub pat: Pat,
     pub in_token: In,
     pub expr: Box<Expr>,
+    pub meta: Option<Ident>,
 }
 
 impl For {
     pub fn declared(&self) -> ViewFields {
-        let mut declared = ViewFields::new();
-        let mut tokens = TokenStream::new();
-        let scopes = ViewFields::new();
+        let mut declared = self.pat.declared();
+
+        if let Some(meta) = &self.meta {
+            declared.push(meta.clone());
+        }
 
-        self.pat.tokenize(&mut tokens, &mut declared, &scopes);
         declared
     }
 }
@@ -108,6 +127,11 @@ impl Parse for For {
             pat: input.parse()?,
             in_token: input.parse()?,
             expr: Box::new(input.call(expr_no_struct)?),
+            meta: if input.parse::<Option<Comma>>()?.is_some() {
+                Some(input.parse()?)
+            } else {
+                None
+            },
         })
     }
 }
@@ -122,6 +146,47 @@ impl Tokenize for For {
     }
 }
 
+/// Patterns of the values given by a scoped slot.
+pub struct Args(pub Punctuated<Pat, Comma>);
+
+impl Parse for Args {
+    fn parse(input: ParseStream) -> Result<Self> {
+        Ok(Args(Punctuated::parse_terminated(input)?))
+    }
+}
+
+pub struct Let {
+    pub pat: Pat,
+    pub eq_token: Token![=],
+    pub expr: Box<Expr>,
+}
+
+impl Let {
+    pub fn declared(&self) -> ViewFields {
+        self.pat.declared()
+    }
+}
+
+impl Parse for Let {
+    fn parse(input: ParseStream) -> Result<Self> {
+        Ok(Let {
+            pat: input.parse()?,
+            eq_token: input.parse()?,
+            expr: Box::new(input.parse()?),
+        })
+    }
+}
+
+impl Tokenize for Let {
+    fn tokenize(&self, tokens: &mut TokenStream, idents: &mut ViewFields, scopes: &ViewFields) {
+        let mut declared = ViewFields::new();
+
+        self.pat.tokenize(tokens, &mut declared, scopes);
+        self.eq_token.to_tokens(tokens);
+        self.expr.tokenize(tokens, idents, scopes);
+    }
+}
+
 // The following code is copied and modified from syn
 
 fn pat_struct_or_tuple_struct(input: ParseStream) -> Result<Pat> {
@@ -132,7 +197,7 @@ fn pat_struct_or_tuple_struct(input: ParseStream) -> Result<Pat> {
     } else if input.peek(Paren) {
         pat_tuple_struct(input, path).map(Pat::TupleStruct)
     } else {
-        Err(input.error("expected struct or tuple struct"))
+        Ok(Pat::Path(path))
     }
 }
 
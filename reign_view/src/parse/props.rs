@@ -0,0 +1,169 @@
+use super::{Error, ParseStream};
+use proc_macro2::Span;
+use syn::{
+    parse::{Parse, ParseStream as SynParseStream, Result},
+    parse_str,
+    punctuated::Punctuated,
+    token::{Colon, Comma, Eq, Question},
+    Expr, GenericArgument, Ident, Lifetime, PathArguments, Type,
+};
+
+/// Prop declared in the `<props>` block at the top of a view.
+pub struct Prop {
+    pub name: Ident,
+    pub optional: bool,
+    pub ty: Type,
+    pub default: Option<Expr>,
+}
+
+impl Prop {
+    /// Whether the prop needs to be given when using the view as a component.
+    pub fn required(&self) -> bool {
+        !self.optional && self.default.is_none()
+    }
+
+    /// Type of the prop with the lifetime of the view on the references without one.
+    pub fn ty(&self) -> Type {
+        let mut ty = self.ty.clone();
+
+        add_lifetime(&mut ty);
+        ty
+    }
+}
+
+impl Parse for Prop {
+    fn parse(input: SynParseStream) -> Result<Self> {
+        let name = input.parse()?;
+        let optional = input.parse::<Option<Question>>()?.is_some();
+
+        input.parse::<Colon>()?;
+
+        let ty = input.parse()?;
+        let default = if input.parse::<Option<Eq>>()?.is_some() {
+            Some(input.parse()?)
+        } else {
+            None
+        };
+
+        Ok(Prop {
+            name,
+            optional,
+            ty,
+            default,
+        })
+    }
+}
+
+struct Props(Vec<Prop>);
+
+impl Parse for Props {
+    fn parse(input: SynParseStream) -> Result<Self> {
+        let props = Punctuated::<Prop, Comma>::parse_terminated(input)?;
+
+        Ok(Props(props.into_iter().collect()))
+    }
+}
+
+/// Parses the `<props>` block if the view starts with one.
+pub(super) fn parse(input: &mut ParseStream) -> std::result::Result<Option<Vec<Prop>>, Error> {
+    if !input.peek("<props>") {
+        return Ok(None);
+    }
+
+    input.step("<props>")?;
+
+    let start = input.cursor;
+    let content = input.until("</props>", true)?;
+
+    let Props(props) = parse_str(&content)
+        .map_err(|e| input.error_at(start, &format!("expected props: {}", e)))?;
+
+    for (i, prop) in props.iter().enumerate() {
+        if props[..i].iter().any(|x| x.name == prop.name) {
+            return Err(input.error_at(start, &format!("prop `{}` is declared twice", prop.name)));
+        }
+    }
+
+    Ok(Some(props))
+}
+
+fn add_lifetime(ty: &mut Type) {
+    match ty {
+        Type::Reference(r) => {
+            r.lifetime
+                .get_or_insert_with(|| Lifetime::new("'a", Span::call_site()));
+            add_lifetime(&mut r.elem);
+        }
+        Type::Path(p) => {
+            for segment in p.path.segments.iter_mut() {
+                if let PathArguments::AngleBracketed(args) = &mut segment.arguments {
+                    for arg in args.args.iter_mut() {
+                        if let GenericArgument::Type(ty) = arg {
+                            add_lifetime(ty);
+                        }
+                    }
+                }
+            }
+        }
+        Type::Slice(s) => add_lifetime(&mut s.elem),
+        Type::Array(a) => add_lifetime(&mut a.elem),
+        Type::Paren(p) => add_lifetime(&mut p.elem),
+        Type::Group(g) => add_lifetime(&mut g.elem),
+        Type::Tuple(t) => {
+            for elem in t.elems.iter_mut() {
+                add_lifetime(elem);
+            }
+        }
+        _ => {}
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{super::ParseStream, parse};
+    use quote::ToTokens;
+
+    #[test]
+    fn test_props() {
+        let mut ps = ParseStream::new(
+            "<props>\n  article: &Article,\n  title: &str = \"Untitled\",\n  tags?: Vec<&str>,\n</props>"
+                .to_string(),
+        );
+
+        let props = parse(&mut ps).unwrap().unwrap();
+
+        assert_eq!(ps.cursor, ps.content.len());
+        assert_eq!(props.len(), 3);
+
+        assert_eq!(props[0].name, "article");
+        assert!(props[0].required());
+        assert_eq!(props[0].ty().to_token_stream().to_string(), "& 'a Article");
+
+        assert!(!props[1].required());
+        assert!(props[1].default.is_some());
+
+        assert!(props[2].optional);
+        assert!(!props[2].required());
+        assert_eq!(
+            props[2].ty().to_token_stream().to_string(),
+            "Vec < & 'a str >"
+        );
+    }
+
+    #[test]
+    fn test_no_props() {
+        let mut ps = ParseStream::new("<div></div>".to_string());
+
+        assert!(parse(&mut ps).unwrap().is_none());
+        assert_eq!(ps.cursor, 0);
+    }
+
+    #[test]
+    fn test_props_duplicate() {
+        let mut ps = ParseStream::new("<props>a: u8, a: u16</props>".to_string());
+
+        let err = parse(&mut ps).err().unwrap();
+
+        assert_eq!(err.message, "prop `a` is declared twice");
+    }
+}
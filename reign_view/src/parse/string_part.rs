@@ -68,7 +68,12 @@ impl StringPart {
                     let expr_until = cursor + end_matches.unwrap();
                     let expr_string = data.get(cursor..expr_until).unwrap();
 
-                    parts.push(StringPart::Expr(Code::parse_expr_from_str(
+                    // Point the errors in the expression to where it starts
+                    if !in_attr {
+                        input.cursor = cursor - 2;
+                    }
+
+                    parts.push(StringPart::Expr(Code::parse_interpolation_from_str(
                         input,
                         expr_string,
                     )?));
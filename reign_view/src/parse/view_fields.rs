@@ -1,18 +1,29 @@
+use crate::common::Signatures;
 use proc_macro2::TokenStream;
 use quote::quote;
-use std::collections::HashMap;
+use std::{collections::HashMap, rc::Rc};
 use syn::Ident;
 
 #[derive(Clone, Default)]
 pub struct ViewFields {
     pub fields: HashMap<Ident, Option<TokenStream>>,
+    /// Errors along with the cursor of the element they occurred in
+    pub errors: Vec<(Option<usize>, String)>,
+    /// Components used by the view
+    pub components: Vec<String>,
+    /// Scoped slots of the view along with the types of their values
+    pub slots: Vec<(String, Vec<TokenStream>)>,
+    /// Signatures of the other views which are used for the component attributes
+    pub signatures: Rc<Signatures>,
+    /// Whether only the fields which were already inserted are allowed
+    pub strict: bool,
+    /// Whether the code of the elements is preceded by a [`LOCATION`](super::LOCATION) marker
+    pub locations: bool,
 }
 
 impl ViewFields {
     pub fn new() -> Self {
-        ViewFields {
-            fields: HashMap::new(),
-        }
+        Self::default()
     }
 
     pub fn push(&mut self, ident: Ident) {
@@ -21,18 +32,37 @@ impl ViewFields {
 
     pub fn insert(&mut self, ident: Ident, tokens: Option<TokenStream>) {
         if let Some(ots) = self.fields.get(&ident) {
-            if ots.is_some() && tokens.is_some() {
+            if self.strict && tokens.is_some() {
+                self.errors.push((
+                    None,
+                    format!("prop `{}` already has a type in the props", ident),
+                ));
+            } else if ots.is_some() && tokens.is_some() {
                 // TODO: Unable to compare the syn::Type or TokenStream here
-                // TODO:(view:err) Show the error position
-                panic!("identifier `{}` has multiple type ascription hints", ident);
+                self.errors.push((
+                    None,
+                    format!("identifier `{}` has multiple type ascription hints", ident),
+                ));
             } else if ots.is_none() {
                 self.fields.insert(ident, tokens);
             }
+        } else if self.strict {
+            self.errors.push((
+                None,
+                format!("identifier `{}` is not declared in the props", ident),
+            ));
         } else {
             self.fields.insert(ident, tokens);
         }
     }
 
+    /// Sets the position of the errors which do not have one yet.
+    pub fn locate(&mut self, cursor: usize) {
+        for error in &mut self.errors {
+            error.0.get_or_insert(cursor);
+        }
+    }
+
     pub fn append(&mut self, other: ViewFields) {
         for field in other.fields {
             self.insert(field.0, field.1);
@@ -0,0 +1,35 @@
+use std::fmt::{Result, Write};
+
+/// Views which can be rendered without knowing their type.
+///
+/// It is implemented by all the views so that a list of different views can be given to
+/// `<component :is="...">`.
+pub trait Render {
+    fn render(&self, f: &mut dyn Write) -> Result;
+}
+
+impl<T: Render + ?Sized> Render for &T {
+    fn render(&self, f: &mut dyn Write) -> Result {
+        (**self).render(f)
+    }
+}
+
+impl<T: Render + ?Sized> Render for Box<T> {
+    fn render(&self, f: &mut dyn Write) -> Result {
+        (**self).render(f)
+    }
+}
+
+// The interpreter can't render the compiled views, so they are given to it as their HTML
+#[cfg(feature = "hot-reload")]
+impl<'a> serde::Serialize for dyn Render + 'a {
+    fn serialize<S>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        let mut html = String::new();
+
+        self.render(&mut html).map_err(serde::ser::Error::custom)?;
+        serializer.serialize_str(&html)
+    }
+}
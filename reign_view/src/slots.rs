@@ -11,13 +11,15 @@ where
     Box::new(f) as SlotRender
 }
 
-pub struct Slots<'a> {
+pub struct Slots<'a, S = ()> {
     pub templates: HashMap<&'a str, SlotRender<'a>>,
     pub children: SlotRender<'a>,
+    /// Templates given to the scoped slots which are called with the values of the slot
+    pub scoped: S,
     pub phantom: PhantomData<&'a str>,
 }
 
-impl<'a> Slots<'a> {
+impl<'a, S> Slots<'a, S> {
     pub fn render(&self, f: &mut dyn Write, name: &str) -> Result {
         if let Some(func) = self.templates.get(name) {
             func(f)
@@ -29,11 +31,12 @@ impl<'a> Slots<'a> {
     }
 }
 
-impl<'a> Default for Slots<'a> {
+impl<'a, S: Default> Default for Slots<'a, S> {
     fn default() -> Self {
         Slots {
             templates: HashMap::new(),
             children: slot_render(|_| Ok(())),
+            scoped: S::default(),
             phantom: PhantomData,
         }
     }
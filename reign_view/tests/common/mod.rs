@@ -1,9 +1,10 @@
 use pretty_assertions;
 use proc_macro2::TokenStream;
-use reign_view::parse::{parse, tokenize};
+use reign_view::{common::Signatures, parse::tokenize};
 use std::env;
 use std::fs::read_to_string;
 use std::path::PathBuf;
+use std::rc::Rc;
 
 #[derive(PartialEq, Eq)]
 #[doc(hidden)]
@@ -31,6 +32,11 @@ fn dir() -> PathBuf {
 }
 
 pub fn parse_pass(file_name: &str) {
+    parse_pass_with(file_name, Signatures::default());
+}
+
+/// Checks the code of a view which is built with the given signatures.
+pub fn parse_pass_with(file_name: &str, signatures: Signatures) {
     let mut fixture = dir();
     let mut output = dir();
 
@@ -41,14 +47,20 @@ pub fn parse_pass(file_name: &str) {
     let o = read_to_string(output).unwrap();
     let t: TokenStream = o.parse().unwrap();
 
-    let node = parse(f).unwrap();
-
     // TODO: test: Tokenstream should be converted to pretty formatted rust
-    eq!(&t.to_string(), &tokenize(node).0.to_string());
+    eq!(
+        &t.to_string(),
+        &tokenize(f, Rc::new(signatures)).unwrap().tokens.to_string()
+    );
     // eq!(&o.trim_end(), &tokenize(node).to_string());
 }
 
 pub fn parse_fail(file_name: &str) {
+    parse_fail_with(file_name, Signatures::default());
+}
+
+/// Checks the error of a view which uses components with the given signatures.
+pub fn parse_fail_with(file_name: &str, signatures: Signatures) {
     let mut fixture = dir();
     let mut errlog = dir();
 
@@ -58,5 +70,8 @@ pub fn parse_fail(file_name: &str) {
     let f = read_to_string(fixture).unwrap().replace("\r\n", "\n");
     let e = read_to_string(errlog).unwrap().replace("\r\n", "\n");
 
-    eq!(&e, &format!("{:?}", parse(f).unwrap_err()));
+    eq!(
+        &e,
+        &format!("{:?}", tokenize(f, Rc::new(signatures)).err().unwrap())
+    );
 }
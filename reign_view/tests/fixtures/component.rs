@@ -24,6 +24,7 @@ write!(
                 write!(f, "{}", "\n")? ;
                 Ok(())
             }),
+            scoped: ::std::default::Default::default(),
             phantom: ::std::marker::PhantomData,
         },
         a: "b",
@@ -17,6 +17,7 @@ write!(f, "{}", crate::views::Page {
             write!(f, "{}", "\n" )? ;
             Ok(())
         }),
+        scoped: ::std::default::Default::default(),
         phantom: ::std::marker::PhantomData,
     },
 })? ;
@@ -0,0 +1,10 @@
+write!(f, "{}", "<div")? ;
+write!(f, ">")? ;
+write!(f, "{}", "\n  ")? ;
+for widget in self.widgets {
+    ::reign::view::Render::render(&(widget), f)? ;
+}
+write!(f, "{}", "\n  ")? ;
+::reign::view::Render::render(&(self.footer), f)? ;
+write!(f, "{}", "\n")? ;
+write!(f, "{}", "</div>")? ;
@@ -0,0 +1,15 @@
+write!(f, "{}", "<p")? ;
+write!(f, " {}=\"{}\"", "title", format!("{}", ::reign::view::filters::lower(&(self.name))))? ;
+write!(f, ">")? ;
+write!(
+    f,
+    "{}{}{}{}{}{}{}",
+    "\n  ",
+    ::reign::view::filters::upper(&(::reign::view::filters::truncate(&(self.title), 10))),
+    " ",
+    crate::filters::shout(&(::reign::view::filters::currency(&(self.price), self.sign))),
+    " ",
+    self.a || self.b,
+    "\n"
+)? ;
+write!(f, "{}", "</p>")? ;
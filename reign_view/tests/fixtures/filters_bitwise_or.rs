@@ -0,0 +1,12 @@
+write!(f, "{}", "<p")? ;
+write!(f, ">")? ;
+write!(
+    f,
+    "{}{}{}{}{}",
+    self.is_admin | self.is_owner,
+    " ",
+    ::reign::view::filters::upper(&(self.a | self.b)),
+    " ",
+    self.flags | self.mask(self.x)
+)? ;
+write!(f, "{}", "</p>")? ;
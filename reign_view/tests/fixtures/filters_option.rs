@@ -0,0 +1,12 @@
+write!(f, "{}", "<p")? ;
+write!(f, ">")? ;
+write!(
+    f,
+    "{}{}{}{}{}",
+    ::reign::view::filters::default(&(::reign::view::filters::date(&(self.created_at), "%Y-%m-%d")), "n/a"),
+    " ",
+    self.flags | self.MASK,
+    " ",
+    self.flags | crate::MASK
+)? ;
+write!(f, "{}", "</p>")? ;
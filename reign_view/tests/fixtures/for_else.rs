@@ -0,0 +1,26 @@
+write!(f, "{}", "<ul")? ;
+write!(f, ">")? ;
+write!(f, "{}", "\n  ")? ;
+let mut iter = ::std::iter::IntoIterator::into_iter(self.users).peekable();
+let mut index: usize = 0;
+while let Some(user) = iter.next() {
+    let meta = ::reign::view::Loop {
+        index: index,
+        first: index == 0,
+        last: iter.peek().is_none(),
+    };
+    write!(f, "{}", "<li")? ;
+    write!(f, " {}=\"{}\"", "class", meta.first)? ;
+    write!(f, ">")? ;
+    write!(f, "{}{}", meta.index, user)? ;
+    write!(f, "{}", "</li>")? ;
+    index += 1;
+}
+if index != 0 {} else {
+    write!(f, "{}", "<li")? ;
+    write!(f, ">")? ;
+    write!(f, "{}", "none")? ;
+    write!(f, "{}", "</li>")? ;
+}
+write!(f, "{}", "\n")? ;
+write!(f, "{}", "</ul>")? ;
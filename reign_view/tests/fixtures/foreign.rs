@@ -0,0 +1,29 @@
+write!(f, "{}", "<div")? ;
+write!(f, ">")? ;
+write!(f, "{}", "\n  ")? ;
+write!(f, "{}", "<svg")? ;
+write!(f, " {}=\"{}\"", "viewBox", "0 0 10 10")? ;
+write!(f, ">")? ;
+write!(f, "{}", "\n    ")? ;
+write!(f, "{}", "<lineargradient")? ;
+write!(f, " {}=\"{}\"", "id", "fade")? ;
+write!(f, ">")? ;
+write!(f, "{}", "<stop")? ;
+write!(f, " {}=\"{}\"", "offset", "0")? ;
+write!(f, ">")? ;
+write!(f, "{}", "</stop>")? ;
+write!(f, "{}", "</lineargradient>")? ;
+write!(f, "{}", "\n    ")? ;
+write!(f, "{}", "<circle")? ;
+write!(f, " {}=\"{}\"", "r", self.radius)? ;
+write!(f, ">")? ;
+write!(f, "{}", "</circle>")? ;
+write!(f, "{}", "\n  ")? ;
+write!(f, "{}", "</svg>")? ;
+write!(f, "{}", "\n  ")? ;
+write!(f, "{}", "<user-card")? ;
+write!(f, " {}=\"{}\"", "name", self.name)? ;
+write!(f, ">")? ;
+write!(f, "{}", "</user-card>")? ;
+write!(f, "{}", "\n")? ;
+write!(f, "{}", "</div>")? ;
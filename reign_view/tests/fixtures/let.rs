@@ -0,0 +1,23 @@
+{
+    let name = self.user.name.to_uppercase();
+    write!(f, "{}", "<div")? ;
+    write!(f, ">")? ;
+    write!(f, "{}", "\n  ")? ;
+    write!(f, "{}", "<p")? ;
+    write!(f, " {}=\"{}\"", "title", name)? ;
+    write!(f, ">")? ;
+    write!(f, "{}", name)? ;
+    write!(f, "{}", "</p>")? ;
+    write!(f, "{}", "\n  ")? ;
+    for item in self.items {
+        {
+            let (a, b) = item;
+            write!(f, "{}", "<span")? ;
+            write!(f, ">")? ;
+            write!(f, "{}{}{}", a, b, name)? ;
+            write!(f, "{}", "</span>")? ;
+        }
+    }
+    write!(f, "{}", "\n")? ;
+    write!(f, "{}", "</div>")? ;
+}
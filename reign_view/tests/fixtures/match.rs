@@ -0,0 +1,25 @@
+write!(f, "{}", "<div")? ;
+write!(f, ">")? ;
+match &(self.status) {
+    Status::Draft => {
+        write!(f, "{}", "<b")? ;
+        write!(f, ">")? ;
+        write!(f, "{}", "draft")? ;
+        write!(f, "{}", "</b>")? ;
+    }
+    Status::Published(date) if !self.hidden => {
+        write!(f, "{}", "<b")? ;
+        write!(f, ">")? ;
+        write!(f, "{}", date)? ;
+        write!(f, "{}", "</b>")? ;
+    }
+    Status::Archived { reason, .. } => {
+        write!(f, "{}", "<b")? ;
+        write!(f, ">")? ;
+        write!(f, "{}", reason)? ;
+        write!(f, "{}", "</b>")? ;
+    }
+    #[allow(unreachable_patterns)]
+    _ => {}
+}
+write!(f, "{}", "</div>")? ;
@@ -0,0 +1,38 @@
+write!(f, "{}", "<div")? ;
+write!(f, ">")? ;
+write!(f, "{}", "\n  ")? ;
+write!(f, "{}", "<ul")? ;
+write!(f, ">")? ;
+write!(f, "{}", "\n    ")? ;
+write!(f, "{}", "<li")? ;
+write!(f, ">")? ;
+write!(f, "{}", "One\n    ")? ;
+write!(f, "{}", "</li>")? ;
+write!(f, "{}", "<li")? ;
+write!(f, " {}=\"{}\"", "class", self.kind)? ;
+write!(f, ">")? ;
+write!(f, "{}", "Two\n  ")? ;
+write!(f, "{}", "</li>")? ;
+write!(f, "{}", "</ul>")? ;
+write!(f, "{}", "\n  ")? ;
+write!(f, "{}", "<p")? ;
+write!(f, ">")? ;
+write!(f, "{}", "Text\n  ")? ;
+write!(f, "{}", "</p>")? ;
+write!(f, "{}", "<table")? ;
+write!(f, ">")? ;
+write!(f, "{}", "\n    ")? ;
+write!(f, "{}", "<tr")? ;
+write!(f, ">")? ;
+write!(f, "{}", "<td")? ;
+write!(f, ">")? ;
+write!(f, "{}", "A")? ;
+write!(f, "{}", "</td>")? ;
+write!(f, "{}", "<td")? ;
+write!(f, ">")? ;
+write!(f, "{}", "B\n  ")? ;
+write!(f, "{}", "</td>")? ;
+write!(f, "{}", "</tr>")? ;
+write!(f, "{}", "</table>")? ;
+write!(f, "{}", "\n")? ;
+write!(f, "{}", "</div>")? ;
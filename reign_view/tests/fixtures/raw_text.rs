@@ -0,0 +1,20 @@
+write!(f, "{}", "<div")? ;
+write!(f, ">")? ;
+write!(f, "{}", "\n  ")? ;
+write!(f, "{}", "<style")? ;
+write!(f, ">")? ;
+write!(f, "{}", "p > a { color: red; }")? ;
+write!(f, "{}", "</style>")? ;
+write!(f, "{}", "\n  ")? ;
+write!(f, "{}", "<script")? ;
+write!(f, ">")? ;
+write!(f, "{}", "if (a < b && c) { render(\"{{ not_a_field }}\"); }")? ;
+write!(f, "{}", "</script>")? ;
+write!(f, "{}", "\n  ")? ;
+write!(f, "{}", "<textarea")? ;
+write!(f, " {}=\"{}\"", "name", self.name)? ;
+write!(f, ">")? ;
+write!(f, "{}{}{}{}{}{}", self.body, " ", "<", "b>left as it is", "<", "/b>")? ;
+write!(f, "{}", "</textarea>")? ;
+write!(f, "{}", "\n")? ;
+write!(f, "{}", "</div>")? ;
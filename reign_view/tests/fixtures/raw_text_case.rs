@@ -0,0 +1,14 @@
+write!(f, "{}", "<div")? ;
+write!(f, ">")? ;
+write!(f, "{}", "\n  ")? ;
+write!(f, "{}", "<script")? ;
+write!(f, ">")? ;
+write!(f, "{}", "x = \"</scripts\"; y = \"</scriptx\";")? ;
+write!(f, "{}", "</script>")? ;
+write!(f, "{}", "\n  ")? ;
+write!(f, "{}", "<textarea")? ;
+write!(f, ">")? ;
+write!(f, "{}", self.body)? ;
+write!(f, "{}", "</textarea>")? ;
+write!(f, "{}", "\n")? ;
+write!(f, "{}", "</div>")? ;
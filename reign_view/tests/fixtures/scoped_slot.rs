@@ -0,0 +1,15 @@
+write!(f, "{}", "<ul")? ;
+write!(f, ">")? ;
+write!(f, "{}", "\n  ")? ;
+for row in self.rows {
+    write!(f, "{}", "<li")? ;
+    write!(f, ">")? ;
+    write!(f, "{}", "\n    ")? ;
+    if let Some(slot) = &self._slots.scoped.row {
+        slot(f, row, row.label.as_str())? ;
+    }
+    write!(f, "{}", "\n  ")? ;
+    write!(f, "{}", "</li>")? ;
+}
+write!(f, "{}", "\n")? ;
+write!(f, "{}", "</ul>")? ;
@@ -1,5 +1,7 @@
 mod common;
 
+use reign_view::{common::Signatures, parse::tokenize};
+
 #[test]
 fn test_comment() {
     common::parse_pass("comment");
@@ -80,7 +82,156 @@ fn test_slot() {
     common::parse_pass("slot");
 }
 
-// #[test]
-// fn test_else_without_if() {
-//     common::parse_fail("else_without_if");
-// }
+#[test]
+fn test_else_without_if() {
+    common::parse_fail("else_without_if");
+}
+
+#[test]
+fn test_slot_name_bad() {
+    common::parse_fail("slot_name_bad");
+}
+
+#[test]
+fn test_type_ascription_bad() {
+    common::parse_fail("type_ascription_bad");
+}
+
+#[test]
+fn test_for_else() {
+    common::parse_pass("for_else");
+}
+
+#[test]
+fn test_match() {
+    common::parse_pass("match");
+}
+
+#[test]
+fn test_match_bad() {
+    common::parse_fail("match_bad");
+}
+
+#[test]
+fn test_case_without_match() {
+    common::parse_fail("case_without_match");
+}
+
+#[test]
+fn test_let() {
+    common::parse_pass("let");
+}
+
+#[test]
+fn test_scoped_slot() {
+    common::parse_pass("scoped_slot");
+}
+
+#[test]
+fn test_scoped_slot_untyped() {
+    common::parse_fail("scoped_slot_untyped");
+}
+
+#[test]
+fn test_scoped_slot_unknown() {
+    let mut signatures = Signatures::default();
+
+    signatures
+        .slots
+        .insert("users".to_string(), vec!["row".to_string()]);
+    common::parse_fail_with("scoped_slot_unknown", signatures);
+}
+
+#[test]
+fn test_scoped_slot_field_type() {
+    let view = "<props>count: u32</props><p><slot name=\"head\" :count=\"count\"></slot></p>";
+    let tokenized = tokenize(view.to_string(), Default::default()).unwrap();
+
+    assert_eq!(tokenized.slots[0].1[0].to_string(), "u32");
+}
+
+#[test]
+fn test_dynamic_component() {
+    common::parse_pass("dynamic_component");
+}
+
+#[test]
+fn test_component_is_bad() {
+    common::parse_fail("component_is_bad");
+}
+
+#[test]
+fn test_filters() {
+    common::parse_pass_with(
+        "filters",
+        Signatures {
+            filters: vec!["shout".to_string()],
+            ..Default::default()
+        },
+    );
+}
+
+#[test]
+fn test_filters_bitwise_or() {
+    common::parse_pass("filters_bitwise_or");
+}
+
+#[test]
+fn test_filters_option() {
+    common::parse_pass("filters_option");
+}
+
+#[test]
+fn test_filter_bad() {
+    common::parse_fail("filter_bad");
+}
+
+#[test]
+fn test_optional_end_tags() {
+    common::parse_pass("optional_end_tags");
+}
+
+#[test]
+fn test_raw_text() {
+    common::parse_pass("raw_text");
+}
+
+#[test]
+fn test_raw_text_case() {
+    common::parse_pass("raw_text_case");
+}
+
+#[test]
+fn test_raw_text_bad() {
+    common::parse_fail("raw_text_bad");
+}
+
+#[test]
+fn test_foreign() {
+    common::parse_pass("foreign");
+}
+
+fn badge() -> Signatures {
+    let mut signatures = Signatures::default();
+
+    signatures.props.insert(
+        "badge".to_string(),
+        vec![("count".to_string(), true), ("color".to_string(), false)],
+    );
+    signatures
+}
+
+#[test]
+fn test_prop_unknown() {
+    common::parse_fail_with("prop_unknown", badge());
+}
+
+#[test]
+fn test_prop_missing() {
+    common::parse_fail_with("prop_missing", badge());
+}
+
+#[test]
+fn test_props_bad() {
+    common::parse_fail("props_bad");
+}
@@ -1,8 +1,8 @@
 use clap::Parser;
 use reign_task::oclif::finish;
 
-// mod server;
 mod new;
+mod server;
 mod tasks;
 
 #[derive(Debug, Parser)]
@@ -15,8 +15,8 @@ struct Reign {
 #[derive(Debug, Parser)]
 enum ReignSubcommand {
     New(new::New),
-    // #[clap(alias = "s")]
-    // Server(server::Server),
+    #[clap(alias = "s")]
+    Server(server::Server),
     Tasks(tasks::Tasks),
     #[clap(external_subcommand)]
     Other(Vec<String>),
@@ -27,7 +27,7 @@ fn main() {
 
     let result = match program.cmd {
         ReignSubcommand::New(x) => x.run(),
-        // ReignSubcommand::Server(x) => x.run(),
+        ReignSubcommand::Server(x) => x.run(),
         ReignSubcommand::Tasks(x) => x.run(),
         ReignSubcommand::Other(x) => tasks::run_task(x),
     };
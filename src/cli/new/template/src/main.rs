@@ -5,7 +5,7 @@ use reign::{
 };
 use reign_plugin_static::StaticPlugin;
 
-views!("src", "views");
+views!("src", "views", layout = layouts::application);
 
 mod controllers;
 
@@ -0,0 +1,69 @@
+use reign_task::oclif::term::{ERR_GREEN_BOLD, ERR_YELLOW_BOLD, TERM_ERR};
+
+use std::{
+    io,
+    process::{Child, Command},
+};
+
+/// The app process being run by the server.
+pub struct App {
+    features: String,
+    process: Option<Child>,
+}
+
+impl App {
+    pub fn new(features: &[String]) -> Self {
+        let mut all = vec!["reign/hot-reload".to_string()];
+
+        all.extend(features.iter().cloned());
+
+        Self {
+            features: all.join(","),
+            process: None,
+        }
+    }
+
+    fn cargo(&self, command: &str) -> Command {
+        let mut cargo = Command::new("cargo");
+
+        cargo.args([command, "--features", &self.features]);
+        cargo
+    }
+
+    /// Rebuilds the app and restarts it if the build succeeds. The previous process is kept
+    /// running if the build fails. Only running cargo can fail.
+    pub fn restart(&mut self) -> io::Result<()> {
+        let status = self.cargo("build").status()?;
+
+        if !status.success() {
+            let _ = TERM_ERR.write_line(&format!(
+                "    {} build failed, waiting for changes",
+                ERR_YELLOW_BOLD.apply_to("error")
+            ));
+
+            return Ok(());
+        }
+
+        self.stop();
+
+        let _ = TERM_ERR.write_line(&format!("    {} app", ERR_GREEN_BOLD.apply_to("start")));
+
+        self.process = Some(self.cargo("run").arg("--quiet").spawn()?);
+
+        Ok(())
+    }
+
+    fn stop(&mut self) {
+        if let Some(mut process) = self.process.take() {
+            // The process might have already exited by itself
+            let _ = process.kill();
+            let _ = process.wait();
+        }
+    }
+}
+
+impl Drop for App {
+    fn drop(&mut self) {
+        self.stop();
+    }
+}
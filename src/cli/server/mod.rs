@@ -1,104 +1,125 @@
-use crate::server::view::{has_any_view_files, is_view_folder, parse};
+use crate::server::{app::App, view::Views};
 
 use clap::Parser;
 use notify::{
-    event::{CreateKind, DataChange, EventKind, ModifyKind, RemoveKind},
-    Error as NotifyError, Event, RecommendedWatcher, RecursiveMode, Watcher,
+    event::ModifyKind, recommended_watcher, Event, EventKind, RecursiveMode,
+    Result as NotifyResult, Watcher,
 };
 use reign_task::{
-    term::{RED_BOLD, TERM_ERR},
+    oclif::term::{ERR_GREEN_BOLD, ERR_YELLOW_BOLD, TERM_ERR},
     Error,
 };
 
 use std::{
     ffi::OsStr,
+    io,
     path::{Path, PathBuf},
-    process::Command,
+    sync::mpsc::{channel, Receiver, RecvTimeoutError},
+    time::Duration,
 };
 
+mod app;
 mod view;
-mod write;
 
-// TODO:(cli) Live reload not seeming to help compile time
-
-// TODO:(cli) view directory
-// TODO:(cli) features for using when building/running
-// TODO:(cli) debounce events
-// TODO:(cli) rename folder/file
-
-/// Start the Reign server
+/// Start the Reign server which rebuilds and restarts the app on changes
 #[derive(Debug, Parser)]
-pub struct Server {}
+pub struct Server {
+    /// Features of the app to activate along with `reign/hot-reload`
+    #[clap(short = 'F', long)]
+    pub features: Vec<String>,
+
+    /// Milliseconds to wait for more changes before rebuilding
+    #[clap(long, default_value = "200")]
+    pub debounce: u64,
+}
 
 impl Server {
+    // The error is shared with the other commands because `finish` reports it
+    #[allow(clippy::result_large_err)]
     pub fn run(&self) -> Result<(), Error> {
-        let cargo = Path::new("Cargo.toml");
+        check_path(Path::new("Cargo.toml"))?;
+
+        let src = check_path(Path::new("src"))?;
+        let debounce = Duration::from_millis(self.debounce);
 
-        check_path(cargo, "Cargo.toml")?;
+        let mut views = Views::new(&src.join("views"));
+        let mut app = App::new(&self.features);
 
-        let src = Path::new("src");
-        let views = src.join("views");
+        app.restart().map_err(|_| Error::Cargo)?;
 
-        check_path(src, "src")?;
-        check_path(&views, "src/views")?;
+        let (sender, receiver) = channel();
+        let mut watcher = recommended_watcher(sender).map_err(|e| Error::Other(e.into()))?;
 
-        // TODO: cli: Maintain dep graph to reload views that depend on views and build
-        // just those views instead of rebuilding everything. Also for views that changed
-        // maintain manifest such that manifest is updated only if idents changed
+        watcher
+            .watch(&src, RecursiveMode::Recursive)
+            .map_err(|e| Error::Other(e.into()))?;
 
-        let _view_watcher = self.view_watcher(&views)?;
+        loop {
+            let paths = changes(&receiver, debounce)?;
 
-        Command::new("cargo")
-            .args(&["watch", "-x", "run --features reign/hot-reload"])
-            .status()?;
+            // Views are interpreted at runtime, so they only need a rebuild when
+            // their fields have changed because `render!` captures them
+            let views_changed = views.update(&paths);
 
-        Ok(())
+            if views_changed || has_any_rust_files(&paths) {
+                app.restart().map_err(|_| Error::Cargo)?;
+            } else if views.contains_any(&paths) {
+                TERM_ERR.write_line(&format!("    {} views", ERR_GREEN_BOLD.apply_to("reload")))?;
+            }
+        }
     }
+}
 
-    fn view_watcher(&self, views: &Path) -> Result<RecommendedWatcher, Error> {
-        let full_path = views.canonicalize()?;
-
-        parse(views)?;
-
-        let mut watcher: RecommendedWatcher =
-            Watcher::new_immediate(move |res: Result<Event, NotifyError>| match res {
-                Ok(event) => match event.kind {
-                    EventKind::Modify(ModifyKind::Data(DataChange::Content))
-                    | EventKind::Create(CreateKind::File)
-                    | EventKind::Remove(RemoveKind::File)
-                        if has_any_view_files(&full_path, &event.paths) =>
-                    {
-                        parse(&full_path).unwrap();
-                    }
-                    EventKind::Remove(RemoveKind::Folder)
-                        if is_view_folder(&full_path, &event.paths) =>
-                    {
-                        parse(&full_path).unwrap();
-                    }
-                    _ => {}
-                },
-                Err(e) => println!("watch error: {:?}", e),
-            })?;
-
-        watcher.watch(views, RecursiveMode::Recursive)?;
-        Ok(watcher)
+/// Waits for a change and collects all the changes until there is a pause of the given duration.
+fn changes(
+    receiver: &Receiver<NotifyResult<Event>>,
+    debounce: Duration,
+) -> io::Result<Vec<PathBuf>> {
+    let mut paths = vec![];
+    let mut event = receiver
+        .recv()
+        .map_err(|e| io::Error::new(io::ErrorKind::BrokenPipe, e))?;
+
+    loop {
+        match event {
+            Ok(event) => match event.kind {
+                EventKind::Create(_)
+                | EventKind::Remove(_)
+                | EventKind::Modify(ModifyKind::Any)
+                | EventKind::Modify(ModifyKind::Data(_))
+                | EventKind::Modify(ModifyKind::Name(_)) => paths.extend(event.paths),
+                _ => {}
+            },
+            Err(e) => {
+                TERM_ERR.write_line(&format!("    {} {}", ERR_YELLOW_BOLD.apply_to("watch"), e))?
+            }
+        }
+
+        event = match receiver.recv_timeout(debounce) {
+            Ok(event) => event,
+            Err(RecvTimeoutError::Timeout) | Err(RecvTimeoutError::Disconnected) => break,
+        };
     }
+
+    paths.sort();
+    paths.dedup();
+
+    Ok(paths)
 }
 
-fn check_path(path: &Path, name: &str) -> Result<(), Error> {
-    if let Err(e) = path.canonicalize() {
-        TERM_ERR.write_line(&format!(
+fn check_path(path: &Path) -> io::Result<PathBuf> {
+    path.canonicalize().map_err(|e| {
+        let _ = TERM_ERR.write_line(&format!(
             "    {} reading {}",
-            RED_BOLD.apply_to("error"),
-            name
-        ))?;
-        eprintln!("{}", e);
-    }
+            ERR_YELLOW_BOLD.apply_to("error"),
+            path.display()
+        ));
 
-    Ok(())
+        e
+    })
 }
 
-fn _has_any_rust_files(paths: &[PathBuf]) -> bool {
+fn has_any_rust_files(paths: &[PathBuf]) -> bool {
     let rs = OsStr::new("rs");
 
     paths
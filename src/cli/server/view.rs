@@ -1,82 +1,218 @@
-use crate::{
-    server::write::{write_file, write_manifest},
-    INTERNAL_ERR,
-};
-
-use inflector::cases::pascalcase::to_pascal_case;
-use proc_macro2::{Ident, Span};
-use quote::quote;
-use reign_task::Error;
-use reign_view::common::{recurse, Manifest, FILE_REGEX, FOLDER_REGEX};
+use reign_task::oclif::term::{ERR_YELLOW_BOLD, TERM_ERR};
+use reign_view::common::{recurse, tokenize_view, Manifest, FILE_REGEX, FOLDER_REGEX};
 
 use std::path::{Path, PathBuf};
 
-pub fn parse(views: &Path) -> Result<(), Error> {
-    let manifest = parse_all_views(views)?;
+/// Fields of all the views which are captured by the `render!` macro.
+pub struct Views {
+    dir: PathBuf,
+    manifest: Manifest,
+}
+
+impl Views {
+    pub fn new(dir: &Path) -> Self {
+        Self {
+            dir: dir.to_path_buf(),
+            manifest: manifest(dir).unwrap_or_default(),
+        }
+    }
+
+    pub fn contains_any(&self, paths: &[PathBuf]) -> bool {
+        paths.iter().any(|path| path.starts_with(&self.dir))
+    }
+
+    /// Regenerates the changed views and returns whether the fields of any of them changed.
+    pub fn update(&mut self, paths: &[PathBuf]) -> bool {
+        let mut changed = false;
+        let mut folder_changed = false;
+
+        for path in paths {
+            let relative = match path.strip_prefix(&self.dir) {
+                Ok(relative) => relative,
+                Err(_) => continue,
+            };
+
+            if let Some(key) = view_key(relative) {
+                changed |= self.regenerate(path, key);
+            } else if is_view_folder(relative) {
+                folder_changed = true;
+            }
+        }
+
+        // Renaming or removing a folder does not give events for the views inside it
+        if folder_changed {
+            if let Some(manifest) = manifest(&self.dir) {
+                changed |= manifest != self.manifest;
+                self.manifest = manifest;
+            }
+        }
+
+        changed
+    }
+
+    fn regenerate(&mut self, path: &Path, key: String) -> bool {
+        if !path.is_file() {
+            return self.manifest.remove(&key).is_some();
+        }
+
+        let file_base_name = path
+            .file_stem()
+            .map(|x| x.to_string_lossy().to_string())
+            .unwrap_or_default();
+
+        match tokenize_view(path, &file_base_name) {
+            Ok((_, idents)) => {
+                let mut fields: Vec<_> = idents.iter().map(|x| (x.0.to_string(), x.1)).collect();
+
+                fields.sort();
+                self.manifest.insert(key, fields.clone()).as_ref() != Some(&fields)
+            }
+            Err(e) => {
+                let _ = TERM_ERR.write_line(&format!(
+                    "    {} parsing {}{}",
+                    ERR_YELLOW_BOLD.apply_to("error"),
+                    path.display(),
+                    e
+                ));
 
-    write_manifest(views, &manifest)?;
-    Ok(())
+                false
+            }
+        }
+    }
 }
 
-fn parse_all_views(views: &Path) -> Result<Manifest, Error> {
+fn manifest(dir: &Path) -> Option<Manifest> {
+    if !dir.is_dir() {
+        return Some(Manifest::new());
+    }
+
     let mut manifest = Manifest::new();
 
     recurse(
-        &views,
-        "",
+        dir,
+        None,
+        &[],
         &mut manifest,
-        |ident, _| Ok(quote! { pub mod #ident; }),
-        |path, file_base_name, file| {
-            write_file(
-                &path.join(format!("{}.rs", file_base_name)),
-                file.to_string(),
-            )?;
-
-            let file_name = Ident::new(file_base_name, Span::call_site());
-            let ident = Ident::new(&to_pascal_case(file_base_name), Span::call_site());
-
-            Ok(quote! {
-                mod #file_name;
-                pub use #file_name::#ident;
-            })
-        },
-        |path, views| {
-            write_file(&path.join("mod.rs"), quote! { #(#views)* }.to_string())?;
-
-            Ok(vec![])
-        },
-    )?;
-
-    Ok(manifest)
+        |_, _| Ok(Default::default()),
+        |_, _, _, _| Ok(Default::default()),
+        |_, _| Ok(vec![]),
+    )
+    .ok()?;
+
+    // Fields are not collected in any particular order
+    for fields in manifest.values_mut() {
+        fields.sort();
+    }
+
+    Some(manifest)
 }
 
-pub fn is_view_folder(full_path: &Path, paths: &[PathBuf]) -> bool {
-    paths.iter().any(|path| {
-        path.strip_prefix(full_path)
-            .expect(INTERNAL_ERR)
-            .components()
-            .map(|c| c.as_os_str().to_string_lossy())
-            .all(|x| FOLDER_REGEX.is_match(&x))
-    })
+fn components(relative: &Path) -> Vec<String> {
+    relative
+        .components()
+        .map(|c| c.as_os_str().to_string_lossy().to_string())
+        .collect()
 }
 
-pub fn has_any_view_files(full_path: &Path, paths: &[PathBuf]) -> bool {
-    let mut has = false;
-
-    for path in paths {
-        if let Some((last, view)) = path
-            .strip_prefix(full_path)
-            .expect(INTERNAL_ERR)
-            .components()
-            .map(|c| c.as_os_str().to_string_lossy())
-            .collect::<Vec<_>>()
-            .split_last()
-        {
-            if FILE_REGEX.is_match(&last) && view.iter().all(|x| FOLDER_REGEX.is_match(&x)) {
-                has = true;
-            }
-        }
+/// Returns the key of the view in the manifest if the path is a view file.
+fn view_key(relative: &Path) -> Option<String> {
+    let components = components(relative);
+    let (last, folders) = components.split_last()?;
+
+    if !FILE_REGEX.is_match(last) || !folders.iter().all(|x| FOLDER_REGEX.is_match(x)) {
+        return None;
     }
 
-    has
+    let mut parts = folders.to_vec();
+
+    parts.push(last.trim_end_matches(".html").to_string());
+    Some(parts.join(":"))
+}
+
+fn is_view_folder(relative: &Path) -> bool {
+    relative.extension().is_none()
+        && components(relative)
+            .iter()
+            .all(|x| FOLDER_REGEX.is_match(x))
+}
+
+#[cfg(test)]
+mod test {
+    use super::{is_view_folder, view_key, Views};
+
+    use std::{
+        env::temp_dir,
+        fs::{create_dir_all, remove_dir_all, remove_file, write},
+        path::{Path, PathBuf},
+    };
+
+    fn dir(name: &str) -> PathBuf {
+        let dir = temp_dir().join(format!("reign_server_views_{}", name));
+
+        let _ = remove_dir_all(&dir);
+        create_dir_all(dir.join("users")).unwrap();
+        write(dir.join("app.html"), "<p>{{ name }}</p>").unwrap();
+        write(dir.join("users").join("avatar.html"), "<img :src=\"url\" />").unwrap();
+        dir
+    }
+
+    #[test]
+    fn test_view_key() {
+        assert_eq!(view_key(Path::new("app.html")), Some("app".to_string()));
+        assert_eq!(
+            view_key(Path::new("users/small_avatar.html")),
+            Some("users:small_avatar".to_string())
+        );
+        assert_eq!(view_key(Path::new("app.html.swp")), None);
+        assert_eq!(view_key(Path::new("notes.txt")), None);
+        assert_eq!(view_key(Path::new("_users/app.html")), None);
+    }
+
+    #[test]
+    fn test_is_view_folder() {
+        assert!(is_view_folder(Path::new("users")));
+        assert!(is_view_folder(Path::new("users/admins")));
+        assert!(!is_view_folder(Path::new("users/app.html")));
+        assert!(!is_view_folder(Path::new("notes.txt")));
+    }
+
+    #[test]
+    fn test_update_content() {
+        let dir = dir("content");
+        let app = [dir.join("app.html")];
+        let mut views = Views::new(&dir);
+
+        assert!(views.contains_any(&app));
+        assert!(!views.contains_any(&[PathBuf::from("src/main.rs")]));
+
+        write(&app[0], "<b>{{ name }}</b>").unwrap();
+        assert!(!views.update(&app));
+
+        write(&app[0], "<b>{{ name }} {{ title }}</b>").unwrap();
+        assert!(views.update(&app));
+
+        // Views which fail to parse keep their previous fields
+        write(&app[0], "<b>{{ name </b>").unwrap();
+        assert!(!views.update(&app));
+
+        write(dir.join("notes.txt"), "notes").unwrap();
+        assert!(!views.update(&[dir.join("notes.txt")]));
+    }
+
+    #[test]
+    fn test_update_files() {
+        let dir = dir("files");
+        let page = [dir.join("page.html")];
+        let mut views = Views::new(&dir);
+
+        write(&page[0], "<p>page</p>").unwrap();
+        assert!(views.update(&page));
+        assert!(!views.update(&page));
+
+        remove_file(&page[0]).unwrap();
+        assert!(views.update(&page));
+
+        remove_dir_all(dir.join("users")).unwrap();
+        assert!(views.update(&[dir.join("users")]));
+    }
 }
@@ -12,5 +12,7 @@ pub use reign_boot::*;
 pub use reign_model as model;
 #[cfg(feature = "router")]
 pub use reign_router as router;
+#[cfg(feature = "router")]
+pub use reign_router::test;
 #[cfg(feature = "view")]
 pub use reign_view as view;
@@ -15,6 +15,6 @@ pub use reign_model::diesel::Identifiable;
 pub use reign_router::helpers::json;
 #[cfg(feature = "router")]
 pub use reign_router::{
-    helpers::{redirect, render},
+    helpers::{redirect, render, respond_to},
     Error, OptionExt, Request, Response,
 };